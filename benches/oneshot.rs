@@ -1,6 +1,6 @@
 use std::io::Write;
 
-use pdf_writer::{Content, PdfWriter, Rect, Ref};
+use pdf_writer::{Content, Name, PdfWriter, Rect, Ref};
 
 fn bench_alloc() -> Vec<u8> {
     Vec::with_capacity(16)
@@ -59,7 +59,24 @@ fn bench_full() -> Vec<u8> {
     w.catalog(Ref::new(1));
     w.page(Ref::new(2)).media_box(Rect::new(0.0, 0.0, 595.0, 842.0));
     w.stream(Ref::new(3), &b"ABCDEFG"[..]);
-    w.finish()
+    w.into_buf()
+}
+
+fn bench_large_stream() -> Vec<u8> {
+    let data = vec![0x42; 1024 * 1024];
+    let mut w = PdfWriter::new();
+    w.stream(Ref::new(1), &data);
+    w.into_buf()
+}
+
+fn bench_many_dicts() -> Vec<u8> {
+    let mut w = PdfWriter::new();
+    for i in 1..=50_000 {
+        let mut dict = w.indirect(Ref::new(i)).dict();
+        dict.pair(Name(b"Type"), Name(b"Annot"));
+        dict.insert(Name(b"Border")).dict().pair(Name(b"W"), 1);
+    }
+    w.into_buf()
 }
 
 #[rustfmt::skip]
@@ -72,4 +89,6 @@ iai::main!(
     bench_content,
     bench_new,
     bench_full,
+    bench_large_stream,
+    bench_many_dicts,
 );
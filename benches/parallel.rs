@@ -0,0 +1,86 @@
+//! Measures how serializing the content streams of a large document into
+//! chunks scales with the number of threads.
+//!
+//! Run with `cargo bench --bench parallel`. The speedup is bounded by the
+//! number of available cores.
+
+use std::time::{Duration, Instant};
+
+use pdf_writer::{Chunk, Content, Name, PdfWriter, Ref, RefAllocator, Str};
+
+const PAGES: usize = 2000;
+const RUNS: usize = 5;
+
+fn main() {
+    println!("{} pages, best of {} runs", PAGES, RUNS);
+    let mut base = None;
+    for threads in [1, 2, 4, 8] {
+        let (serialize, total) = measure(threads);
+        let base = *base.get_or_insert(serialize);
+        let speedup = base.as_secs_f64() / serialize.as_secs_f64();
+        println!(
+            "{} threads: serialize {:>9.2?} ({:.2}x), total {:>9.2?}",
+            threads, serialize, speedup, total,
+        );
+    }
+}
+
+/// The fastest times of multiple runs with the given number of threads for
+/// serializing the chunks and for the whole document.
+fn measure(threads: usize) -> (Duration, Duration) {
+    let mut best = (Duration::MAX, Duration::MAX);
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        let chunks = serialize(threads);
+        let serialized = start.elapsed();
+        assert!(!assemble(chunks).is_empty());
+        best.0 = best.0.min(serialized);
+        best.1 = best.1.min(start.elapsed());
+    }
+    best
+}
+
+/// Serialize the content streams on the given number of threads.
+fn serialize(threads: usize) -> Vec<Chunk> {
+    let per_thread = (PAGES + threads - 1) / threads;
+    std::thread::scope(|s| {
+        let handles: Vec<_> = (0..threads)
+            .map(|t| {
+                s.spawn(move || {
+                    let mut chunk = Chunk::new();
+                    for i in t * per_thread..((t + 1) * per_thread).min(PAGES) {
+                        chunk.stream(Ref::new(1 + i as i32), &content(i));
+                    }
+                    chunk
+                })
+            })
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    })
+}
+
+/// Renumber the chunks and add them to a writer sequentially.
+fn assemble(chunks: Vec<Chunk>) -> Vec<u8> {
+    let mut alloc = RefAllocator::new();
+    let mut writer = PdfWriter::new();
+    for mut chunk in chunks {
+        chunk.renumber(|_| alloc.bump());
+        writer.extend(&chunk);
+    }
+    writer.into_buf()
+}
+
+/// Create a content stream with lots of text.
+fn content(page: usize) -> Vec<u8> {
+    let mut content = Content::new();
+    content.begin_text();
+    content.set_font(Name(b"F1"), 10.0);
+    content.next_line(50.0, 800.0);
+    for line in 0..70 {
+        content.next_line(0.0, -11.0);
+        let text = format!("This is line {} on page {}.", line + 1, page + 1);
+        content.show(Str(text.as_bytes()));
+    }
+    content.end_text();
+    content.finish()
+}
@@ -3,7 +3,7 @@
 use pdf_writer::types::{ActionType, AnnotationType, BorderType};
 use pdf_writer::{Content, Finish, Name, PdfWriter, Rect, Ref, Str, TextStr};
 
-fn main() -> std::io::Result<()> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Start writing.
     let mut writer = PdfWriter::new();
 
@@ -87,8 +87,9 @@ fn main() -> std::io::Result<()> {
 
     // Finish writing (this automatically creates the cross-reference table and
     // file trailer) and retrieve the resulting byte buffer.
-    let buf: Vec<u8> = writer.finish();
+    let buf: Vec<u8> = writer.finish()?;
 
     // Write the thing to a file.
-    std::fs::write("target/hello.pdf", buf)
+    std::fs::write("target/hello.pdf", buf)?;
+    Ok(())
 }
@@ -3,7 +3,7 @@
 use pdf_writer::writers::ColorSpace;
 use pdf_writer::{Content, Finish, Name, PdfWriter, Rect, Ref};
 
-fn main() -> std::io::Result<()> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Start writing.
     let mut writer = PdfWriter::new();
 
@@ -56,8 +56,9 @@ fn main() -> std::io::Result<()> {
     // Set the fill color in the current color space. Note that only the
     // `set_fill_color` and `set_stroke_color` operators will use custom color
     // spaces. The `set_fill_rgb`, `set_fill_cmyk` and `set_fill_gray` operators
-    // will always use the non-calibrated Device color spaces, the same applies
-    // to the stroke color operators.
+    // use the non-calibrated Device color spaces unless the resources define
+    // replacements with `default_rgb` and friends. The same applies to the
+    // stroke color operators.
     content.set_fill_color([0.0, 1.0, 0.0]);
     // Draw a green rectangle at the top of the page.
     content.rect(108.0, 734.0, 100.0, 100.0);
@@ -104,5 +105,6 @@ fn main() -> std::io::Result<()> {
     icc_profile.finish();
 
     // Write the thing to a file.
-    std::fs::write("target/icc.pdf", writer.finish())
+    std::fs::write("target/icc.pdf", writer.finish()?)?;
+    Ok(())
 }
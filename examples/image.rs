@@ -4,7 +4,7 @@ use image::{ColorType, GenericImageView, ImageFormat};
 use miniz_oxide::deflate::{compress_to_vec_zlib, CompressionLevel};
 use pdf_writer::{Content, Filter, Finish, Name, PdfWriter, Rect, Ref};
 
-fn main() -> std::io::Result<()> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Start writing.
     let mut writer = PdfWriter::new();
 
@@ -87,7 +87,7 @@ fn main() -> std::io::Result<()> {
 
     // Add SMask if the image has transparency.
     if let Some(encoded) = &mask {
-        let mut s_mask = writer.image_xobject(s_mask_id, &encoded);
+        let mut s_mask = writer.image_xobject(s_mask_id, encoded);
         s_mask.filter(filter);
         s_mask.width(dynamic.width() as i32);
         s_mask.height(dynamic.height() as i32);
@@ -120,5 +120,6 @@ fn main() -> std::io::Result<()> {
     writer.stream(content_id, &content.finish());
 
     // Write the thing to a file.
-    std::fs::write("target/image.pdf", writer.finish())
+    std::fs::write("target/image.pdf", writer.finish()?)?;
+    Ok(())
 }
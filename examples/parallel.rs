@@ -0,0 +1,78 @@
+//! This example shows how to serialize the content streams of many pages in
+//! parallel with chunks.
+
+use pdf_writer::{Chunk, Content, Name, PdfError, PdfWriter, Rect, Ref, Str};
+
+const PAGES: usize = 2000;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::write("target/parallel.pdf", write(4)?)?;
+    Ok(())
+}
+
+/// Write a document whose page content streams are serialized on the given
+/// number of threads.
+fn write(threads: usize) -> Result<Vec<u8>, PdfError> {
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let font_id = Ref::new(3);
+    let page_ids = |i: usize| (Ref::new(4 + 2 * i as i32), Ref::new(5 + 2 * i as i32));
+
+    // Serialize the content streams. Each thread writes one chunk with its
+    // share of the pages and uses the final ids right away, so no renumbering
+    // is necessary.
+    let per_thread = (PAGES + threads - 1) / threads;
+    let chunks: Vec<Chunk> = std::thread::scope(|s| {
+        let handles: Vec<_> = (0..threads)
+            .map(|t| {
+                s.spawn(move || {
+                    let mut chunk = Chunk::new();
+                    for i in t * per_thread..((t + 1) * per_thread).min(PAGES) {
+                        chunk.stream(page_ids(i).1, &content(i));
+                    }
+                    chunk
+                })
+            })
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+
+    // Write the rest of the document sequentially.
+    let mut writer = PdfWriter::new();
+    writer.catalog(catalog_id).pages(page_tree_id);
+    writer
+        .pages(page_tree_id)
+        .kids((0..PAGES).map(|i| page_ids(i).0))
+        .count(PAGES as i32);
+    writer.type1_font(font_id).base_font(Name(b"Helvetica"));
+
+    for i in 0..PAGES {
+        let (page_id, content_id) = page_ids(i);
+        let mut page = writer.page(page_id);
+        page.parent(page_tree_id)
+            .media_box(Rect::new(0.0, 0.0, 595.0, 842.0))
+            .contents(content_id);
+        page.resources().fonts().pair(Name(b"F1"), font_id);
+    }
+
+    for chunk in &chunks {
+        writer.extend(chunk);
+    }
+
+    writer.finish()
+}
+
+/// Create a content stream with lots of text.
+fn content(page: usize) -> Vec<u8> {
+    let mut content = Content::new();
+    content.begin_text();
+    content.set_font(Name(b"F1"), 10.0);
+    content.next_line(50.0, 800.0);
+    for line in 0..70 {
+        content.next_line(0.0, -11.0);
+        let text = format!("This is line {} on page {}.", line + 1, page + 1);
+        content.show(Str(text.as_bytes()));
+    }
+    content.end_text();
+    content.finish()
+}
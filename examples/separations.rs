@@ -3,7 +3,7 @@
 use pdf_writer::writers::ColorSpace;
 use pdf_writer::{Content, Finish, Name, PdfWriter, Rect, Ref};
 
-fn main() -> std::io::Result<()> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Start writing.
     let mut writer = PdfWriter::new();
 
@@ -175,5 +175,6 @@ fn main() -> std::io::Result<()> {
     writer.stream(content_id, &content.finish());
 
     // Write the thing to a file.
-    std::fs::write("target/separations.pdf", writer.finish())
+    std::fs::write("target/separations.pdf", writer.finish()?)?;
+    Ok(())
 }
@@ -0,0 +1,76 @@
+//! This example shows how to embed a video that plays when clicked.
+//!
+//! Run it with the path to an MP4 file:
+//! `cargo run --example video -- path/to/video.mp4`
+
+use pdf_writer::types::{
+    ActionType, AnnotationFlags, AnnotationType, RenditionOperation, RenditionType,
+    TempFilePermission,
+};
+use pdf_writer::{Finish, Name, PdfWriter, Rect, Ref, Str, TextStr};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::args().nth(1).expect("expected path to an MP4 file");
+    let video = std::fs::read(path)?;
+
+    // Start writing.
+    let mut writer = PdfWriter::new();
+
+    // Define some indirect reference ids we'll use.
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let page_id = Ref::new(3);
+    let annotation_id = Ref::new(4);
+    let file_spec_id = Ref::new(5);
+    let video_id = Ref::new(6);
+
+    // Set up the page tree. For more details see `hello.rs`.
+    writer.catalog(catalog_id).pages(page_tree_id);
+    writer.pages(page_tree_id).kids([page_id]).count(1);
+
+    // Write an A4 page. The screen annotation must be an indirect object
+    // because the rendition action that plays the video references it.
+    let mut page = writer.page(page_id);
+    page.media_box(Rect::new(0.0, 0.0, 595.0, 842.0));
+    page.parent(page_tree_id);
+    page.annotation_refs([annotation_id]);
+    page.finish();
+
+    // The screen annotation defines the region of the page in which the video
+    // is played. Screen annotations also need to know the page they are on.
+    let mut annotation = writer.annotation(annotation_id);
+    annotation.subtype(AnnotationType::Screen);
+    annotation.rect(Rect::new(97.5, 421.0, 497.5, 646.0));
+    annotation.contents(TextStr("A video"));
+    annotation.flags(AnnotationFlags::PRINT);
+    annotation.page(page_id);
+
+    // When the annotation is clicked, its action plays a media rendition in
+    // the same annotation. The media clip points to the file specification of
+    // the embedded video and says how it is encoded.
+    let mut action = annotation.action();
+    action.action_type(ActionType::Rendition);
+    action.operation(RenditionOperation::PlayOrResume);
+    action.screen_annotation(annotation_id);
+    let mut rendition = action.rendition();
+    rendition.subtype(RenditionType::Media);
+    rendition
+        .media_clip()
+        .data_ref(file_spec_id)
+        .content_type(Str(b"video/mp4"))
+        .temp_file(TempFilePermission::Access);
+    rendition.finish();
+    action.finish();
+    annotation.finish();
+
+    // The video itself is just an embedded file.
+    writer
+        .file_spec(file_spec_id)
+        .path(Str(b"video.mp4"))
+        .embedded_file(video_id);
+    writer.embedded_file(video_id, &video).subtype(Name(b"video/mp4"));
+
+    // Write the thing to a file.
+    std::fs::write("target/video.pdf", writer.finish()?)?;
+    Ok(())
+}
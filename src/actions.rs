@@ -0,0 +1,352 @@
+use super::*;
+use crate::types::{RelativeView3D, RenditionOperation, SubmitFlags};
+
+/// Writer for an _action dictionary_.
+///
+/// This struct is created by [`PdfWriter::action`], [`Annotation::action`],
+/// and [`Catalog::open_action`].
+pub struct Action<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(Action: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"Action"));
+    Self { dict }
+});
+
+impl<'a> Action<'a> {
+    /// Write the `/S` attribute to set the action type.
+    pub fn action_type(&mut self, kind: ActionType) -> &mut Self {
+        self.pair(Name(b"S"), kind.to_name());
+        self
+    }
+
+    /// Start writing the `/D` attribute to set the destination of this
+    /// GoTo-type action.
+    pub fn destination(&mut self) -> Destination<'_> {
+        self.insert(Name(b"D")).start()
+    }
+
+    /// Write the `/D` attribute to set the destination of this GoTo-type action
+    /// to a named destination.
+    pub fn destination_named(&mut self, name: Name) -> &mut Self {
+        self.pair(Name(b"D"), name);
+        self
+    }
+
+    /// Start writing the `/SD` attribute to set the structure destination of
+    /// this GoTo-type action, which targets a [structure
+    /// element](Destination::struct_element) instead of a page. A
+    /// [`destination`](Self::destination) should still be written as a
+    /// fallback for viewers without support for structure destinations. PDF
+    /// 2.0+.
+    pub fn structure_destination(&mut self) -> Destination<'_> {
+        self.insert(Name(b"SD")).start()
+    }
+
+    /// Start writing the `/F` attribute, setting which file to go to or which
+    /// application to launch.
+    pub fn file_spec(&mut self) -> FileSpec<'_> {
+        self.insert(Name(b"F")).start()
+    }
+
+    /// Write the `/NewWindow` attribute to set whether this remote GoTo action
+    /// should open the referenced destination in another window.
+    pub fn new_window(&mut self, new: bool) -> &mut Self {
+        self.pair(Name(b"NewWindow"), new);
+        self
+    }
+
+    /// Write the `/URI` attribute to set where this link action goes.
+    pub fn uri(&mut self, uri: Str) -> &mut Self {
+        self.pair(Name(b"URI"), uri);
+        self
+    }
+
+    /// Write the `/IsMap` attribute to set if the click position of the user's
+    /// cursor inside the link rectangle should be appended to the referenced
+    /// URI as a query parameter.
+    pub fn is_map(&mut self, map: bool) -> &mut Self {
+        self.pair(Name(b"IsMap"), map);
+        self
+    }
+
+    /// Write the `/JS` attribute to set the script of this JavaScript action.
+    /// PDF 1.3+.
+    pub fn js_string(&mut self, script: TextStr) -> &mut Self {
+        self.pair(Name(b"JS"), script);
+        self
+    }
+
+    /// Write the `/JS` attribute to reference a stream with the script of
+    /// this JavaScript action. This is preferable to a string for large
+    /// scripts. PDF 1.3+.
+    pub fn js_stream(&mut self, script: Ref) -> &mut Self {
+        self.pair(Name(b"JS"), script);
+        self
+    }
+}
+
+/// Rendition actions. PDF 1.5+.
+impl<'a> Action<'a> {
+    /// Start writing the `/R` dictionary to set the rendition that the
+    /// [operation](Self::operation) applies to.
+    pub fn rendition(&mut self) -> Rendition<'_> {
+        self.insert(Name(b"R")).start()
+    }
+
+    /// Write the `/AN` attribute to reference the screen annotation in which
+    /// the rendition is played.
+    ///
+    /// The annotation must be an indirect object written with
+    /// [`PdfWriter::annotation`] with the
+    /// [`Screen`](crate::types::AnnotationType::Screen) subtype. It is
+    /// typically on the same page as the annotation or field that triggers
+    /// the action. The action itself can also be the screen annotation's own
+    /// [`action`](Annotation::action), which plays the rendition when the
+    /// annotation is clicked.
+    pub fn screen_annotation(&mut self, annotation: Ref) -> &mut Self {
+        self.pair(Name(b"AN"), annotation);
+        self
+    }
+
+    /// Write the `/OP` attribute to set what to do with the rendition.
+    pub fn operation(&mut self, op: RenditionOperation) -> &mut Self {
+        self.pair(Name(b"OP"), op.to_int());
+        self
+    }
+}
+
+/// Go-to-3D-view actions. PDF 1.6+.
+impl<'a> Action<'a> {
+    /// Write the `/TA` attribute to reference the 3D annotation whose view is
+    /// changed. Required.
+    ///
+    /// The annotation must be an indirect object written with
+    /// [`PdfWriter::annotation`] with the
+    /// [`ThreeD`](crate::types::AnnotationType::ThreeD) subtype. The views
+    /// selected through the other methods refer to its [3D
+    /// stream](Annotation::stream_3d).
+    pub fn target_annotation(&mut self, annotation: Ref) -> &mut Self {
+        self.pair(Name(b"TA"), annotation);
+        self
+    }
+
+    /// Start writing the `/V` dictionary to set the view to go to. Either
+    /// this or one of the other `view_3d` methods is required.
+    pub fn view_3d(&mut self) -> View3D<'_> {
+        self.insert(Name(b"V")).start()
+    }
+
+    /// Write the `/V` attribute to go to one of the views from the 3D stream's
+    /// [`views`](Stream3D::views) array by index.
+    pub fn view_3d_index(&mut self, index: i32) -> &mut Self {
+        self.pair(Name(b"V"), index);
+        self
+    }
+
+    /// Write the `/V` attribute to go to one of the views from the 3D stream's
+    /// [`views`](Stream3D::views) array by its
+    /// [internal name](View3D::internal_name).
+    pub fn view_3d_name(&mut self, name: TextStr) -> &mut Self {
+        self.pair(Name(b"V"), name);
+        self
+    }
+
+    /// Write the `/V` attribute to go to a view relative to the current one
+    /// or to the default view.
+    pub fn view_3d_relative(&mut self, view: RelativeView3D) -> &mut Self {
+        self.pair(Name(b"V"), view.to_name());
+        self
+    }
+}
+
+/// Hide actions. PDF 1.2+.
+impl<'a> Action<'a> {
+    /// Write the `/T` attribute to reference the annotation that is shown or
+    /// hidden. Either this, [`hide_target_field`](Self::hide_target_field),
+    /// or [`hide_targets`](Self::hide_targets) is required.
+    pub fn hide_target(&mut self, annotation: Ref) -> &mut Self {
+        self.pair(Name(b"T"), annotation);
+        self
+    }
+
+    /// Write the `/T` attribute to set the fully qualified name of the form
+    /// field whose widget annotations are shown or hidden.
+    pub fn hide_target_field(&mut self, name: TextStr) -> &mut Self {
+        self.pair(Name(b"T"), name);
+        self
+    }
+
+    /// Start writing the `/T` array to set multiple targets, each of which is
+    /// either a reference to an annotation or the fully qualified name of a
+    /// form field.
+    pub fn hide_targets(&mut self) -> Array<'_> {
+        self.insert(Name(b"T")).array()
+    }
+
+    /// Write the `/H` attribute to set whether the targets are hidden or
+    /// shown. Defaults to `true`.
+    pub fn hide(&mut self, hide: bool) -> &mut Self {
+        self.pair(Name(b"H"), hide);
+        self
+    }
+}
+
+/// Set-OCG-state actions. PDF 1.5+.
+impl<'a> Action<'a> {
+    /// Start writing the `/State` array to set which optional content groups
+    /// are turned on, off, or toggled. Required.
+    pub fn ocg_state(&mut self) -> OcgState<'_> {
+        self.insert(Name(b"State")).start()
+    }
+
+    /// Write the `/PreserveRB` attribute to set whether the radio-button
+    /// relationships between the optional content groups are preserved, i.e.
+    /// whether turning a group on turns the other groups of its radio-button
+    /// set off. Defaults to `true`.
+    pub fn preserve_radio_buttons(&mut self, preserve: bool) -> &mut Self {
+        self.pair(Name(b"PreserveRB"), preserve);
+        self
+    }
+}
+
+/// Transition actions. PDF 1.5+.
+impl<'a> Action<'a> {
+    /// Start writing the `/Trans` dictionary to set the transition effect
+    /// that is used to update the display when the action is executed, e.g.
+    /// as part of a sequence of actions. Required.
+    pub fn transition(&mut self) -> Transition<'_> {
+        self.insert(Name(b"Trans")).start()
+    }
+}
+
+/// Submit-form and reset-form actions. PDF 1.2+.
+impl<'a> Action<'a> {
+    /// Write the `/F` attribute to set the URL to which the form data is
+    /// submitted. Required for submit-form actions.
+    pub fn submit_url(&mut self, url: Str) -> &mut Self {
+        self.file_spec().file_system(Name(b"URL")).path(url);
+        self
+    }
+
+    /// Write the `/Fields` attribute to reference the fields that are
+    /// submitted or reset. Without it, all fields are affected.
+    ///
+    /// Whether the fields are included or excluded is controlled by the
+    /// [`INCLUDE_EXCLUDE`](SubmitFlags::INCLUDE_EXCLUDE) flag for submit-form
+    /// actions and with [`reset_exclude`](Self::reset_exclude) for reset-form
+    /// actions.
+    pub fn fields(&mut self, fields: impl IntoIterator<Item = Ref>) -> &mut Self {
+        self.insert(Name(b"Fields")).array().items(fields);
+        self
+    }
+
+    /// Write the `/Flags` attribute to set how the form data of a submit-form
+    /// action is submitted. Defaults to submitting all field values as FDF
+    /// with a POST request.
+    pub fn submit_flags(&mut self, flags: SubmitFlags) -> &mut Self {
+        self.pair(Name(b"Flags"), flags.bits() as i32);
+        self
+    }
+
+    /// Write the `/Flags` attribute to set whether a reset-form action resets
+    /// all fields except for the [referenced ones](Self::fields) instead of
+    /// only the referenced ones.
+    pub fn reset_exclude(&mut self, exclude: bool) -> &mut Self {
+        self.pair(Name(b"Flags"), i32::from(exclude));
+        self
+    }
+}
+
+deref!('a, Action<'a> => Dict<'a>, dict);
+
+/// Writer for the _state array_ of a set-OCG-state action. PDF 1.5+.
+///
+/// The changes are applied in order.
+///
+/// This struct is created by [`Action::ocg_state`].
+pub struct OcgState<'a> {
+    array: Array<'a>,
+}
+
+writer!(OcgState: |obj| Self { array: obj.array() });
+
+impl<'a> OcgState<'a> {
+    /// Turn the given optional content groups on.
+    pub fn on(&mut self, groups: impl IntoIterator<Item = Ref>) -> &mut Self {
+        self.change(Name(b"ON"), groups)
+    }
+
+    /// Turn the given optional content groups off.
+    pub fn off(&mut self, groups: impl IntoIterator<Item = Ref>) -> &mut Self {
+        self.change(Name(b"OFF"), groups)
+    }
+
+    /// Toggle the given optional content groups.
+    pub fn toggle(&mut self, groups: impl IntoIterator<Item = Ref>) -> &mut Self {
+        self.change(Name(b"Toggle"), groups)
+    }
+
+    fn change(
+        &mut self,
+        state: Name,
+        groups: impl IntoIterator<Item = Ref>,
+    ) -> &mut Self {
+        self.array.item(state);
+        self.array.items(groups);
+        self
+    }
+}
+
+deref!('a, OcgState<'a> => Array<'a>, array);
+
+/// What kind of action to perform when clicking a link annotation.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ActionType {
+    /// Go to a destination in the document.
+    GoTo,
+    /// Go to a destination in another document.
+    RemoteGoTo,
+    /// Launch an application.
+    Launch,
+    /// Open a URI.
+    Uri,
+    /// Execute a JavaScript script. PDF 1.3+.
+    JavaScript,
+    /// Control the playback of multimedia content. PDF 1.5+.
+    Rendition,
+    /// Send the data of an interactive form to a URL. PDF 1.2+.
+    SubmitForm,
+    /// Reset the fields of an interactive form to their default values. PDF
+    /// 1.2+.
+    ResetForm,
+    /// Show or hide annotations or form fields. PDF 1.2+.
+    Hide,
+    /// Change the visibility of optional content groups. PDF 1.5+.
+    SetOcgState,
+    /// Change the view of a 3D annotation. PDF 1.6+.
+    GoTo3DView,
+    /// Update the display with a transition effect. PDF 1.5+.
+    Trans,
+}
+
+impl ActionType {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::GoTo => Name(b"GoTo"),
+            Self::RemoteGoTo => Name(b"GoToR"),
+            Self::Launch => Name(b"Launch"),
+            Self::Uri => Name(b"URI"),
+            Self::JavaScript => Name(b"JavaScript"),
+            Self::Rendition => Name(b"Rendition"),
+            Self::SubmitForm => Name(b"SubmitForm"),
+            Self::ResetForm => Name(b"ResetForm"),
+            Self::Hide => Name(b"Hide"),
+            Self::SetOcgState => Name(b"SetOCGState"),
+            Self::GoTo3DView => Name(b"GoTo3DView"),
+            Self::Trans => Name(b"Trans"),
+        }
+    }
+}
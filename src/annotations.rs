@@ -2,21 +2,36 @@ use super::*;
 
 /// Writer for an _annotation dictionary_.
 ///
-/// An array of this struct is created by [`Page::annotations`].
+/// This struct is created by [`PdfWriter::annotation`]. An array of this
+/// struct is created by [`Page::annotations`].
+///
+/// With the [`Legacy14`](crate::Profile::Legacy14) profile, an annotation with
+/// a [border style](Self::border_style) also gets a `/Border` array with the
+/// same width when it is finished, unless a `/Border` was written. Consumers
+/// that predate `/BS` then draw the same border.
 pub struct Annotation<'a> {
     dict: Dict<'a>,
+    border_width: f32,
 }
 
 writer!(Annotation: |obj| {
     let mut dict = obj.dict();
+    if let Some(record) = dict.recorder() {
+        record.annotation();
+    }
+    dict.watch(&[b"BS", b"Border"]);
     dict.pair(Name(b"Type"), Name(b"Annot"));
-    Self { dict }
+    Self { dict, border_width: 1.0 }
 });
 
 impl<'a> Annotation<'a> {
     /// Write the `/Subtype` attribute to tell the viewer the type of this
     /// particular annotation.
     pub fn subtype(&mut self, kind: AnnotationType) -> &mut Self {
+        if let Some(record) = self.dict.recorder() {
+            let (feature, version) = kind.requirement();
+            record.requires(feature, version);
+        }
         self.pair(Name(b"Subtype"), kind.to_name());
         self
     }
@@ -24,7 +39,7 @@ impl<'a> Annotation<'a> {
     /// Write the `/Rect` attribute. This is the location and dimensions of the
     /// annotation on the page.
     pub fn rect(&mut self, rect: Rect) -> &mut Self {
-        self.pair(Name(b"Rect"), rect);
+        self.pair(Name(b"Rect"), rect.normalized());
         self
     }
 
@@ -84,35 +99,40 @@ impl<'a> Annotation<'a> {
     /// Start writing the `/BS` attribute. These are some more elaborate border
     /// settings taking precedence over `/B` for some annotation types. PDF 1.2+.
     pub fn border_style(&mut self) -> BorderStyle<'_> {
-        self.insert(Name(b"BS")).start()
+        let mut style: BorderStyle = self.dict.insert(Name(b"BS")).start();
+        style.width = Some(&mut self.border_width);
+        style
+    }
+
+    /// Write the `/C` attribute. This sets the annotations background color
+    /// and its popup title bar color. PDF 1.1+.
+    pub fn color(&mut self, color: Color) -> &mut Self {
+        self.pair(Name(b"C"), color);
+        self
     }
 
     /// Write the `/C` attribute forcing a transparent color. This sets the
     /// annotations background color and its popup title bar color. PDF 1.1+.
     pub fn color_transparent(&mut self) -> &mut Self {
-        self.insert(Name(b"C")).array();
-        self
+        self.color(Color::Transparent)
     }
 
     /// Write the `/C` attribute using a grayscale color. This sets the
     /// annotations background color and its popup title bar color. PDF 1.1+.
     pub fn color_gray(&mut self, gray: f32) -> &mut Self {
-        self.insert(Name(b"C")).array().item(gray);
-        self
+        self.color(Color::Gray(gray))
     }
 
     /// Write the `/C` attribute using an RGB color. This sets the annotations
     /// background color and its popup title bar color. PDF 1.1+.
     pub fn color_rgb(&mut self, r: f32, g: f32, b: f32) -> &mut Self {
-        self.insert(Name(b"C")).array().items([r, g, b]);
-        self
+        self.color(Color::Rgb(r, g, b))
     }
 
     /// Write the `/C` attribute using a CMYK color. This sets the annotations
     /// background color and its popup title bar color. PDF 1.1+.
     pub fn color_cmyk(&mut self, c: f32, m: f32, y: f32, k: f32) -> &mut Self {
-        self.insert(Name(b"C")).array().items([c, m, y, k]);
-        self
+        self.color(Color::Cmyk(c, m, y, k))
     }
 
     /// Write the `/StructParent` attribute to indicate the [structure tree
@@ -122,12 +142,50 @@ impl<'a> Annotation<'a> {
         self
     }
 
-    /// Start writing the `/A` dictionary. Only permissible for the subtype
-    /// `Link`.
+    /// Write the `/P` attribute to reference the page the annotation is on.
+    /// PDF 1.3+.
+    ///
+    /// Required for screen annotations that are targeted by rendition actions.
+    pub fn page(&mut self, page: Ref) -> &mut Self {
+        self.pair(Name(b"P"), page);
+        self
+    }
+
+    /// Start writing the `/AP` dictionary to set the appearance streams of the
+    /// annotation. PDF 1.2+.
+    pub fn appearance(&mut self) -> Appearance<'_> {
+        self.insert(Name(b"AP")).start()
+    }
+
+    /// Write the `/AS` attribute to select the current appearance of the
+    /// annotation if its [appearance](Self::appearance) entries are
+    /// subdictionaries of multiple states, e.g. `On` and `Off` for a check
+    /// box. Required in that case. PDF 1.2+.
+    pub fn appearance_state(&mut self, state: Name) -> &mut Self {
+        self.pair(Name(b"AS"), state);
+        self
+    }
+
+    /// Start writing the `/A` dictionary. Only permissible for the subtypes
+    /// `Link` and `Screen`.
     pub fn action(&mut self) -> Action<'_> {
         self.insert(Name(b"A")).start()
     }
 
+    /// Write the `/A` attribute to reference an [action](Action) written with
+    /// [`PdfWriter::action`]. Only permissible for the subtypes `Link` and
+    /// `Screen`.
+    pub fn action_ref(&mut self, action: Ref) -> &mut Self {
+        self.pair(Name(b"A"), action);
+        self
+    }
+
+    /// Start writing the `/MK` dictionary to set how the annotation is drawn.
+    /// Only permissible for the subtypes `Widget` and `Screen`. PDF 1.2+.
+    pub fn appearance_characteristics(&mut self) -> AppearanceCharacteristics<'_> {
+        self.insert(Name(b"MK")).start()
+    }
+
     /// Write the `/H` attribute to set what effect is used to convey that the
     /// user is pressing a link annotation. Only permissible for the subtype
     /// `Link`. PDF 1.2+.
@@ -160,6 +218,56 @@ impl<'a> Annotation<'a> {
         self
     }
 
+    /// Write the `/QuadPoints` attribute from rectangles, e.g. the bounding
+    /// boxes of lines of text found during text extraction. PDF 1.6+.
+    ///
+    /// Each rectangle is converted with [`Rect::to_quad_points`].
+    pub fn quad_points_rects(
+        &mut self,
+        rects: impl IntoIterator<Item = Rect>,
+    ) -> &mut Self {
+        self.insert(Name(b"QuadPoints"))
+            .array()
+            .items(rects.into_iter().flat_map(Rect::to_quad_points));
+        self
+    }
+
+    /// Write the `/IC` attribute to set the interior color of the
+    /// annotation. Only permissible for the subtypes `Line`, `Square`,
+    /// `Circle`, and `Redact`. PDF 1.4+.
+    pub fn interior_color(&mut self, color: Color) -> &mut Self {
+        self.pair(Name(b"IC"), color);
+        self
+    }
+
+    /// Write the `/IC` attribute to leave the interior of the annotation
+    /// unfilled. Only permissible for the subtypes `Line`, `Square`,
+    /// `Circle`, and `Redact`. PDF 1.4+.
+    pub fn interior_color_transparent(&mut self) -> &mut Self {
+        self.interior_color(Color::Transparent)
+    }
+
+    /// Write the `/IC` attribute to set the interior color of the annotation
+    /// in the `DeviceGray` color space. Only permissible for the subtypes
+    /// `Line`, `Square`, `Circle`, and `Redact`. PDF 1.4+.
+    pub fn interior_color_gray(&mut self, gray: f32) -> &mut Self {
+        self.interior_color(Color::Gray(gray))
+    }
+
+    /// Write the `/IC` attribute to set the interior color of the annotation
+    /// in the `DeviceRGB` color space. Only permissible for the subtypes
+    /// `Line`, `Square`, `Circle`, and `Redact`. PDF 1.4+.
+    pub fn interior_color_rgb(&mut self, r: f32, g: f32, b: f32) -> &mut Self {
+        self.interior_color(Color::Rgb(r, g, b))
+    }
+
+    /// Write the `/IC` attribute to set the interior color of the annotation
+    /// in the `DeviceCMYK` color space. Only permissible for the subtypes
+    /// `Line`, `Square`, `Circle`, and `Redact`. PDF 1.4+.
+    pub fn interior_color_cmyk(&mut self, c: f32, m: f32, y: f32, k: f32) -> &mut Self {
+        self.interior_color(Color::Cmyk(c, m, y, k))
+    }
+
     /// Write the `/L` attribute. This defines the start and end point of a
     /// line annotation
     pub fn line_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32) -> &mut Self {
@@ -180,6 +288,280 @@ impl<'a> Annotation<'a> {
     }
 }
 
+/// Redaction annotations. PDF 1.7+.
+///
+/// These only mark content for redaction. Applying the redaction, i.e.
+/// removing the marked content, is up to the consumer of the document.
+impl<'a> Annotation<'a> {
+    /// Write the `/RO` attribute to reference a [form XObject](FormXObject)
+    /// that is drawn over the redacted region once the redaction is applied.
+    pub fn overlay(&mut self, form: Ref) -> &mut Self {
+        self.pair(Name(b"RO"), form);
+        self
+    }
+
+    /// Write the `/OverlayText` attribute to set the text that is drawn over
+    /// the redacted region once the redaction is applied.
+    pub fn overlay_text(&mut self, text: TextStr) -> &mut Self {
+        self.pair(Name(b"OverlayText"), text);
+        self
+    }
+
+    /// Write the `/Repeat` attribute to set whether the overlay text is
+    /// repeated to fill the redacted region.
+    pub fn repeat(&mut self, repeat: bool) -> &mut Self {
+        self.pair(Name(b"Repeat"), repeat);
+        self
+    }
+}
+
+/// Caret annotations. PDF 1.5+.
+impl<'a> Annotation<'a> {
+    /// Write the `/Sy` attribute to set whether a paragraph symbol is shown
+    /// after the caret to indicate a new paragraph.
+    pub fn paragraph_symbol(&mut self, paragraph: bool) -> &mut Self {
+        self.pair(Name(b"Sy"), if paragraph { Name(b"P") } else { Name(b"None") });
+        self
+    }
+}
+
+/// Free text annotations. PDF 1.3+.
+impl<'a> Annotation<'a> {
+    /// Write the `/IT` attribute to set the intent of the free text
+    /// annotation. PDF 1.6+.
+    pub fn free_text_intent(&mut self, intent: FreeTextIntent) -> &mut Self {
+        self.pair(Name(b"IT"), intent.to_name());
+        self
+    }
+
+    /// Write the `/CL` attribute to set the callout line from the annotated
+    /// point via an optional knee point to the text box. Only permissible for
+    /// the [`Callout`](FreeTextIntent::Callout) intent. PDF 1.6+.
+    pub fn callout_line(
+        &mut self,
+        start: (f32, f32),
+        knee: Option<(f32, f32)>,
+        end: (f32, f32),
+    ) -> &mut Self {
+        let mut array = self.insert(Name(b"CL")).array();
+        array.items([start.0, start.1]);
+        if let Some((x, y)) = knee {
+            array.items([x, y]);
+        }
+        array.items([end.0, end.1]);
+        array.finish();
+        self
+    }
+
+    /// Write the `/DS` attribute to set the default style of the [rich
+    /// text](Self::rich_text) as a CSS2 style string, e.g. `font: 12pt
+    /// Helvetica; color: #000000`. PDF 1.5+.
+    pub fn default_style(&mut self, style: TextStr) -> &mut Self {
+        self.pair(Name(b"DS"), style);
+        self
+    }
+
+    /// Write the `/RC` attribute to set the contents of the annotation as an
+    /// XHTML rich text string. Permissible for all markup annotations. PDF
+    /// 1.5+.
+    ///
+    /// The rich text must be consistent with the plain text
+    /// [`contents`](Self::contents), i.e. contain the same text with added
+    /// formatting. Viewers that support rich text display it instead of the
+    /// contents and may regenerate the appearance stream from it.
+    pub fn rich_text(&mut self, text: TextStr) -> &mut Self {
+        self.pair(Name(b"RC"), text);
+        self
+    }
+
+    /// Write the `/RC` attribute to reference a stream with the XHTML rich
+    /// text contents of the annotation. See [`rich_text`](Self::rich_text)
+    /// for details. PDF 1.5+.
+    pub fn rich_text_ref(&mut self, stream: Ref) -> &mut Self {
+        self.pair(Name(b"RC"), stream);
+        self
+    }
+
+    /// Write the `/BE` dictionary to draw the border of the annotation as a
+    /// cloud with the given intensity. PDF 1.5+.
+    ///
+    /// Panics if the intensity is not between `0.0` and `2.0`.
+    pub fn cloudy_border(&mut self, intensity: f32) -> &mut Self {
+        assert!(
+            (0.0..=2.0).contains(&intensity),
+            "cloud intensity must be between 0 and 2"
+        );
+        self.insert(Name(b"BE"))
+            .dict()
+            .pair(Name(b"S"), Name(b"C"))
+            .pair(Name(b"I"), intensity);
+        self
+    }
+}
+
+/// Common entries of text annotations.
+impl<'a> Annotation<'a> {
+    /// Write the `/DA` attribute to set the appearance of the text of a free
+    /// text or redaction annotation as a sequence of content stream
+    /// operators, e.g. `/Helv 12 Tf 0 g`.
+    ///
+    /// The operators can be built with [`DefaultAppearance`].
+    pub fn default_appearance(&mut self, appearance: Str) -> &mut Self {
+        self.pair(Name(b"DA"), appearance);
+        self
+    }
+
+    /// Write the `/Q` attribute to set the justification of the text.
+    pub fn quadding(&mut self, quadding: Quadding) -> &mut Self {
+        self.pair(Name(b"Q"), quadding.to_int());
+        self
+    }
+
+    /// Write the `/RD` attribute to set the distances from the left, top,
+    /// right, and bottom edges of the annotation rectangle to the drawn
+    /// caret, text box, or border. Only permissible for the subtypes `Caret`,
+    /// `FreeText`, `Square`, and `Circle`. PDF 1.5+.
+    pub fn rect_differences(
+        &mut self,
+        left: f32,
+        top: f32,
+        right: f32,
+        bottom: f32,
+    ) -> &mut Self {
+        self.insert(Name(b"RD")).array().items([left, top, right, bottom]);
+        self
+    }
+}
+
+/// Watermark annotations. PDF 1.6+.
+impl<'a> Annotation<'a> {
+    /// Start writing the `/FixedPrint` dictionary to keep the size and
+    /// position of the watermark when the page is printed at a different size.
+    pub fn fixed_print(&mut self) -> FixedPrint<'_> {
+        self.insert(Name(b"FixedPrint")).start()
+    }
+}
+
+/// Trap network annotations. PDF 1.3+. Deprecated in PDF 2.0.
+impl<'a> Annotation<'a> {
+    /// Write the `/LastModified` attribute to set when the trap network was
+    /// generated. Either this or [`version`](Self::version) is required.
+    pub fn last_modified(&mut self, date: Date) -> &mut Self {
+        self.pair(Name(b"LastModified"), date);
+        self
+    }
+
+    /// Write the `/Version` attribute to reference the objects whose changes
+    /// invalidate the trap network, e.g. content streams and fonts.
+    pub fn version(&mut self, objects: impl IntoIterator<Item = Ref>) -> &mut Self {
+        self.insert(Name(b"Version")).array().items(objects);
+        self
+    }
+
+    /// Write the `/AnnotStates` attribute to set the appearance states of the
+    /// page's annotations when the trap network was generated, in the order
+    /// of the page's `/Annots` array. Annotations without appearance states
+    /// are `None`. Required if [`version`](Self::version) is written.
+    pub fn annotation_states<'n>(
+        &mut self,
+        states: impl IntoIterator<Item = Option<Name<'n>>>,
+    ) -> &mut Self {
+        let mut array = self.insert(Name(b"AnnotStates")).array();
+        for state in states {
+            match state {
+                Some(name) => array.item(name),
+                None => array.item(Null),
+            };
+        }
+        array.finish();
+        self
+    }
+
+    /// Write the `/FontFauxing` attribute to reference the [font
+    /// descriptors](FontDescriptor) of the fonts that were substituted when
+    /// the trap network was generated.
+    pub fn font_fauxing(
+        &mut self,
+        descriptors: impl IntoIterator<Item = Ref>,
+    ) -> &mut Self {
+        self.insert(Name(b"FontFauxing")).array().items(descriptors);
+        self
+    }
+}
+
+/// Rich media annotations. PDF 2.0+.
+impl<'a> Annotation<'a> {
+    /// Start writing the `/RichMediaContent` dictionary to set the media
+    /// files and how they are presented. Required for the subtype
+    /// `RichMedia`.
+    pub fn rich_media_content(&mut self) -> RichMediaContent<'_> {
+        self.insert(Name(b"RichMediaContent")).start()
+    }
+
+    /// Start writing the `/RichMediaSettings` dictionary to set when the
+    /// annotation is activated and deactivated.
+    pub fn rich_media_settings(&mut self) -> RichMediaSettings<'_> {
+        self.insert(Name(b"RichMediaSettings")).start()
+    }
+}
+
+/// 3D annotations. PDF 1.6+.
+impl<'a> Annotation<'a> {
+    /// Write the `/3DD` attribute to reference the [3D stream](Stream3D) with
+    /// the artwork. Required for the subtype `3D`.
+    pub fn stream_3d(&mut self, stream: Ref) -> &mut Self {
+        self.pair(Name(b"3DD"), stream);
+        self
+    }
+
+    /// Start writing the `/3DV` dictionary to set the view that is shown
+    /// when the annotation is activated.
+    pub fn view_3d(&mut self) -> View3D<'_> {
+        self.insert(Name(b"3DV")).start()
+    }
+
+    /// Write the `/3DV` attribute to use one of the views from the 3D stream's
+    /// [`views`](Stream3D::views) array by index when the annotation is
+    /// activated.
+    pub fn view_3d_index(&mut self, index: i32) -> &mut Self {
+        self.pair(Name(b"3DV"), index);
+        self
+    }
+
+    /// Write the `/3DV` attribute to use one of the views from the 3D stream's
+    /// [`views`](Stream3D::views) array by its
+    /// [internal name](View3D::internal_name) when the annotation is
+    /// activated.
+    pub fn view_3d_name(&mut self, name: TextStr) -> &mut Self {
+        self.pair(Name(b"3DV"), name);
+        self
+    }
+
+    /// Start writing the `/3DA` dictionary to control when the artwork is
+    /// activated and deactivated.
+    pub fn activation_3d(&mut self) -> Activation3D<'_> {
+        self.insert(Name(b"3DA")).start()
+    }
+
+    /// Write the `/3DI` attribute to set whether the artwork is interactive
+    /// or driven by scripts only.
+    pub fn interactive_3d(&mut self, interactive: bool) -> &mut Self {
+        self.pair(Name(b"3DI"), interactive);
+        self
+    }
+}
+
+impl Drop for Annotation<'_> {
+    fn drop(&mut self) {
+        if self.dict.profile() == Profile::Legacy14
+            && self.dict.written(b"BS")
+            && !self.dict.written(b"Border")
+        {
+            self.border(0.0, 0.0, self.border_width, None);
+        }
+    }
+}
+
 deref!('a, Annotation<'a> => Dict<'a>, dict);
 
 /// Kind of the annotation to produce.
@@ -205,6 +587,32 @@ pub enum AnnotationType {
     StrikeOut,
     /// A reference to another file. PDF 1.3+.
     FileAttachment,
+    /// A sound that is played when the annotation is activated. PDF 1.2+.
+    /// Deprecated in PDF 2.0.
+    Sound,
+    /// A movie that is played when the annotation is activated. PDF 1.2+.
+    /// Deprecated in PDF 2.0.
+    Movie,
+    /// A region of the page in which media is played. PDF 1.5+.
+    Screen,
+    /// A widget of an interactive [form field](crate::writers::Field). PDF
+    /// 1.2+.
+    Widget,
+    /// Text that is written directly on the page. PDF 1.3+.
+    FreeText,
+    /// A caret marking where text should be inserted. PDF 1.5+.
+    Caret,
+    /// Trapping information for the page. PDF 1.3+. Deprecated in PDF 2.0.
+    TrapNet,
+    /// A mark that is printed at a fixed size and position, independently of
+    /// the page size. PDF 1.6+.
+    Watermark,
+    /// Content that is marked for removal. PDF 1.7+.
+    Redact,
+    /// Interactive 3D artwork. PDF 1.6+.
+    ThreeD,
+    /// Video, audio, or 3D content with a configurable player. PDF 2.0+.
+    RichMedia,
 }
 
 impl AnnotationType {
@@ -220,6 +628,44 @@ impl AnnotationType {
             Self::Squiggly => Name(b"Squiggly"),
             Self::StrikeOut => Name(b"StrikeOut"),
             Self::FileAttachment => Name(b"FileAttachment"),
+            Self::Sound => Name(b"Sound"),
+            Self::Movie => Name(b"Movie"),
+            Self::Screen => Name(b"Screen"),
+            Self::Widget => Name(b"Widget"),
+            Self::FreeText => Name(b"FreeText"),
+            Self::Caret => Name(b"Caret"),
+            Self::TrapNet => Name(b"TrapNet"),
+            Self::Watermark => Name(b"Watermark"),
+            Self::Redact => Name(b"Redact"),
+            Self::ThreeD => Name(b"3D"),
+            Self::RichMedia => Name(b"RichMedia"),
+        }
+    }
+
+    /// The annotations of this type and the PDF version they require.
+    pub(crate) fn requirement(self) -> (&'static str, (u8, u8)) {
+        match self {
+            Self::Text => ("text annotations", (1, 0)),
+            Self::Link => ("link annotations", (1, 0)),
+            Self::Line => ("line annotations", (1, 3)),
+            Self::Square => ("square annotations", (1, 3)),
+            Self::Circle => ("circle annotations", (1, 3)),
+            Self::Highlight => ("highlight annotations", (1, 3)),
+            Self::Underline => ("underline annotations", (1, 3)),
+            Self::Squiggly => ("squiggly annotations", (1, 4)),
+            Self::StrikeOut => ("strike out annotations", (1, 3)),
+            Self::FileAttachment => ("file attachment annotations", (1, 3)),
+            Self::Sound => ("sound annotations", (1, 2)),
+            Self::Movie => ("movie annotations", (1, 2)),
+            Self::Screen => ("screen annotations", (1, 5)),
+            Self::Widget => ("widget annotations", (1, 2)),
+            Self::FreeText => ("free text annotations", (1, 3)),
+            Self::Caret => ("caret annotations", (1, 5)),
+            Self::TrapNet => ("trap network annotations", (1, 3)),
+            Self::Watermark => ("watermark annotations", (1, 6)),
+            Self::Redact => ("redaction annotations", (1, 7)),
+            Self::ThreeD => ("3D annotations", (1, 6)),
+            Self::RichMedia => ("rich media annotations", (2, 0)),
         }
     }
 }
@@ -306,89 +752,446 @@ bitflags::bitflags! {
     }
 }
 
-/// Writer for an _action dictionary_.
+/// Writer for an _appearance dictionary_. PDF 1.2+.
 ///
-/// This struct is created by [`Annotation::action`].
-pub struct Action<'a> {
+/// Each entry references a [form XObject](FormXObject) that is drawn in the
+/// annotation rectangle.
+///
+/// This struct is created by [`Annotation::appearance`].
+pub struct Appearance<'a> {
     dict: Dict<'a>,
 }
 
-writer!(Action: |obj| {
+writer!(Appearance: |obj| Self { dict: obj.dict() });
+
+impl<'a> Appearance<'a> {
+    /// Write the `/N` attribute to set the normal appearance. Required.
+    pub fn normal(&mut self, form: Ref) -> &mut Self {
+        self.pair(Name(b"N"), form);
+        self
+    }
+
+    /// Write the `/R` attribute to set the appearance when the user hovers the
+    /// annotation.
+    pub fn rollover(&mut self, form: Ref) -> &mut Self {
+        self.pair(Name(b"R"), form);
+        self
+    }
+
+    /// Write the `/D` attribute to set the appearance when the user presses
+    /// the mouse button on the annotation.
+    pub fn down(&mut self, form: Ref) -> &mut Self {
+        self.pair(Name(b"D"), form);
+        self
+    }
+}
+
+deref!('a, Appearance<'a> => Dict<'a>, dict);
+
+/// Writer for an _appearance characteristics dictionary_. PDF 1.2+.
+///
+/// Viewers use these characteristics to construct the appearance of an
+/// annotation if it has no [appearance stream](Annotation::appearance).
+///
+/// This struct is created by [`Annotation::appearance_characteristics`].
+pub struct AppearanceCharacteristics<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(AppearanceCharacteristics: |obj| Self { dict: obj.dict() });
+
+impl<'a> AppearanceCharacteristics<'a> {
+    /// Write the `/R` attribute to set the counterclockwise rotation of the
+    /// annotation in degrees. Must be a multiple of 90.
+    pub fn rotation(&mut self, degrees: i32) -> &mut Self {
+        self.pair(Name(b"R"), degrees);
+        self
+    }
+
+    /// Write the `/BC` attribute to set the border color.
+    pub fn border_color(&mut self, color: Color) -> &mut Self {
+        self.pair(Name(b"BC"), color);
+        self
+    }
+
+    /// Write the `/BC` attribute to set the border color in the `DeviceGray`
+    /// color space.
+    pub fn border_color_gray(&mut self, gray: f32) -> &mut Self {
+        self.border_color(Color::Gray(gray))
+    }
+
+    /// Write the `/BC` attribute to set the border color in the `DeviceRGB`
+    /// color space.
+    pub fn border_color_rgb(&mut self, r: f32, g: f32, b: f32) -> &mut Self {
+        self.border_color(Color::Rgb(r, g, b))
+    }
+
+    /// Write the `/BC` attribute to set the border color in the `DeviceCMYK`
+    /// color space.
+    pub fn border_color_cmyk(&mut self, c: f32, m: f32, y: f32, k: f32) -> &mut Self {
+        self.border_color(Color::Cmyk(c, m, y, k))
+    }
+
+    /// Write the `/BG` attribute to set the background color.
+    pub fn background_color(&mut self, color: Color) -> &mut Self {
+        self.pair(Name(b"BG"), color);
+        self
+    }
+
+    /// Write the `/BG` attribute to set the background color in the
+    /// `DeviceGray` color space.
+    pub fn background_color_gray(&mut self, gray: f32) -> &mut Self {
+        self.background_color(Color::Gray(gray))
+    }
+
+    /// Write the `/BG` attribute to set the background color in the
+    /// `DeviceRGB` color space.
+    pub fn background_color_rgb(&mut self, r: f32, g: f32, b: f32) -> &mut Self {
+        self.background_color(Color::Rgb(r, g, b))
+    }
+
+    /// Write the `/BG` attribute to set the background color in the
+    /// `DeviceCMYK` color space.
+    pub fn background_color_cmyk(&mut self, c: f32, m: f32, y: f32, k: f32) -> &mut Self {
+        self.background_color(Color::Cmyk(c, m, y, k))
+    }
+
+    /// Write the `/CA` attribute to set the caption that is shown normally.
+    pub fn normal_caption(&mut self, caption: TextStr) -> &mut Self {
+        self.pair(Name(b"CA"), caption);
+        self
+    }
+
+    /// Write the `/RC` attribute to set the caption that is shown when the
+    /// user hovers the annotation.
+    pub fn rollover_caption(&mut self, caption: TextStr) -> &mut Self {
+        self.pair(Name(b"RC"), caption);
+        self
+    }
+
+    /// Write the `/AC` attribute to set the caption that is shown when the
+    /// user presses the mouse button on the annotation.
+    pub fn alternate_caption(&mut self, caption: TextStr) -> &mut Self {
+        self.pair(Name(b"AC"), caption);
+        self
+    }
+
+    /// Write the `/I` attribute to reference the [form XObject](FormXObject)
+    /// that is shown as the icon normally.
+    pub fn normal_icon(&mut self, icon: Ref) -> &mut Self {
+        self.pair(Name(b"I"), icon);
+        self
+    }
+
+    /// Write the `/RI` attribute to reference the [form XObject](FormXObject)
+    /// that is shown as the icon when the user hovers the annotation.
+    pub fn rollover_icon(&mut self, icon: Ref) -> &mut Self {
+        self.pair(Name(b"RI"), icon);
+        self
+    }
+
+    /// Write the `/IX` attribute to reference the [form XObject](FormXObject)
+    /// that is shown as the icon when the user presses the mouse button on
+    /// the annotation.
+    pub fn alternate_icon(&mut self, icon: Ref) -> &mut Self {
+        self.pair(Name(b"IX"), icon);
+        self
+    }
+
+    /// Start writing the `/IF` dictionary to set how the icon is fitted into
+    /// the annotation rectangle. Only permissible for push buttons.
+    pub fn icon_fit(&mut self) -> IconFit<'_> {
+        self.insert(Name(b"IF")).start()
+    }
+
+    /// Write the `/TP` attribute to set where the caption is placed relative to
+    /// the icon. Only permissible for push buttons.
+    pub fn text_position(&mut self, position: TextPosition) -> &mut Self {
+        self.pair(Name(b"TP"), position.to_int());
+        self
+    }
+}
+
+deref!('a, AppearanceCharacteristics<'a> => Dict<'a>, dict);
+
+/// Where the caption of a push button is placed relative to its icon.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TextPosition {
+    /// Only show the caption.
+    CaptionOnly,
+    /// Only show the icon.
+    IconOnly,
+    /// Show the caption below the icon.
+    Below,
+    /// Show the caption above the icon.
+    Above,
+    /// Show the caption right of the icon.
+    Right,
+    /// Show the caption left of the icon.
+    Left,
+    /// Show the caption on top of the icon.
+    Overlaid,
+}
+
+impl TextPosition {
+    pub(crate) fn to_int(self) -> i32 {
+        match self {
+            Self::CaptionOnly => 0,
+            Self::IconOnly => 1,
+            Self::Below => 2,
+            Self::Above => 3,
+            Self::Right => 4,
+            Self::Left => 5,
+            Self::Overlaid => 6,
+        }
+    }
+}
+
+/// Writer for an _icon fit dictionary_. PDF 1.2+.
+///
+/// This struct is created by [`AppearanceCharacteristics::icon_fit`].
+pub struct IconFit<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(IconFit: |obj| Self { dict: obj.dict() });
+
+impl<'a> IconFit<'a> {
+    /// Write the `/SW` attribute to set when the icon is scaled. Defaults to
+    /// [`IconScaleWhen::Always`].
+    pub fn scale_when(&mut self, when: IconScaleWhen) -> &mut Self {
+        self.pair(Name(b"SW"), when.to_name());
+        self
+    }
+
+    /// Write the `/S` attribute to set whether the icon is scaled
+    /// proportionally. Defaults to `true`.
+    pub fn proportional(&mut self, proportional: bool) -> &mut Self {
+        self.pair(Name(b"S"), if proportional { Name(b"P") } else { Name(b"A") });
+        self
+    }
+
+    /// Write the `/A` attribute to set how the leftover space is distributed
+    /// when the icon is scaled proportionally, as fractions of the space to
+    /// the left and to the bottom of the icon. Defaults to `(0.5, 0.5)`, i.e.
+    /// centered.
+    pub fn alignment(&mut self, left: f32, bottom: f32) -> &mut Self {
+        self.insert(Name(b"A")).array().items([left, bottom]);
+        self
+    }
+
+    /// Write the `/FB` attribute to set whether the icon is fitted into the
+    /// annotation rectangle without regard to the border width. PDF 1.5+.
+    pub fn fit_bounds(&mut self, fit: bool) -> &mut Self {
+        self.pair(Name(b"FB"), fit);
+        self
+    }
+}
+
+deref!('a, IconFit<'a> => Dict<'a>, dict);
+
+/// When the icon of a push button is scaled to fit the annotation rectangle.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum IconScaleWhen {
+    /// Always scale the icon.
+    Always,
+    /// Scale the icon only if it is bigger than the rectangle.
+    Bigger,
+    /// Scale the icon only if it is smaller than the rectangle.
+    Smaller,
+    /// Never scale the icon.
+    Never,
+}
+
+impl IconScaleWhen {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::Always => Name(b"A"),
+            Self::Bigger => Name(b"B"),
+            Self::Smaller => Name(b"S"),
+            Self::Never => Name(b"N"),
+        }
+    }
+}
+
+/// Writer for a _fixed print dictionary_. PDF 1.6+.
+///
+/// When a page is printed at a different size, e.g. scaled to fit the paper,
+/// a watermark annotation with this dictionary keeps its original size. Its
+/// position is determined by the [`horizontal`](Self::horizontal) and
+/// [`vertical`](Self::vertical) translations relative to the dimensions of
+/// the target media, applied after the [`matrix`](Self::matrix).
+///
+/// This struct is created by [`Annotation::fixed_print`].
+///
+/// # Example
+/// A diagonal "DRAFT" mark that is centered on the printed page.
+/// ```
+/// use pdf_writer::types::AnnotationType;
+/// use pdf_writer::{Content, Finish, Name, PdfWriter, Rect, Ref, Str};
+///
+/// let mut writer = PdfWriter::new();
+/// let page_id = Ref::new(1);
+/// let annotation_id = Ref::new(2);
+/// let form_id = Ref::new(3);
+/// let font_id = Ref::new(4);
+///
+/// let mut page = writer.page(page_id);
+/// page.media_box(Rect::new(0.0, 0.0, 595.0, 842.0));
+/// page.annotation_refs([annotation_id]);
+/// page.finish();
+///
+/// // The annotation rectangle is the bounding box of the form placed
+/// // around the origin so that the translation centers it.
+/// let mut annotation = writer.annotation(annotation_id);
+/// annotation.subtype(AnnotationType::Watermark);
+/// annotation.rect(Rect::new(-200.0, -200.0, 200.0, 200.0));
+/// annotation.appearance().normal(form_id);
+/// annotation.fixed_print().horizontal(0.5).vertical(0.5);
+/// annotation.finish();
+///
+/// // Rotate the text by 45 degrees.
+/// let (sin, cos) = std::f32::consts::FRAC_PI_4.sin_cos();
+/// let mut content = Content::new();
+/// content.begin_text();
+/// content.set_font(Name(b"F1"), 72.0);
+/// content.set_text_matrix([cos, sin, -sin, cos, -120.0, -120.0]);
+/// content.show(Str(b"DRAFT"));
+/// content.end_text();
+/// let content = content.finish();
+///
+/// let mut form = writer.form_xobject(form_id, &content);
+/// form.bbox(Rect::new(-200.0, -200.0, 200.0, 200.0));
+/// form.resources().fonts().pair(Name(b"F1"), font_id);
+/// form.finish();
+///
+/// writer.type1_font(font_id).base_font(Name(b"Helvetica"));
+/// ```
+pub struct FixedPrint<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(FixedPrint: |obj| {
     let mut dict = obj.dict();
-    dict.pair(Name(b"Type"), Name(b"Action"));
+    dict.pair(Name(b"Type"), Name(b"FixedPrint"));
     Self { dict }
 });
 
-impl<'a> Action<'a> {
-    /// Write the `/S` attribute to set the action type.
-    pub fn action_type(&mut self, kind: ActionType) -> &mut Self {
-        self.pair(Name(b"S"), kind.to_name());
+impl<'a> FixedPrint<'a> {
+    /// Write the `/Matrix` attribute to transform the annotation rectangle
+    /// before it is translated. Defaults to the identity matrix.
+    pub fn matrix(&mut self, matrix: [f32; 6]) -> &mut Self {
+        self.insert(Name(b"Matrix")).array().items(matrix);
         self
     }
 
-    /// Start writing the `/D` attribute to set the destination of this
-    /// GoTo-type action.
-    pub fn destination(&mut self) -> Destination<'_> {
-        self.insert(Name(b"D")).start()
+    /// Write the `/H` attribute to set the horizontal translation as a
+    /// fraction of the width of the target media. Defaults to `0.0`.
+    pub fn horizontal(&mut self, fraction: f32) -> &mut Self {
+        self.pair(Name(b"H"), fraction);
+        self
     }
 
-    /// Write the `/D` attribute to set the destination of this GoTo-type action
-    /// to a named destination.
-    pub fn destination_named(&mut self, name: Name) -> &mut Self {
-        self.pair(Name(b"D"), name);
+    /// Write the `/V` attribute to set the vertical translation as a fraction
+    /// of the height of the target media. Defaults to `0.0`.
+    pub fn vertical(&mut self, fraction: f32) -> &mut Self {
+        self.pair(Name(b"V"), fraction);
         self
     }
+}
 
-    /// Start writing the `/F` attribute, setting which file to go to or which
-    /// application to launch.
-    pub fn file_spec(&mut self) -> FileSpec<'_> {
-        self.insert(Name(b"F")).start()
+deref!('a, FixedPrint<'a> => Dict<'a>, dict);
+
+/// The intent of a free text annotation.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FreeTextIntent {
+    /// A plain text box.
+    FreeText,
+    /// A text box with a [callout line](Annotation::callout_line) pointing to
+    /// an area of the page.
+    Callout,
+    /// Text that is typed directly onto the page without a border.
+    TypeWriter,
+}
+
+impl FreeTextIntent {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::FreeText => Name(b"FreeText"),
+            Self::Callout => Name(b"FreeTextCallout"),
+            Self::TypeWriter => Name(b"FreeTextTypeWriter"),
+        }
     }
+}
 
-    /// Write the `/NewWindow` attribute to set whether this remote GoTo action
-    /// should open the referenced destination in another window.
-    pub fn new_window(&mut self, new: bool) -> &mut Self {
-        self.pair(Name(b"NewWindow"), new);
+/// A builder for the [default appearance](Annotation::default_appearance) of
+/// text in annotations and [form fields](Field::default_appearance).
+///
+/// This writes the operators in the same format as [`Content`].
+pub struct DefaultAppearance {
+    content: Content,
+}
+
+impl DefaultAppearance {
+    /// Create a new, empty default appearance.
+    pub fn new() -> Self {
+        Self { content: Content::with_capacity(32) }
+    }
+
+    /// `Tf`: Set the font from the `/DR` resource dictionary and the font
+    /// size. A size of zero means that the text is sized automatically.
+    pub fn font(&mut self, font: Name, size: f32) -> &mut Self {
+        self.content.set_font(font, size);
         self
     }
 
-    /// Write the `/URI` attribute to set where this link action goes.
-    pub fn uri(&mut self, uri: Str) -> &mut Self {
-        self.pair(Name(b"URI"), uri);
+    /// `g`: Set the text color in the `DeviceGray` color space.
+    pub fn gray(&mut self, gray: f32) -> &mut Self {
+        self.content.set_fill_gray(gray);
         self
     }
 
-    /// Write the `/IsMap` attribute to set if the click position of the user's
-    /// cursor inside the link rectangle should be appended to the referenced
-    /// URI as a query parameter.
-    pub fn is_map(&mut self, map: bool) -> &mut Self {
-        self.pair(Name(b"IsMap"), map);
+    /// `rg`: Set the text color in the `DeviceRGB` color space.
+    pub fn rgb(&mut self, r: f32, g: f32, b: f32) -> &mut Self {
+        self.content.set_fill_rgb(r, g, b);
         self
     }
+
+    /// `k`: Set the text color in the `DeviceCMYK` color space.
+    pub fn cmyk(&mut self, c: f32, m: f32, y: f32, k: f32) -> &mut Self {
+        self.content.set_fill_cmyk(c, m, y, k);
+        self
+    }
+
+    /// Return the raw operators for use with [`Str`].
+    pub fn finish(self) -> Vec<u8> {
+        self.content.finish()
+    }
 }
 
-deref!('a, Action<'a> => Dict<'a>, dict);
+impl Default for DefaultAppearance {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-/// What kind of action to perform when clicking a link annotation.
+/// How text in an annotation is justified.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-pub enum ActionType {
-    /// Go to a destination in the document.
-    GoTo,
-    /// Go to a destination in another document.
-    RemoteGoTo,
-    /// Launch an application.
-    Launch,
-    /// Open a URI.
-    Uri,
-}
-
-impl ActionType {
-    pub(crate) fn to_name(self) -> Name<'static> {
+pub enum Quadding {
+    /// Left-justified text.
+    Left,
+    /// Centered text.
+    Center,
+    /// Right-justified text.
+    Right,
+}
+
+impl Quadding {
+    pub(crate) fn to_int(self) -> i32 {
         match self {
-            Self::GoTo => Name(b"GoTo"),
-            Self::RemoteGoTo => Name(b"GoToR"),
-            Self::Launch => Name(b"Launch"),
-            Self::Uri => Name(b"URI"),
+            Self::Left => 0,
+            Self::Center => 1,
+            Self::Right => 2,
         }
     }
 }
@@ -423,17 +1226,21 @@ impl HighlightEffect {
 /// This struct is created by [`Annotation::border_style`].
 pub struct BorderStyle<'a> {
     dict: Dict<'a>,
+    width: Option<&'a mut f32>,
 }
 
 writer!(BorderStyle: |obj| {
     let mut dict = obj.dict();
     dict.pair(Name(b"Type"), Name(b"Border"));
-    Self { dict }
+    Self { dict, width: None }
 });
 
 impl<'a> BorderStyle<'a> {
     /// Write the `/W` attribute. This is the width of the border in points.
     pub fn width(&mut self, points: f32) -> &mut Self {
+        if let Some(width) = &mut self.width {
+            **width = points;
+        }
         self.pair(Name(b"W"), points);
         self
     }
@@ -480,3 +1287,90 @@ impl BorderType {
         }
     }
 }
+
+/// A color of an annotation, an [outline item](OutlineItem) or a [structure
+/// element](LayoutAttributes).
+///
+/// The number of components determines the color space. Entries that only
+/// support RGB colors take an [`OpaqueColor`] instead.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Color {
+    /// No color at all, e.g. to leave the interior of an annotation unfilled.
+    Transparent,
+    /// A color in the `DeviceGray` color space.
+    Gray(f32),
+    /// A color in the `DeviceRGB` color space.
+    Rgb(f32, f32, f32),
+    /// A color in the `DeviceCMYK` color space.
+    Cmyk(f32, f32, f32, f32),
+}
+
+impl From<[f32; 3]> for Color {
+    fn from([r, g, b]: [f32; 3]) -> Self {
+        Self::Rgb(r, g, b)
+    }
+}
+
+impl From<OpaqueColor> for Color {
+    fn from(color: OpaqueColor) -> Self {
+        match color {
+            OpaqueColor::Gray(gray) => Self::Gray(gray),
+            OpaqueColor::Rgb(r, g, b) => Self::Rgb(r, g, b),
+            OpaqueColor::Cmyk(c, m, y, k) => Self::Cmyk(c, m, y, k),
+        }
+    }
+}
+
+impl Primitive for Color {
+    fn write(self, buf: &mut Vec<u8>) {
+        let components: &[f32] = match &self {
+            Self::Transparent => &[],
+            Self::Gray(gray) => std::slice::from_ref(gray),
+            Self::Rgb(r, g, b) => &[*r, *g, *b],
+            Self::Cmyk(c, m, y, k) => &[*c, *m, *y, *k],
+        };
+
+        buf.push(b'[');
+        for (i, &component) in components.iter().enumerate() {
+            if i != 0 {
+                buf.push(b' ');
+            }
+            buf.push_val(component);
+        }
+        buf.push(b']');
+    }
+}
+
+/// A [color](Color) that is never transparent, for an [outline
+/// item](OutlineItem) or a [structure element](LayoutAttributes).
+///
+/// These entries only support RGB colors, so gray and CMYK colors are
+/// converted accordingly.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum OpaqueColor {
+    /// A color in the `DeviceGray` color space.
+    Gray(f32),
+    /// A color in the `DeviceRGB` color space.
+    Rgb(f32, f32, f32),
+    /// A color in the `DeviceCMYK` color space.
+    Cmyk(f32, f32, f32, f32),
+}
+
+impl OpaqueColor {
+    /// Convert the color to its RGB components.
+    pub(crate) fn to_rgb(self) -> [f32; 3] {
+        match self {
+            Self::Gray(gray) => [gray; 3],
+            Self::Rgb(r, g, b) => [r, g, b],
+            Self::Cmyk(c, m, y, k) => {
+                [(1.0 - c) * (1.0 - k), (1.0 - m) * (1.0 - k), (1.0 - y) * (1.0 - k)]
+            }
+        }
+    }
+}
+
+impl From<[f32; 3]> for OpaqueColor {
+    fn from([r, g, b]: [f32; 3]) -> Self {
+        Self::Rgb(r, g, b)
+    }
+}
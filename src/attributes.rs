@@ -1,10 +1,12 @@
 use super::*;
+use crate::types::OpaqueColor;
 
 /// Writer for an _attribute dictionary_. PDF 1.4+
 ///
 /// This struct must set the `/O` attribute by calling any of the methods. This
-/// struct is created by [`ClassMap::single`]. An array of this struct is
-/// created by [`StructElement::attributes`] and [`ClassMap::multiple`].
+/// struct is created by [`ClassMap::single`], [`StructElement::single_attributes`]
+/// and [`StructAttributes::push`]. An array of this struct is created by
+/// [`StructElement::attributes`] and [`ClassMap::multiple`].
 pub struct Attributes<'a> {
     dict: Dict<'a>,
 }
@@ -22,7 +24,7 @@ impl<'a> Attributes<'a> {
 
     /// Set the `/O` attribute to user-defined and start writing the `/P` array
     /// with user properties. PDF 1.6+
-    pub fn user(&mut self) -> TypedArray<'_, UserProperty> {
+    pub fn user(&mut self) -> TypedArray<'_, UserProperty<'_>> {
         self.pair(Name(b"O"), AttributeOwner::User.to_name());
         self.insert(Name(b"P")).array().typed()
     }
@@ -51,6 +53,42 @@ impl<'a> Attributes<'a> {
 
 deref!('a, Attributes<'a> => Dict<'a>, dict);
 
+/// Writer for an _attribute array_ with revision numbers. PDF 1.4+
+///
+/// Each attribute object may be followed by the revision number of the
+/// structure element for which it was last updated. Attribute objects that are
+/// not followed by a revision number have the revision number 0.
+///
+/// This struct is created by [`StructElement::attributes_with_revisions`] and
+/// [`ClassMap::multiple_with_revisions`].
+pub struct StructAttributes<'a> {
+    array: Array<'a>,
+}
+
+writer!(StructAttributes: |obj| Self { array: obj.array() });
+
+impl<'a> StructAttributes<'a> {
+    /// Start writing an attribute dictionary.
+    pub fn push(&mut self) -> Attributes<'_> {
+        self.array.push().start()
+    }
+
+    /// Reference an attribute dictionary that is written as an indirect
+    /// object.
+    pub fn indirect(&mut self, attributes: Ref) -> &mut Self {
+        self.array.item(attributes);
+        self
+    }
+
+    /// Write the revision number of the preceding attribute object.
+    pub fn revision(&mut self, revision: i32) -> &mut Self {
+        self.array.item(revision);
+        self
+    }
+}
+
+deref!('a, StructAttributes<'a> => Array<'a>, array);
+
 /// Writer for an _user property dictionary_. PDF 1.6+
 ///
 /// An array of this struct is created by [`Attributes::user`].
@@ -117,18 +155,26 @@ impl<'a> LayoutAttributes<'a> {
 
     /// Write the `/BackgroundColor` attribute to set the background color in
     /// RGB between `0` and `1`. PDF 1.5+
-    pub fn background_color(&mut self, color: [f32; 3]) -> &mut Self {
+    ///
+    /// Like all color attributes, this accepts `[r, g, b]` arrays and
+    /// [opaque colors](OpaqueColor) that are converted to RGB.
+    pub fn background_color(&mut self, color: impl Into<OpaqueColor>) -> &mut Self {
         self.dict
             .insert(Name(b"BackgroundColor"))
             .array()
             .typed()
-            .items(color);
+            .items(color.into().to_rgb());
         self
     }
 
-    /// Write the `/BorderColor` attribute.
-    pub fn border_color(&mut self, color: [f32; 3]) -> &mut Self {
-        self.dict.insert(Name(b"BorderColor")).array().typed().items(color);
+    /// Write the `/BorderColor` attribute to set the border color in RGB
+    /// between `0` and `1`.
+    pub fn border_color(&mut self, color: impl Into<OpaqueColor>) -> &mut Self {
+        self.dict
+            .insert(Name(b"BorderColor"))
+            .array()
+            .typed()
+            .items(color.into().to_rgb());
         self
     }
 
@@ -158,9 +204,14 @@ impl<'a> LayoutAttributes<'a> {
         self
     }
 
-    /// Write the `/Color` attribute.
-    pub fn color(&mut self, color: [f32; 3]) -> &mut Self {
-        self.dict.insert(Name(b"Color")).array().typed().items(color);
+    /// Write the `/Color` attribute to set the content color in RGB between
+    /// `0` and `1`.
+    pub fn color(&mut self, color: impl Into<OpaqueColor>) -> &mut Self {
+        self.dict
+            .insert(Name(b"Color"))
+            .array()
+            .typed()
+            .items(color.into().to_rgb());
         self
     }
 }
@@ -462,12 +513,12 @@ impl LayoutAttributes<'_> {
     }
 
     /// Write the `/TextDecorationColor` attribute in RGB. PDF 1.5+.
-    pub fn text_decoration_color(&mut self, color: [f32; 3]) -> &mut Self {
+    pub fn text_decoration_color(&mut self, color: impl Into<OpaqueColor>) -> &mut Self {
         self.dict
             .insert(Name(b"TextDecorationColor"))
             .array()
             .typed()
-            .items(color);
+            .items(color.into().to_rgb());
         self
     }
 
@@ -778,7 +829,7 @@ impl<'a> TableAttributes<'a> {
 
     /// Write the `/Headers` attribute to refer to the header cells of the
     /// table. PDF 1.6+.
-    pub fn headers(&mut self) -> TypedArray<'_, Str> {
+    pub fn headers(&mut self) -> TypedArray<'_, Str<'_>> {
         self.dict.insert(Name(b"Headers")).array().typed()
     }
 
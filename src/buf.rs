@@ -8,6 +8,7 @@ pub trait BufExt {
     fn push_decimal(&mut self, value: f32);
     fn push_hex(&mut self, value: u8);
     fn push_hex_u16(&mut self, value: u16);
+    fn push_indent(&mut self, indent: u8);
 }
 
 impl BufExt for Vec<u8> {
@@ -67,4 +68,14 @@ impl BufExt for Vec<u8> {
         self.push_hex((value >> 8) as u8);
         self.push_hex(value as u8);
     }
+
+    #[inline]
+    fn push_indent(&mut self, indent: u8) {
+        const SPACES: [u8; u8::MAX as usize] = [b' '; u8::MAX as usize];
+        if indent == 0 {
+            return;
+        }
+
+        self.extend_from_slice(&SPACES[..indent as usize]);
+    }
 }
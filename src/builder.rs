@@ -0,0 +1,233 @@
+use super::*;
+
+/// A convenience builder for a page that accumulates its contents, resources
+/// and annotations.
+///
+/// The builder automatically names the resources that are used in its
+/// content stream (`/F1`, `/F2`, ... for fonts, `/Im1`, ... for images, and so
+/// on) and writes the content stream and the page dictionary in one go when
+/// [finished](Self::finish). For anything not covered here, write the page
+/// with [`finish_with`](Self::finish_with) or use the [`Page`] writer
+/// directly.
+///
+/// ```
+/// use pdf_writer::{Name, PageBuilder, PdfWriter, Rect, Ref, RefAllocator, Str};
+///
+/// let mut alloc = RefAllocator::new();
+/// let catalog_id = alloc.bump();
+/// let page_tree_id = alloc.bump();
+/// let font_id = alloc.bump();
+///
+/// let mut writer = PdfWriter::new();
+/// writer.catalog(catalog_id).pages(page_tree_id);
+/// writer.type1_font(font_id).base_font(Name(b"Helvetica"));
+///
+/// // 1. Register the font and use the returned name in the content stream.
+/// let mut page = PageBuilder::new(Rect::new(0.0, 0.0, 595.0, 842.0));
+/// let font = page.font(font_id);
+/// page.content()
+///     .begin_text()
+///     .set_font(Name(&font), 14.0)
+///     .next_line(108.0, 734.0)
+///     .show(Str(b"Hello World"))
+///     .end_text();
+///
+/// // 2. Write the content stream and the page.
+/// let page_id = page.finish(&mut writer, &mut alloc, page_tree_id);
+/// writer.pages(page_tree_id).kids([page_id]).count(1);
+/// ```
+pub struct PageBuilder {
+    media_box: Rect,
+    content: Content,
+    fonts: Vec<Ref>,
+    images: Vec<Ref>,
+    forms: Vec<Ref>,
+    ext_g_states: Vec<Ref>,
+    annotations: Vec<Ref>,
+}
+
+impl PageBuilder {
+    /// Create a new page builder for a page with the given media box.
+    pub fn new(media_box: Rect) -> Self {
+        Self {
+            media_box,
+            content: Content::new(),
+            fonts: vec![],
+            images: vec![],
+            forms: vec![],
+            ext_g_states: vec![],
+            annotations: vec![],
+        }
+    }
+
+    /// The content stream of the page.
+    pub fn content(&mut self) -> &mut Content {
+        &mut self.content
+    }
+
+    /// Register a font with the page's resources and return its name for use
+    /// with [`Content::set_font`].
+    ///
+    /// Registering the same font twice returns the same name.
+    pub fn font(&mut self, font: Ref) -> Vec<u8> {
+        register(&mut self.fonts, b"F", font)
+    }
+
+    /// Register an [image XObject](ImageXObject) with the page's resources and
+    /// return its name for use with [`Content::x_object`].
+    pub fn image(&mut self, image: Ref) -> Vec<u8> {
+        register(&mut self.images, b"Im", image)
+    }
+
+    /// Register a [form XObject](FormXObject) with the page's resources and
+    /// return its name for use with [`Content::x_object`].
+    pub fn form_xobject(&mut self, form: Ref) -> Vec<u8> {
+        register(&mut self.forms, b"Fm", form)
+    }
+
+    /// Register a [graphics state parameter dictionary](ExtGraphicsState)
+    /// with the page's resources and return its name for use with
+    /// [`Content::set_parameters`].
+    pub fn ext_g_state(&mut self, state: Ref) -> Vec<u8> {
+        register(&mut self.ext_g_states, b"GS", state)
+    }
+
+    /// Add an [annotation](Annotation) that was written with
+    /// [`PdfWriter::annotation`] to the page.
+    pub fn annotation(&mut self, annotation: Ref) -> &mut Self {
+        self.annotations.push(annotation);
+        self
+    }
+
+    /// Write the content stream and the page dictionary with the given parent
+    /// page tree and return the page's id.
+    ///
+    /// The ids of the page and the content stream are taken from `alloc`.
+    pub fn finish(
+        self,
+        writer: &mut PdfWriter,
+        alloc: &mut RefAllocator,
+        parent: Ref,
+    ) -> Ref {
+        self.finish_with(writer, alloc, parent, |_| {})
+    }
+
+    /// Like [`finish`](Self::finish), but with a closure that can write
+    /// additional attributes to the page dictionary, e.g. a
+    /// [transparency group](Page::group).
+    ///
+    /// ```
+    /// use pdf_writer::{PageBuilder, PdfWriter, Rect, Ref, RefAllocator};
+    ///
+    /// let mut alloc = RefAllocator::new();
+    /// let page_tree_id = alloc.bump();
+    ///
+    /// let mut writer = PdfWriter::new();
+    /// let page = PageBuilder::new(Rect::new(0.0, 0.0, 595.0, 842.0));
+    /// page.finish_with(&mut writer, &mut alloc, page_tree_id, |page| {
+    ///     page.group().transparency().isolated(true).color_space().device_rgb();
+    /// });
+    /// ```
+    pub fn finish_with(
+        self,
+        writer: &mut PdfWriter,
+        alloc: &mut RefAllocator,
+        parent: Ref,
+        attrs: impl FnOnce(&mut Page<'_>),
+    ) -> Ref {
+        let page_id = alloc.bump();
+        let content_id = alloc.bump();
+        writer.stream(content_id, &self.content.finish());
+
+        let mut page = writer.page(page_id);
+        page.parent(parent).media_box(self.media_box).contents(content_id);
+
+        let mut resources = page.resources();
+        if !self.fonts.is_empty() {
+            write_names(&mut resources.fonts(), b"F", &self.fonts);
+        }
+
+        if !self.images.is_empty() || !self.forms.is_empty() {
+            let mut x_objects = resources.x_objects();
+            write_names(&mut x_objects, b"Im", &self.images);
+            write_names(&mut x_objects, b"Fm", &self.forms);
+        }
+
+        if !self.ext_g_states.is_empty() {
+            write_names(&mut resources.ext_g_states(), b"GS", &self.ext_g_states);
+        }
+
+        resources.finish();
+
+        if !self.annotations.is_empty() {
+            page.annotation_refs(self.annotations);
+        }
+
+        attrs(&mut page);
+        page_id
+    }
+}
+
+impl Debug for PageBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.pad("PageBuilder(..)")
+    }
+}
+
+/// Add a resource to a list unless it is already present and return its name.
+fn register(list: &mut Vec<Ref>, prefix: &[u8], id: Ref) -> Vec<u8> {
+    let index = match list.iter().position(|&other| other == id) {
+        Some(index) => index,
+        None => {
+            list.push(id);
+            list.len() - 1
+        }
+    };
+    name(prefix, index)
+}
+
+/// Write the pairs of a resource dictionary.
+fn write_names(dict: &mut Dict, prefix: &[u8], list: &[Ref]) {
+    for (i, &id) in list.iter().enumerate() {
+        dict.pair(Name(&name(prefix, i)), id);
+    }
+}
+
+/// The name of the resource at the given index in its list.
+fn name(prefix: &[u8], index: usize) -> Vec<u8> {
+    let mut name = prefix.to_vec();
+    name.push_int(index as i32 + 1);
+    name
+}
+
+/// Allocates consecutive indirect reference ids.
+#[derive(Debug, Clone)]
+pub struct RefAllocator {
+    next: i32,
+}
+
+impl RefAllocator {
+    /// Create a new allocator that starts with the id `1`.
+    pub fn new() -> Self {
+        Self::starting_at(Ref::new(1))
+    }
+
+    /// Create a new allocator that starts with the given id, e.g. to continue
+    /// after ids that were allocated manually.
+    pub fn starting_at(id: Ref) -> Self {
+        Self { next: id.get() }
+    }
+
+    /// Return the next unused id.
+    pub fn bump(&mut self) -> Ref {
+        let id = Ref::new(self.next);
+        self.next += 1;
+        id
+    }
+}
+
+impl Default for RefAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
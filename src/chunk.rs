@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+
+use super::*;
+
+/// A collection of indirect objects that is written independently of a
+/// [`PdfWriter`].
+///
+/// Chunks own their buffer and are [`Send`], so independent parts of a
+/// document, like the content streams of many pages, can be serialized in
+/// parallel and then be [added](PdfWriter::extend) to the writer. Since the
+/// final ids are often only known at that point, chunks can use placeholder
+/// ids that are [renumbered](Self::renumber) before adding them.
+///
+/// Chunks are also useful to deduplicate objects: Write a font or an image
+/// into a chunk with a fixed placeholder id and compare or hash its
+/// [bytes](Self::as_bytes) to check whether an identical object was already
+/// added to the document.
+///
+/// ```
+/// use pdf_writer::{Chunk, Content, PdfWriter, Ref, RefAllocator};
+///
+/// // Serialize the content streams on multiple threads. With rayon, this
+/// // would be `pages.par_iter().map(..).collect()`.
+/// let pages = ["A", "B", "C", "D"];
+/// let chunks: Vec<Chunk> = std::thread::scope(|s| {
+///     let handles: Vec<_> = pages
+///         .iter()
+///         .map(|&text| {
+///             s.spawn(move || {
+///                 let mut content = Content::new();
+///                 content.begin_text().show(pdf_writer::Str(text.as_bytes())).end_text();
+///
+///                 // Every chunk uses the placeholder id 1 for its stream.
+///                 let mut chunk = Chunk::new();
+///                 chunk.stream(Ref::new(1), &content.finish());
+///                 chunk
+///             })
+///         })
+///         .collect();
+///     handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+/// });
+///
+/// // Assign the final ids and add the chunks to the document sequentially.
+/// let mut alloc = RefAllocator::starting_at(Ref::new(10));
+/// let mut writer = PdfWriter::new();
+/// for mut chunk in chunks {
+///     chunk.renumber(|_| alloc.bump());
+///     writer.extend(&chunk);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Chunk {
+    pub(crate) buf: Vec<u8>,
+    pub(crate) offsets: Vec<(Ref, usize)>,
+    pub(crate) structure: Structure,
+    profile: Profile,
+}
+
+impl Chunk {
+    /// Create a new chunk with the default buffer capacity (currently 1 KB).
+    pub fn new() -> Self {
+        Self::with_capacity(1024)
+    }
+
+    /// Create a new chunk with the specified initial buffer capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: Vec::with_capacity(capacity),
+            offsets: vec![],
+            structure: Structure::default(),
+            profile: Profile::default(),
+        }
+    }
+
+    /// Set the compatibility profile for the objects written into the chunk.
+    ///
+    /// This should match the [profile of the writer](PdfWriter::set_profile)
+    /// the chunk is added to.
+    ///
+    /// _Default value_: [`Profile::Modern17`].
+    pub fn set_profile(&mut self, profile: Profile) {
+        self.profile = profile;
+    }
+
+    /// The number of bytes that were written so far.
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Whether nothing was written so far.
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// The serialized objects in the chunk.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// The ids of the objects in the chunk in the order they were written.
+    pub fn refs(&self) -> impl ExactSizeIterator<Item = Ref> + '_ {
+        self.offsets.iter().map(|&(id, _)| id)
+    }
+
+    /// Start writing an indirectly referenceable object.
+    pub fn indirect(&mut self, id: Ref) -> Obj<'_> {
+        self.offsets.push((id, self.buf.len()));
+        Obj::indirect(&mut self.buf, id)
+            .profile(self.profile)
+            .record(&mut self.structure, id)
+    }
+
+    /// Write an indirectly referenceable primitive object in one go.
+    pub fn indirect_primitive<T: Primitive>(&mut self, id: Ref, value: T) {
+        self.indirect(id).primitive(value);
+    }
+
+    /// Start writing an indirectly referenceable stream.
+    pub fn stream<'a>(&'a mut self, id: Ref, data: &'a [u8]) -> Stream<'a> {
+        Stream::start(self.indirect(id), data)
+    }
+
+    /// Replace the ids of the objects in the chunk and all indirect
+    /// references to them according to `mapping`.
+    ///
+    /// The mapping is called once for every object in the chunk, in the order
+    /// they were written, so it can allocate new ids as it goes. References to
+    /// objects outside of the chunk, e.g. to a font or the page tree, are kept
+    /// as they are. References in strings and stream data are left untouched.
+    pub fn renumber(&mut self, mut mapping: impl FnMut(Ref) -> Ref) {
+        let mut ids = HashMap::new();
+        for &(id, _) in &self.offsets {
+            ids.entry(id).or_insert_with(|| mapping(id));
+        }
+        let mapping = |id| ids.get(&id).copied().unwrap_or(id);
+
+        let mut buf = Vec::with_capacity(self.buf.len());
+        let ends = self.offsets.iter().skip(1).map(|&(_, offset)| offset);
+        let ends: Vec<usize> = ends.chain(std::iter::once(self.buf.len())).collect();
+
+        for ((id, offset), end) in self.offsets.iter_mut().zip(ends) {
+            let object = &self.buf[*offset..end];
+            let header =
+                object.windows(4).position(|w| w == b"obj\n").map_or(0, |p| p + 4);
+
+            *id = mapping(*id);
+            *offset = buf.len();
+            buf.push_int(id.get());
+            buf.extend(b" 0 obj\n");
+            renumber(&object[header..], &mut buf, mapping);
+        }
+
+        self.buf = buf;
+        self.structure.renumber(mapping);
+    }
+}
+
+impl Default for Chunk {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for Chunk {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.pad("Chunk(..)")
+    }
+}
+
+/// Copy a serialized object and replace its indirect references.
+fn renumber(chunk: &[u8], buf: &mut Vec<u8>, mut mapping: impl FnMut(Ref) -> Ref) {
+    let mut i = 0;
+    while i < chunk.len() {
+        let start = i;
+        match chunk[i] {
+            // Copy literal strings, respecting escapes and nested parentheses.
+            b'(' => {
+                let mut depth = 0;
+                while i < chunk.len() {
+                    match chunk[i] {
+                        b'\\' => i += 1,
+                        b'(' => depth += 1,
+                        b')' => depth -= 1,
+                        _ => {}
+                    }
+                    i += 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+            }
+            // Copy dictionary delimiters.
+            b'<' | b'>' if chunk.get(i + 1) == Some(&chunk[i]) => i += 2,
+            // Copy hexadecimal strings.
+            b'<' => {
+                while i < chunk.len() && chunk[i] != b'>' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            // Copy names and other tokens.
+            b'/' | b'a'..=b'z' | b'A'..=b'Z' | b'.' | b'+' | b'-' => {
+                i += 1;
+                while i < chunk.len() && !is_delimiter(chunk[i]) {
+                    i += 1;
+                }
+                // Copy stream data verbatim.
+                if &chunk[start..i] == b"stream" {
+                    i = chunk.len();
+                }
+            }
+            // Replace indirect references of the form `12 0 R`.
+            b'0'..=b'9' => {
+                while i < chunk.len() && !is_delimiter(chunk[i]) {
+                    i += 1;
+                }
+
+                let rest = &chunk[i..];
+                if let Some(len) = reference_tail(rest) {
+                    let id = std::str::from_utf8(&chunk[start..i])
+                        .ok()
+                        .and_then(|id| id.parse::<i32>().ok())
+                        .filter(|&id| id > 0);
+                    if let Some(id) = id {
+                        mapping(Ref::new(id)).write(buf);
+                        i += len;
+                        continue;
+                    }
+                }
+            }
+            _ => i += 1,
+        }
+        buf.extend(&chunk[start..i.min(chunk.len())]);
+    }
+}
+
+/// Return the length of the ` 0 R` part of an indirect reference.
+fn reference_tail(rest: &[u8]) -> Option<usize> {
+    if !rest.starts_with(b" 0 R") {
+        return None;
+    }
+
+    match rest.get(4) {
+        Some(&b) if !is_delimiter(b) => None,
+        _ => Some(4),
+    }
+}
+
+/// Whether the byte ends a token.
+fn is_delimiter(b: u8) -> bool {
+    b.is_ascii_whitespace() || b"()<>[]{}/%".contains(&b)
+}
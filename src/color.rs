@@ -82,6 +82,9 @@ writer!(ColorSpace: |obj| Self { obj });
 /// CIE-based color spaces.
 impl ColorSpace<'_> {
     /// Write a `CalRGB` color space.
+    ///
+    /// In debug builds, panics if the `white_point` does not have a luminance
+    /// (Y) of 1 or if its X and Z components are not positive.
     pub fn cal_rgb(
         self,
         white_point: [f32; 3],
@@ -89,6 +92,7 @@ impl ColorSpace<'_> {
         gamma: Option<[f32; 3]>,
         matrix: Option<[f32; 9]>,
     ) {
+        check_white_point(white_point);
         let mut array = self.obj.array();
         array.item(ColorSpaceType::CalRgb.to_name());
 
@@ -109,12 +113,16 @@ impl ColorSpace<'_> {
     }
 
     /// Write a `CalGray` color space.
+    ///
+    /// In debug builds, panics if the `white_point` does not have a luminance
+    /// (Y) of 1 or if its X and Z components are not positive.
     pub fn cal_gray(
         self,
         white_point: [f32; 3],
         black_point: Option<[f32; 3]>,
         gamma: Option<f32>,
     ) {
+        check_white_point(white_point);
         let mut array = self.obj.array();
         array.item(ColorSpaceType::CalGray.to_name());
 
@@ -131,12 +139,16 @@ impl ColorSpace<'_> {
     }
 
     /// Write a `Lab` color space.
+    ///
+    /// In debug builds, panics if the `white_point` does not have a luminance
+    /// (Y) of 1 or if its X and Z components are not positive.
     pub fn lab(
         self,
         white_point: [f32; 3],
         black_point: Option<[f32; 3]>,
         range: Option<[f32; 4]>,
     ) {
+        check_white_point(white_point);
         let mut array = self.obj.array();
         array.item(ColorSpaceType::Lab.to_name());
 
@@ -163,6 +175,15 @@ impl ColorSpace<'_> {
     }
 }
 
+/// Check that a CIE 1931 XYZ white point is valid for a CIE-based color space.
+///
+/// The luminance is compared with a tolerance since white points are often
+/// the result of a computation.
+fn check_white_point([x, y, z]: [f32; 3]) {
+    debug_assert!((y - 1.0).abs() <= 1e-4, "white point luminance must be 1");
+    debug_assert!(x > 0.0 && z > 0.0, "white point X and Z must be positive");
+}
+
 /// Writer for an _ICC profile stream_.
 ///
 /// This struct is created by [`PdfWriter::icc_profile`].
@@ -276,6 +297,7 @@ impl ColorSpace<'_> {
     /// Write a `CalRGB` color space approximating ProPhoto.
     ///
     /// Use an ICC profile for more accurate results.
+    #[allow(clippy::excessive_precision)]
     pub fn pro_photo(self) {
         self.cal_rgb(
             CIE_D50,
@@ -289,6 +311,7 @@ impl ColorSpace<'_> {
     }
 
     /// Write a `CalRGB` color space for ECI RGB v1.
+    #[allow(clippy::excessive_precision)]
     pub fn eci_rgb(self) {
         self.cal_rgb(
             CIE_D50,
@@ -302,6 +325,7 @@ impl ColorSpace<'_> {
     }
 
     /// Write a `CalRGB` color space for NTSC RGB.
+    #[allow(clippy::excessive_precision)]
     pub fn ntsc(self) {
         self.cal_rgb(
             CIE_C,
@@ -315,6 +339,7 @@ impl ColorSpace<'_> {
     }
 
     /// Write a `CalRGB` color space for PAL/SECAM RGB.
+    #[allow(clippy::excessive_precision)]
     pub fn pal(self) {
         self.cal_rgb(
             CIE_D65,
@@ -648,7 +673,7 @@ impl DeviceNAttrs<'_> {
     /// names and its values are separation color space arrays.
     ///
     /// Required if the `/Subtype` attribute is `NChannel`.
-    pub fn colorants(&mut self) -> TypedDict<'_, Dict> {
+    pub fn colorants(&mut self) -> TypedDict<'_, Dict<'_>> {
         self.dict.insert(Name(b"Colorants")).dict().typed()
     }
 
@@ -704,6 +729,7 @@ impl DeviceNProcess<'_> {
 }
 
 /// Type of n-dimensional color space.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum DeviceNSubtype {
     /// A subtractive color space.
     DeviceN,
@@ -794,7 +820,7 @@ impl<'a> TilingPattern<'a> {
     /// Sets the bounding box of the pattern in the pattern's coordinate system.
     /// Required.
     pub fn bbox(&mut self, bbox: Rect) -> &mut Self {
-        self.stream.pair(Name(b"BBox"), bbox);
+        self.stream.pair(Name(b"BBox"), bbox.normalized());
         self
     }
 
@@ -1069,7 +1095,7 @@ impl<'a> StreamShading<'a> {
     ///
     /// Sets the bounding box of the shading in the target coordinate system.
     pub fn bbox(&mut self, bbox: Rect) -> &mut Self {
-        self.stream.pair(Name(b"BBox"), bbox);
+        self.stream.pair(Name(b"BBox"), bbox.normalized());
         self
     }
 
@@ -1189,7 +1215,8 @@ impl StreamShadingType {
 
 /// Writer for the _separation information dictionary_. PDF 1.3+.
 ///
-/// This struct is created by [`Catalog::separation_info`].
+/// This struct is created by [`Page::separation_info`] and
+/// [`Catalog::separation_info`].
 pub struct SeparationInfo<'a> {
     dict: Dict<'a>,
 }
@@ -1229,6 +1256,13 @@ impl SeparationInfo<'_> {
     pub fn color_space(&mut self) -> ColorSpace<'_> {
         self.dict.insert(Name(b"ColorSpace")).start()
     }
+
+    /// Write the `/ColorSpace` attribute as a reference to a Separation or
+    /// DeviceN color space.
+    pub fn color_space_ref(&mut self, id: Ref) -> &mut Self {
+        self.dict.pair(Name(b"ColorSpace"), id);
+        self
+    }
 }
 
 /// Writer for an _output intent dictionary_. PDF 1.4+.
@@ -1293,9 +1327,77 @@ impl OutputIntent<'_> {
         self.dict.pair(Name(b"DestOutputProfile"), profile);
         self
     }
+
+    /// Start writing the `/DestOutputProfileRef` dictionary to identify an
+    /// ICC profile that is not embedded in the document. PDF 2.0+.
+    ///
+    /// Must not be combined with [`dest_output_profile`](Self::dest_output_profile).
+    pub fn dest_output_profile_ref(&mut self) -> OutputProfileRef<'_> {
+        self.dict.insert(Name(b"DestOutputProfileRef")).start()
+    }
+}
+
+deref!('a, OutputIntent<'a> => Dict<'a>, dict);
+
+/// Writer for an _ICC profile reference dictionary_. PDF 2.0+.
+///
+/// This struct is created by [`OutputIntent::dest_output_profile_ref`].
+pub struct OutputProfileRef<'a> {
+    dict: Dict<'a>,
 }
 
+writer!(OutputProfileRef: |obj| Self { dict: obj.dict() });
+
+impl<'a> OutputProfileRef<'a> {
+    /// Write the `/CheckSum` attribute to set the 16-byte MD5 checksum of the
+    /// profile.
+    pub fn checksum(&mut self, checksum: Str) -> &mut Self {
+        self.pair(Name(b"CheckSum"), checksum);
+        self
+    }
+
+    /// Write the `/CheckSum` attribute with the MD5 checksum of the given
+    /// profile data.
+    pub fn checksum_from_bytes(&mut self, data: &[u8]) -> &mut Self {
+        self.checksum(Str(&crate::md5::md5(data)))
+    }
+
+    /// Write the `/ICCVersion` attribute to set the version of the ICC
+    /// specification the profile conforms to as it is stored in the profile
+    /// header, e.g. `[4, 2, 0, 0]`.
+    pub fn icc_version(&mut self, version: [u8; 4]) -> &mut Self {
+        self.pair(Name(b"ICCVersion"), Str(&version));
+        self
+    }
+
+    /// Write the `/ProfileCS` attribute to set the color space of the profile
+    /// as it is stored in the profile header, e.g. `CMYK`.
+    pub fn profile_color_space(&mut self, space: Str) -> &mut Self {
+        self.pair(Name(b"ProfileCS"), space);
+        self
+    }
+
+    /// Write the `/ProfileName` attribute to set the name of the profile as
+    /// it is registered with the ICC.
+    pub fn profile_name(&mut self, name: TextStr) -> &mut Self {
+        self.pair(Name(b"ProfileName"), name);
+        self
+    }
+
+    /// Start writing the `/URLs` array with the locations where the profile
+    /// can be retrieved from.
+    ///
+    /// Each entry is a [file specification](FileSpec) with the
+    /// [file system](FileSpec::file_system) `URL`.
+    pub fn urls(&mut self) -> TypedArray<'_, FileSpec<'_>> {
+        self.insert(Name(b"URLs")).array().typed()
+    }
+}
+
+deref!('a, OutputProfileRef<'a> => Dict<'a>, dict);
+
 /// The output intent subtype.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum OutputIntentSubtype<'a> {
     /// `GTS_PDFX`
     PDFX,
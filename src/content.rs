@@ -3,6 +3,9 @@ use super::*;
 /// A builder for a content stream.
 pub struct Content {
     buf: Vec<u8>,
+    depth: usize,
+    unmatched_restores: usize,
+    max_line_length: Option<usize>,
 }
 
 /// Core methods.
@@ -15,13 +18,68 @@ impl Content {
 
     /// Create a new content stream with the specified initial buffer capacity.
     pub fn with_capacity(capacity: usize) -> Self {
-        Self { buf: Vec::with_capacity(capacity) }
+        Self {
+            buf: Vec::with_capacity(capacity),
+            depth: 0,
+            unmatched_restores: 0,
+            max_line_length: None,
+        }
+    }
+
+    /// Set the maximum length of a line in the content stream, e.g. `Some(255)`
+    /// for the limit recommended by the specification.
+    ///
+    /// Longer operations are broken into multiple lines between their
+    /// operands. Strings and names are never split, so a line may still be
+    /// longer if a single operand exceeds the limit. Operands written with
+    /// [`Obj::raw`] or a custom [`Primitive`] are not understood by the
+    /// wrapping and may have their spaces turned into newlines, so don't
+    /// enable it if they contain spaces that must be kept, e.g. in inline
+    /// image data.
+    ///
+    /// _Default value_: `None`, lines are not wrapped.
+    pub fn set_max_line_length(&mut self, max: Option<usize>) -> &mut Self {
+        self.max_line_length = max;
+        self
     }
 
     /// Start writing an arbitrary operation.
     #[inline]
     pub fn op<'a>(&'a mut self, operator: &'a str) -> Operation<'a> {
-        Operation::start(&mut self.buf, operator)
+        Operation::start(&mut self.buf, operator, self.max_line_length)
+    }
+
+    /// The number of bytes written so far.
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Whether no operations were written so far.
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// The number of graphics states that were [saved](Self::save_state) but
+    /// not yet [restored](Self::restore_state).
+    ///
+    /// States that are saved or restored through [`op`](Self::op) are not
+    /// tracked and restoring without a saved state keeps the depth at zero.
+    pub fn state_nesting_depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Append the operations of another content stream to this one.
+    ///
+    /// The bytes are moved over as they are. Restores in `other` without a
+    /// saved state of its own close the states that are still open in this
+    /// stream before the [nesting depth](Self::state_nesting_depth) of `other`
+    /// is added to this one's.
+    pub fn extend(&mut self, other: Content) -> &mut Self {
+        self.buf.extend(other.buf);
+        let closed = other.unmatched_restores.min(self.depth);
+        self.unmatched_restores += other.unmatched_restores - closed;
+        self.depth = self.depth - closed + other.depth;
+        self
     }
 
     /// Return the raw constructed byte stream.
@@ -33,6 +91,12 @@ impl Content {
     }
 }
 
+impl Default for Content {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Writer for an _operation_ in a content stream.
 ///
 /// This struct is created by [`Content::op`].
@@ -40,12 +104,19 @@ pub struct Operation<'a> {
     buf: &'a mut Vec<u8>,
     op: &'a str,
     first: bool,
+    start: usize,
+    max_line_length: Option<usize>,
 }
 
 impl<'a> Operation<'a> {
     #[inline]
-    pub(crate) fn start(buf: &'a mut Vec<u8>, op: &'a str) -> Self {
-        Self { buf, op, first: true }
+    pub(crate) fn start(
+        buf: &'a mut Vec<u8>,
+        op: &'a str,
+        max_line_length: Option<usize>,
+    ) -> Self {
+        let start = buf.len();
+        Self { buf, op, first: true, start, max_line_length }
     }
 
     /// Write a primitive operand.
@@ -87,6 +158,62 @@ impl Drop for Operation<'_> {
         }
         self.buf.extend(self.op.as_bytes());
         self.buf.push(b'\n');
+
+        if let Some(max) = self.max_line_length {
+            if self.buf.len() - self.start > max {
+                wrap_lines(&mut self.buf[self.start..], max);
+            }
+        }
+    }
+}
+
+/// Break the lines of a written operation that are longer than `max` bytes
+/// by turning spaces between tokens into newlines.
+fn wrap_lines(buf: &mut [u8], max: usize) {
+    let mut line_start = 0;
+    let mut space = None;
+    let mut literal = 0;
+    let mut hex = false;
+    let mut escaped = false;
+
+    for i in 0..buf.len() {
+        let byte = buf[i];
+        if byte == b'\n' || byte == b'\r' {
+            line_start = i + 1;
+            space = None;
+            escaped = false;
+            continue;
+        }
+
+        if literal > 0 {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'(' => literal += 1,
+                b')' => literal -= 1,
+                _ => {}
+            }
+        } else if hex {
+            hex = byte != b'>';
+        } else {
+            match byte {
+                b'(' => literal = 1,
+                b'<' if buf.get(i + 1) != Some(&b'<')
+                    && (i == 0 || buf[i - 1] != b'<') =>
+                {
+                    hex = true
+                }
+                b' ' => space = Some(i),
+                _ => {}
+            }
+        }
+
+        if i - line_start >= max {
+            if let Some(pos) = space.take() {
+                buf[pos] = b'\n';
+                line_start = pos + 1;
+            }
+        }
     }
 }
 
@@ -236,19 +363,59 @@ impl RenderingIntent {
     }
 }
 
+/// An error that occurs when [saving the graphics
+/// state](Content::save_state_checked) would nest too many graphics states.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct NestingLimitError;
+
+impl NestingLimitError {
+    /// The maximum nesting depth of graphics states.
+    const MAX_DEPTH: usize = 28;
+}
+
+impl std::fmt::Display for NestingLimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "graphics states must not be nested more than {} levels deep",
+            Self::MAX_DEPTH,
+        )
+    }
+}
+
+impl std::error::Error for NestingLimitError {}
+
 /// Special graphics state.
 impl Content {
     /// `q`: Save the graphics state on the stack.
     #[inline]
     pub fn save_state(&mut self) -> &mut Self {
         self.op("q");
+        self.depth += 1;
         self
     }
 
+    /// `q`: Save the graphics state on the stack, unless this would nest more
+    /// than 28 graphics states, the limit recommended by the specification.
+    ///
+    /// Nothing is written if this returns an error.
+    #[inline]
+    pub fn save_state_checked(&mut self) -> Result<&mut Self, NestingLimitError> {
+        if self.depth >= NestingLimitError::MAX_DEPTH {
+            return Err(NestingLimitError);
+        }
+        Ok(self.save_state())
+    }
+
     /// `Q`: Restore the graphics state from the stack.
     #[inline]
     pub fn restore_state(&mut self) -> &mut Self {
         self.op("Q");
+        if self.depth == 0 {
+            self.unmatched_restores += 1;
+        } else {
+            self.depth -= 1;
+        }
         self
     }
 
@@ -294,7 +461,7 @@ impl Content {
     }
 
     /// `v`: Append a cubic Bézier segment to (x3, y3) with (x2, y2) as control
-    /// point.
+    /// point. The other control point is the current point.
     #[inline]
     pub fn cubic_to_initial(&mut self, x2: f32, y2: f32, x3: f32, y3: f32) -> &mut Self {
         self.op("v").operands([x2, y2, x3, y3]);
@@ -302,7 +469,7 @@ impl Content {
     }
 
     /// `y`: Append a cubic Bézier segment to (x3, y3) with (x1, y1) as control
-    /// point.
+    /// point. The other control point is (x3, y3).
     #[inline]
     pub fn cubic_to_final(&mut self, x1: f32, y1: f32, x3: f32, y3: f32) -> &mut Self {
         self.op("y").operands([x1, y1, x3, y3]);
@@ -492,7 +659,9 @@ pub enum TextRenderingMode {
     Stroke,
     /// First fill and then stroke the text.
     FillStroke,
-    /// Don't fill and don't stroke the text.
+    /// Don't fill and don't stroke the text. This is useful for text that is
+    /// selectable and searchable, but not visible, e.g. the recognized text
+    /// over a scanned page.
     Invisible,
     /// Fill the text, then apply the text outlines to the current clipping
     /// path.
@@ -641,7 +810,10 @@ impl<'a> PositionedItems<'a> {
     /// Specify an adjustment between two glyphs.
     ///
     /// The `amount` is specified in thousands of units of text space and is
-    /// subtracted from the current writing-mode dependent coordinate.
+    /// subtracted from the current writing-mode dependent coordinate. In
+    /// horizontal writing, a positive amount thus moves the next glyph to the
+    /// left. In vertical writing, it moves the next glyph down, widening the
+    /// gap.
     #[inline]
     pub fn adjust(&mut self, amount: f32) -> &mut Self {
         self.array.item(amount);
@@ -738,6 +910,23 @@ impl Content {
         self
     }
 
+    /// `CS` and `SCN`: Set the stroke color space to a pattern color space and
+    /// the stroke color to a pattern in one go. PDF 1.2+.
+    ///
+    /// For a colored pattern, `space` is typically
+    /// [`ColorSpaceOperand::Pattern`] and the `tint` iterator shall remain
+    /// empty. For an uncolored pattern, `space` must name a pattern color
+    /// space with a base color space, in which the `tint` is given.
+    #[inline]
+    pub fn set_stroke_pattern_with_space<'a>(
+        &mut self,
+        space: impl Into<ColorSpaceOperand<'a>>,
+        tint: impl IntoIterator<Item = f32>,
+        name: Name,
+    ) -> &mut Self {
+        self.set_stroke_color_space(space).set_stroke_pattern(tint, name)
+    }
+
     /// `scn`: Set the fill color to the parameter within the current color
     /// space. PDF 1.2+.
     #[inline]
@@ -761,6 +950,23 @@ impl Content {
         self
     }
 
+    /// `cs` and `scn`: Set the fill color space to a pattern color space and
+    /// the fill color to a pattern in one go. PDF 1.2+.
+    ///
+    /// For a colored pattern, `space` is typically
+    /// [`ColorSpaceOperand::Pattern`] and the `tint` iterator shall remain
+    /// empty. For an uncolored pattern, `space` must name a pattern color
+    /// space with a base color space, in which the `tint` is given.
+    #[inline]
+    pub fn set_fill_pattern_with_space<'a>(
+        &mut self,
+        space: impl Into<ColorSpaceOperand<'a>>,
+        tint: impl IntoIterator<Item = f32>,
+        name: Name,
+    ) -> &mut Self {
+        self.set_fill_color_space(space).set_fill_pattern(tint, name)
+    }
+
     /// `G`: Set the stroke color to the parameter and the color space to
     /// `DeviceGray`.
     #[inline]
@@ -912,6 +1118,13 @@ impl Content {
     }
 
     /// `BDC`: Start writing a "begin marked content" operation. PDF 1.2+.
+    ///
+    /// The property list can either be written inline with
+    /// [`MarkContent::properties`] or referenced by name from the
+    /// [`Resources::properties_dict`] dictionary with
+    /// [`MarkContent::properties_named`]. The latter is required if the
+    /// property list contains indirect references, e.g. for the `/OC` tag of
+    /// optional content.
     #[inline]
     pub fn begin_marked_content_with_properties(&mut self, tag: Name) -> MarkContent<'_> {
         let mut op = self.op("BDC");
@@ -925,6 +1138,33 @@ impl Content {
         self.op("EMC");
         self
     }
+
+    /// `BDC` and `EMC`: Wrap the content written by `f` into a `/Span`
+    /// marked-content sequence with an `/ActualText`. PDF 1.5+.
+    ///
+    /// Text extraction then yields the actual text instead of the glyphs
+    /// shown within the sequence, e.g. the unhyphenated form of a word that is
+    /// split across lines or the characters of a ligature.
+    ///
+    /// ```
+    /// use pdf_writer::{Content, Str, TextStr};
+    ///
+    /// let mut content = Content::new();
+    /// content.span_with_actual_text(TextStr("ffi"), |content| {
+    ///     content.show(Str(b"\x01"));
+    /// });
+    /// ```
+    pub fn span_with_actual_text(
+        &mut self,
+        text: TextStr,
+        f: impl FnOnce(&mut Self),
+    ) -> &mut Self {
+        self.begin_marked_content_with_properties(Name(b"Span"))
+            .properties()
+            .actual_text(text);
+        f(self);
+        self.end_marked_content()
+    }
 }
 
 /// Writer for a _begin marked content operation_. PDF 1.3+.
@@ -940,13 +1180,16 @@ impl<'a> MarkContent<'a> {
 
     /// Start writing this marked content's property list. Mutually exclusive
     /// with [`properties_named`](Self::properties_named).
+    ///
+    /// Inline property lists must only contain direct objects.
     #[inline]
     pub fn properties(&mut self) -> PropertyList<'_> {
         self.op.obj().start()
     }
 
     /// Reference a property list from the Resource dictionary. These property
-    /// lists can be written using the [`Resources::properties`] method.
+    /// lists can be written using the [`Resources::properties`] or
+    /// [`Resources::properties_dict`] methods.
     /// Mutually exclusive with [`properties`](Self::properties).
     #[inline]
     pub fn properties_named(mut self, name: Name) {
@@ -980,6 +1223,44 @@ impl<'a> PropertyList<'a> {
         self
     }
 
+    /// Write the `/Alt` attribute to set an alternate description of this
+    /// marked content sequence, e.g. for a figure. PDF 1.5+.
+    #[inline]
+    pub fn alt(&mut self, alt: TextStr) -> &mut Self {
+        self.pair(Name(b"Alt"), alt);
+        self
+    }
+
+    /// Write the `/E` attribute to set the expanded form of an abbreviation
+    /// or acronym in this marked content sequence. PDF 1.5+.
+    #[inline]
+    pub fn expanded(&mut self, expansion: TextStr) -> &mut Self {
+        self.pair(Name(b"E"), expansion);
+        self
+    }
+
+    /// Write the `/Lang` attribute to set the language of this marked content
+    /// sequence if it differs from the surrounding content. PDF 1.4+.
+    #[inline]
+    pub fn lang(&mut self, lang: TextStr) -> &mut Self {
+        self.pair(Name(b"Lang"), lang);
+        self
+    }
+
+    /// Write the `/AF` attribute to reference [file
+    /// specifications](FileSpec) of files associated with this
+    /// marked content sequence. The property list must then be referenced
+    /// through [`properties_named`](MarkContent::properties_named) since the
+    /// references are indirect objects. PDF 2.0+.
+    #[inline]
+    pub fn associated_files(
+        &mut self,
+        files: impl IntoIterator<Item = Ref>,
+    ) -> &mut Self {
+        self.insert(Name(b"AF")).array().items(files);
+        self
+    }
+
     /// Start writing artifact property list. The tag of the marked content
     /// operation must have been `/Artifact`. PDF 1.4+.
     #[inline]
@@ -1071,7 +1352,7 @@ impl ArtifactType {
 }
 
 /// The various subtypes of pagination artifacts.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum ArtifactSubtype<'a> {
     /// Headers.
     Header,
@@ -1138,11 +1419,19 @@ impl Content {
 ///
 /// This struct is created by [`Pages::resources`], [`Page::resources`],
 /// [`FormXObject::resources`], and [`TilingPattern::resources`].
+///
+/// With the [`Legacy14`](crate::Profile::Legacy14) profile, the [default
+/// procedure sets](Self::default_proc_set) are written when the dictionary is
+/// finished unless a `/ProcSet` was written.
 pub struct Resources<'a> {
     dict: Dict<'a>,
 }
 
-writer!(Resources: |obj| Self { dict: obj.dict() });
+writer!(Resources: |obj| {
+    let mut dict = obj.dict();
+    dict.watch(&[b"ProcSet"]);
+    Self { dict }
+});
 
 impl<'a> Resources<'a> {
     /// Start writing the `/XObject` dictionary.
@@ -1168,8 +1457,8 @@ impl<'a> Resources<'a> {
     ///
     /// Relevant types:
     /// - [`ColorSpace`]
-    pub fn color_spaces(&mut self) -> Dict<'_> {
-        self.insert(Name(b"ColorSpace")).dict()
+    pub fn color_spaces(&mut self) -> ColorSpaces<'_> {
+        self.insert(Name(b"ColorSpace")).start()
     }
 
     /// Start writing the `/Pattern` dictionary. PDF 1.2+.
@@ -1209,12 +1498,14 @@ impl<'a> Resources<'a> {
         self
     }
 
-    /// Write the `/ProcSet` attribute with all available procedure sets.
+    /// Write the `/ProcSet` attribute with the default procedure sets
+    /// `[/PDF /Text /ImageB /ImageC /ImageI]`, i.e. all available ones.
     ///
     /// The PDF 1.7 specification recommends that modern PDFs either omit the
     /// attribute or specify all available procedure sets, as this function
-    /// does.
-    pub fn proc_sets_all(&mut self) -> &mut Self {
+    /// does. Some older viewers show blank pages if the attribute is missing,
+    /// so it is a good idea to write it for files targeting PDF 1.3 and below.
+    pub fn default_proc_set(&mut self) -> &mut Self {
         self.proc_sets([
             ProcSet::Pdf,
             ProcSet::Text,
@@ -1224,18 +1515,116 @@ impl<'a> Resources<'a> {
         ])
     }
 
+    /// Write the `/ProcSet` attribute with all available procedure sets.
+    #[deprecated = "use `default_proc_set`, which writes the same procedure sets"]
+    pub fn proc_sets_all(&mut self) -> &mut Self {
+        self.default_proc_set()
+    }
+
     /// Start writing the `/Properties` attribute.
     ///
-    /// This allows to write property lists with indirect objects for
-    /// marked-content sequences. These properties can be used by property lists
-    /// using the [`MarkContent::properties_named`] method. PDF 1.2+.
-    pub fn properties(&mut self) -> TypedDict<'_, PropertyList> {
+    /// This allows to write property lists for marked-content sequences.
+    /// These properties can be used by property lists using the
+    /// [`MarkContent::properties_named`] method. PDF 1.2+.
+    ///
+    /// To reference property lists that are indirect objects, such as optional
+    /// content groups, use [`properties_dict`](Self::properties_dict) instead.
+    pub fn properties(&mut self) -> TypedDict<'_, PropertyList<'_>> {
+        self.insert(Name(b"Properties")).dict().typed()
+    }
+
+    /// Start writing the `/Properties` dictionary with references to property
+    /// lists. PDF 1.2+.
+    ///
+    /// This maps names to indirect property lists for marked-content
+    /// sequences, such as optional content groups, which can then be
+    /// referenced with the [`MarkContent::properties_named`] method.
+    pub fn properties_dict(&mut self) -> TypedDict<'_, Ref> {
         self.insert(Name(b"Properties")).dict().typed()
     }
 }
 
+impl Drop for Resources<'_> {
+    fn drop(&mut self) {
+        if self.dict.profile() == Profile::Legacy14 && !self.dict.written(b"ProcSet") {
+            self.default_proc_set();
+        }
+    }
+}
+
 deref!('a, Resources<'a> => Dict<'a>, dict);
 
+/// Writer for the _color space dictionary_ of a [resource
+/// dictionary](Resources). PDF 1.1+.
+///
+/// Besides the color spaces that content streams select by name, the
+/// dictionary can hold default color spaces. These replace the device color
+/// spaces, including those implicitly selected by operators like
+/// [`Content::set_fill_rgb`], for all content that uses these resources.
+/// Typically, a default color space is an [ICC-based](ColorSpace::icc_based)
+/// space, so that device colors are rendered with a known profile. The
+/// default spaces don't affect an [output intent](Catalog::output_intents),
+/// which describes the device the document is produced for rather than the
+/// colors in its content.
+///
+/// This struct is created by [`Resources::color_spaces`].
+///
+/// # Example
+/// A page whose RGB colors are rendered with an embedded sRGB profile.
+/// ```
+/// use pdf_writer::{Content, Finish, PdfWriter, Rect, Ref};
+///
+/// # let srgb_profile = b"";
+/// let mut writer = PdfWriter::new();
+/// let mut page = writer.page(Ref::new(1));
+/// page.media_box(Rect::new(0.0, 0.0, 595.0, 842.0));
+/// page.contents(Ref::new(2));
+/// page.resources().color_spaces().default_rgb(Ref::new(3));
+/// page.finish();
+///
+/// // The device color operators are now calibrated.
+/// let mut content = Content::new();
+/// content.set_fill_rgb(1.0, 0.0, 0.0);
+/// content.rect(100.0, 100.0, 200.0, 200.0).fill_nonzero();
+/// writer.stream(Ref::new(2), &content.finish());
+///
+/// writer.color_space(Ref::new(3)).icc_based(Ref::new(4));
+/// writer.icc_profile(Ref::new(4), srgb_profile).n(3);
+/// ```
+pub struct ColorSpaces<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(ColorSpaces: |obj| Self { dict: obj.dict() });
+
+impl<'a> ColorSpaces<'a> {
+    /// Write the `/DefaultRGB` attribute to reference the [color
+    /// space](PdfWriter::color_space) that replaces `DeviceRGB`. It must have
+    /// three components.
+    pub fn default_rgb(&mut self, color_space: Ref) -> &mut Self {
+        self.pair(Name(b"DefaultRGB"), color_space);
+        self
+    }
+
+    /// Write the `/DefaultCMYK` attribute to reference the [color
+    /// space](PdfWriter::color_space) that replaces `DeviceCMYK`. It must have
+    /// four components.
+    pub fn default_cmyk(&mut self, color_space: Ref) -> &mut Self {
+        self.pair(Name(b"DefaultCMYK"), color_space);
+        self
+    }
+
+    /// Write the `/DefaultGray` attribute to reference the [color
+    /// space](PdfWriter::color_space) that replaces `DeviceGray`. It must have
+    /// one component.
+    pub fn default_gray(&mut self, color_space: Ref) -> &mut Self {
+        self.pair(Name(b"DefaultGray"), color_space);
+        self
+    }
+}
+
+deref!('a, ColorSpaces<'a> => Dict<'a>, dict);
+
 /// What procedure sets to send to a PostScript printer or other output device.
 ///
 /// This enumeration provides compatibility for printing PDFs of versions 1.3 and
@@ -1341,6 +1730,9 @@ impl<'a> ExtGraphicsState<'a> {
 
     /// Write the `OPM` attribute to set the overprint mode for components that
     /// have been zeroed out. PDF 1.3+.
+    ///
+    /// This only has an effect if [overprinting](Self::overprint) is enabled
+    /// and the current color space is `DeviceCMYK`.
     pub fn overprint_mode(&mut self, mode: OverprintMode) -> &mut Self {
         self.pair(Name(b"OPM"), mode.to_int());
         self
@@ -1389,6 +1781,21 @@ impl<'a> ExtGraphicsState<'a> {
         self
     }
 
+    /// Write the `TR` attribute to set a separate transfer function for each of
+    /// the four primary color components (red, green, blue, and gray or cyan,
+    /// magenta, yellow, and black).
+    pub fn transfer_components(&mut self, funcs: [Ref; 4]) -> &mut Self {
+        self.insert(Name(b"TR")).array().items(funcs);
+        self
+    }
+
+    /// Write the `TR` attribute to set the transfer function to the identity
+    /// function.
+    pub fn transfer_identity(&mut self) -> &mut Self {
+        self.pair(Name(b"TR"), Name(b"Identity"));
+        self
+    }
+
     /// Write the `TR2` attribute to set the transfer function back to the
     /// function that has been in effect at the beginning of the page. PDF 1.3+.
     pub fn transfer_default(&mut self) -> &mut Self {
@@ -1397,6 +1804,10 @@ impl<'a> ExtGraphicsState<'a> {
     }
 
     /// Write the `HT` attribute to set the halftone.
+    ///
+    /// The reference shall point to a [halftone dictionary](Halftone),
+    /// [composite halftone](CompositeHalftone), or [threshold
+    /// halftone](ThresholdHalftone).
     pub fn halftone(&mut self, ht: Ref) -> &mut Self {
         self.pair(Name(b"HT"), ht);
         self
@@ -1627,6 +2038,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_content_painting() {
+        let mut content = Content::new();
+        content.x_object(Name(b"Im0")).shading(Name(b"Sh0"));
+        assert_eq!(content.finish(), b"/Im0 Do\n/Sh0 sh");
+    }
+
+    #[test]
+    fn test_content_patterns() {
+        let mut content = Content::new();
+        content
+            .set_fill_pattern_with_space(ColorSpaceOperand::Pattern, [], Name(b"P1"))
+            .set_fill_pattern_with_space(Name(b"CsP"), [0.2, 0.4, 0.6], Name(b"P2"))
+            .set_stroke_pattern_with_space(Name(b"CsG"), [0.5], Name(b"P3"));
+        assert_eq!(
+            content.finish(),
+            b"/Pattern cs\n/P1 scn\n/CsP cs\n0.2 0.4 0.6 /P2 scn\n/CsG CS\n0.5 /P3 SCN"
+        );
+    }
+
+    #[test]
+    fn test_content_paths() {
+        let mut content = Content::new();
+        content
+            .move_to(0.0, 0.0)
+            .cubic_to(1.0, 2.0, 3.0, 4.0, 5.0, 6.0)
+            .cubic_to_initial(7.0, 8.0, 9.0, 10.0)
+            .cubic_to_final(11.0, 12.0, 13.0, 14.0)
+            .close_path()
+            .rect(0.0, 0.5, 10.0, 20.0);
+        assert_eq!(
+            content.finish(),
+            b"0 0 m\n1 2 3 4 5 6 c\n7 8 9 10 v\n11 12 13 14 y\nh\n0 0.5 10 20 re"
+        );
+    }
+
+    #[test]
+    fn test_content_text_state() {
+        let mut content = Content::new();
+        content
+            .set_char_spacing(0.5)
+            .set_word_spacing(2.0)
+            .set_horizontal_scaling(90.0)
+            .set_rise(-3.0)
+            .set_text_rendering_mode(TextRenderingMode::Invisible)
+            .set_text_rendering_mode(TextRenderingMode::Clip);
+        assert_eq!(content.finish(), b"0.5 Tc\n2 Tw\n90 Tz\n-3 Ts\n3 Tr\n7 Tr");
+    }
+
     #[test]
     fn test_content_text() {
         let mut content = Content::new();
@@ -1644,4 +2104,91 @@ mod tests {
 
         assert_eq!(content.finish(), b"/F1 12 Tf\nBT\n[] TJ\n[(AB) 2 (CD)] TJ\nET");
     }
+
+    #[test]
+    fn test_content_actual_text() {
+        let mut content = Content::new();
+        content.span_with_actual_text(TextStr("Straße (x)"), |content| {
+            content.show(Str(b"Stra\x01e"));
+        });
+        content
+            .begin_marked_content_with_properties(Name(b"Span"))
+            .properties()
+            .alt(TextStr("Pi"))
+            .expanded(TextStr("et cetera"))
+            .lang(TextStr("la"));
+        content.end_marked_content();
+        assert_eq!(
+            content.finish(),
+            b"/Span <<\n  /ActualText <FEFF0053007400720061\
+              00DF0065002000280078\
+              0029>\n>> BDC\n\
+              (Stra\x01e) Tj\nEMC\n\
+              /Span <<\n  /Alt (Pi)\n  /E (et cetera)\n  /Lang (la)\n>> BDC\nEMC"
+        );
+    }
+
+    #[test]
+    fn test_content_nesting() {
+        let mut content = Content::new();
+        assert!(content.is_empty());
+        for _ in 0..28 {
+            content.save_state_checked().unwrap();
+        }
+        assert_eq!(content.state_nesting_depth(), 28);
+        assert_eq!(content.save_state_checked().err(), Some(NestingLimitError));
+        assert_eq!(content.len(), 56);
+
+        let mut chunk = Content::new();
+        chunk.save_state().save_state().restore_state();
+        content.restore_state().extend(chunk);
+        assert_eq!(content.state_nesting_depth(), 28);
+        assert_eq!(content.len(), 64);
+
+        // The second chunk closes a state that was opened in the first one.
+        let mut first = Content::new();
+        first.save_state().save_state();
+        let mut second = Content::new();
+        second.restore_state().save_state();
+        first.extend(second);
+        assert_eq!(first.state_nesting_depth(), 2);
+        let mut third = Content::new();
+        third.restore_state().restore_state();
+        first.extend(third);
+        assert_eq!(first.state_nesting_depth(), 0);
+    }
+
+    #[test]
+    fn test_content_line_wrapping() {
+        let mut content = Content::new();
+        content.set_max_line_length(Some(16));
+        content
+            .show_positioned()
+            .items()
+            .show(Str(b"AB"))
+            .adjust(-250.0)
+            .show(Str(b"CD"));
+        content
+            .show_positioned()
+            .items()
+            .show(Str(b"long line\nwith spaces"))
+            .adjust(2.0)
+            .show(Str(b"(a b)"));
+        content.move_to(1.0, 2.0);
+
+        assert_eq!(
+            content.finish(),
+            b"[(AB) -250 (CD)]\nTJ\n[(long line\nwith spaces) 2\n<2861206229>] TJ\n1 2 m"
+        );
+
+        let mut buf = b"(a \\) b (c d)) e f <<\n/A <0 1>>> g\n".to_vec();
+        wrap_lines(&mut buf, 4);
+        assert_eq!(buf, b"(a \\) b (c d))\ne f\n<<\n/A\n<0 1>>>\ng\n");
+
+        // Without a maximum, raw operands are kept as they are.
+        let mut content = Content::new();
+        let data = b"1 2 ".repeat(100);
+        content.op("ID").obj().raw(&data);
+        assert_eq!(content.finish(), [data, b" ID".to_vec()].concat());
+    }
 }
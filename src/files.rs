@@ -59,6 +59,13 @@ impl<'a> FileSpec<'a> {
         self.insert(Name(b"EF")).dict().pair(Name(b"F"), id);
         self
     }
+
+    /// Start writing the `/CI` dictionary to set the values of the fields of
+    /// the document's [collection schema](CollectionSchema) for this file.
+    /// PDF 1.7+.
+    pub fn collection_item(&mut self) -> CollectionItem<'_> {
+        self.insert(Name(b"CI")).start()
+    }
 }
 
 deref!('a, FileSpec<'a> => Dict<'a>, dict);
@@ -68,21 +75,31 @@ deref!('a, FileSpec<'a> => Dict<'a>, dict);
 /// This struct is created by [`PdfWriter::embedded_file`].
 pub struct EmbeddedFile<'a> {
     stream: Stream<'a>,
+    size: Option<i32>,
 }
 
 impl<'a> EmbeddedFile<'a> {
     /// Create a new embedded file writer.
     pub(crate) fn start(mut stream: Stream<'a>) -> Self {
+        stream.watch(&[b"Params"]);
         stream.pair(Name(b"Type"), Name(b"EmbeddedFile"));
-        Self { stream }
+        Self { stream, size: None }
+    }
+
+    /// Create a new embedded file writer whose `/Params` dictionary contains
+    /// the given uncompressed size.
+    #[cfg(feature = "flate")]
+    pub(crate) fn start_with_size(stream: Stream<'a>, size: i32) -> Self {
+        let mut file = Self::start(stream);
+        file.size = Some(size);
+        file
     }
 
     /// Write the `/Subtype` attribute to set the file type.
     ///
     /// This can either be a MIME type or a name prefixed by a first class PDF
-    /// prefix. Note that special characters must be encoded as described in
-    /// section 7.3.5 of the PDF 1.7 specification, e.g. `image/svg+xml` would
-    /// become `Name(b"image#2Fsvg+xml")`.
+    /// prefix. Pass the MIME type as is, e.g. `Name(b"image/svg+xml")`. Special
+    /// characters are escaped automatically when the name is written.
     pub fn subtype(&mut self, subtype: Name) -> &mut Self {
         self.pair(Name(b"Subtype"), subtype);
         self
@@ -90,7 +107,24 @@ impl<'a> EmbeddedFile<'a> {
 
     /// Start writing the `/Params` dictionary.
     pub fn params(&mut self) -> EmbeddingParams<'_> {
-        self.insert(Name(b"Params")).start()
+        let size = self.size.take();
+        let mut params: EmbeddingParams = self.insert(Name(b"Params")).start();
+        if let Some(size) = size {
+            params.size(size);
+        }
+        params
+    }
+}
+
+impl Drop for EmbeddedFile<'_> {
+    fn drop(&mut self) {
+        // The size is only added if the `/Params` were not written at all,
+        // also not through the generic dictionary methods.
+        if let Some(size) = self.size.take() {
+            if !self.stream.written(b"Params") {
+                self.params().size(size);
+            }
+        }
     }
 }
 
@@ -131,6 +165,318 @@ impl<'a> EmbeddingParams<'a> {
         self.pair(Name(b"CheckSum"), checksum);
         self
     }
+
+    /// Write the `/CheckSum` attribute with the MD5 checksum of the given
+    /// file data.
+    ///
+    /// The data must be the uncompressed file contents, not the possibly
+    /// filtered stream data.
+    pub fn checksum_from_bytes(&mut self, data: &[u8]) -> &mut Self {
+        self.checksum(Str(&crate::md5::md5(data)))
+    }
 }
 
 deref!('a, EmbeddingParams<'a> => Dict<'a>, dict);
+
+/// Writer for a _collection dictionary_. PDF 1.7+.
+///
+/// A collection turns the document into a portable collection (also known as
+/// a portfolio), in which the viewer presents the embedded files of the
+/// document primarily.
+///
+/// This struct is created by [`Catalog::collection`].
+///
+/// # Example
+/// A portfolio of two files with a custom "Category" column.
+/// ```
+/// use pdf_writer::types::{CollectionFieldType, CollectionView};
+/// use pdf_writer::{Finish, Name, PdfWriter, Ref, Str, TextStr};
+///
+/// let mut writer = PdfWriter::new();
+/// let mut catalog = writer.catalog(Ref::new(1));
+/// catalog.pages(Ref::new(2));
+/// catalog.names().embedded_files().names()
+///     .insert(Str(b"data.csv"), Ref::new(4))
+///     .insert(Str(b"report.txt"), Ref::new(3));
+///
+/// let mut collection = catalog.collection();
+/// collection.view(CollectionView::Details);
+/// collection.document(Str(b"report.txt"));
+/// let mut schema = collection.schema();
+/// schema.field(Name(b"File"))
+///     .subtype(CollectionFieldType::FileName)
+///     .name(TextStr("Name"))
+///     .order(0);
+/// schema.field(Name(b"Category"))
+///     .subtype(CollectionFieldType::Text)
+///     .name(TextStr("Category"))
+///     .order(1);
+/// schema.finish();
+/// collection.sort().key(Name(b"Category")).ascending(true);
+/// collection.finish();
+/// catalog.finish();
+///
+/// writer.pages(Ref::new(2)).count(0);
+/// for (id, name, category) in [
+///     (Ref::new(3), "report.txt", "Text"),
+///     (Ref::new(4), "data.csv", "Tables"),
+/// ] {
+///     let mut file_spec = writer.file_spec(id);
+///     file_spec.path(Str(name.as_bytes()));
+///     file_spec.collection_item().text(Name(b"Category"), TextStr(category));
+/// }
+/// ```
+pub struct Collection<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(Collection: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"Collection"));
+    Self { dict }
+});
+
+impl<'a> Collection<'a> {
+    /// Start writing the `/Schema` dictionary to specify which fields are
+    /// shown for the files in the collection.
+    pub fn schema(&mut self) -> CollectionSchema<'_> {
+        self.insert(Name(b"Schema")).start()
+    }
+
+    /// Write the `/D` attribute to set the name of the document in the
+    /// [`/EmbeddedFiles`](Names::embedded_files) name tree that is initially
+    /// shown.
+    pub fn document(&mut self, name: Str) -> &mut Self {
+        self.pair(Name(b"D"), name);
+        self
+    }
+
+    /// Write the `/View` attribute to set how the viewer presents the
+    /// collection initially.
+    pub fn view(&mut self, view: CollectionView) -> &mut Self {
+        self.pair(Name(b"View"), view.to_name());
+        self
+    }
+
+    /// Start writing the `/Sort` dictionary to specify the order in which the
+    /// files of the collection are presented.
+    pub fn sort(&mut self) -> CollectionSort<'_> {
+        self.insert(Name(b"Sort")).start()
+    }
+}
+
+deref!('a, Collection<'a> => Dict<'a>, dict);
+
+/// How a viewer presents a collection initially.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum CollectionView {
+    /// List the files with the fields of the schema as columns.
+    Details,
+    /// Show the files as a grid of tiles.
+    Tile,
+    /// Hide the file list and only show the initial document.
+    Hidden,
+}
+
+impl CollectionView {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::Details => Name(b"D"),
+            Self::Tile => Name(b"T"),
+            Self::Hidden => Name(b"H"),
+        }
+    }
+}
+
+/// Writer for a _collection schema dictionary_. PDF 1.7+.
+///
+/// The schema maps field names to [collection field
+/// dictionaries](CollectionField). The values of the fields are set for each
+/// file through [`FileSpec::collection_item`].
+///
+/// This struct is created by [`Collection::schema`].
+pub struct CollectionSchema<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(CollectionSchema: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"CollectionSchema"));
+    Self { dict }
+});
+
+impl<'a> CollectionSchema<'a> {
+    /// Start writing a field dictionary with the given field name.
+    pub fn field(&mut self, name: Name) -> CollectionField<'_> {
+        self.insert(name).start()
+    }
+}
+
+deref!('a, CollectionSchema<'a> => Dict<'a>, dict);
+
+/// Writer for a _collection field dictionary_. PDF 1.7+.
+///
+/// This struct is created by [`CollectionSchema::field`].
+pub struct CollectionField<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(CollectionField: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"CollectionField"));
+    Self { dict }
+});
+
+impl<'a> CollectionField<'a> {
+    /// Write the `/Subtype` attribute to set the type of data in the field.
+    /// Required.
+    pub fn subtype(&mut self, kind: CollectionFieldType) -> &mut Self {
+        self.pair(Name(b"Subtype"), kind.to_name());
+        self
+    }
+
+    /// Write the `/N` attribute to set the name of the field shown to the
+    /// user. Required.
+    pub fn name(&mut self, name: TextStr) -> &mut Self {
+        self.pair(Name(b"N"), name);
+        self
+    }
+
+    /// Write the `/O` attribute to set the position of the field relative to
+    /// the other fields.
+    pub fn order(&mut self, order: i32) -> &mut Self {
+        self.pair(Name(b"O"), order);
+        self
+    }
+
+    /// Write the `/V` attribute to set whether the field is visible.
+    pub fn visible(&mut self, visible: bool) -> &mut Self {
+        self.pair(Name(b"V"), visible);
+        self
+    }
+}
+
+deref!('a, CollectionField<'a> => Dict<'a>, dict);
+
+/// The type of data in a collection field.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum CollectionFieldType {
+    /// A text string set through the collection items.
+    Text,
+    /// A date set through the collection items.
+    Date,
+    /// A number set through the collection items.
+    Number,
+    /// The file name of the embedded file.
+    FileName,
+    /// The description of the file specification.
+    Description,
+    /// The modification date of the embedded file.
+    ModificationDate,
+    /// The creation date of the embedded file.
+    CreationDate,
+    /// The size of the embedded file.
+    Size,
+}
+
+impl CollectionFieldType {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::Text => Name(b"S"),
+            Self::Date => Name(b"D"),
+            Self::Number => Name(b"N"),
+            Self::FileName => Name(b"F"),
+            Self::Description => Name(b"Desc"),
+            Self::ModificationDate => Name(b"ModDate"),
+            Self::CreationDate => Name(b"CreationDate"),
+            Self::Size => Name(b"Size"),
+        }
+    }
+}
+
+/// Writer for a _collection sort dictionary_. PDF 1.7+.
+///
+/// This struct is created by [`Collection::sort`].
+pub struct CollectionSort<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(CollectionSort: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"CollectionSort"));
+    Self { dict }
+});
+
+impl<'a> CollectionSort<'a> {
+    /// Write the `/S` attribute to set the name of the schema field by which
+    /// the files are sorted. Required.
+    pub fn key(&mut self, field: Name) -> &mut Self {
+        self.pair(Name(b"S"), field);
+        self
+    }
+
+    /// Write the `/S` attribute to sort the files by multiple schema fields.
+    /// Files that compare equal for the first field are ordered by the
+    /// second one and so on. Required.
+    pub fn keys<'n>(&mut self, fields: impl IntoIterator<Item = Name<'n>>) -> &mut Self {
+        self.insert(Name(b"S")).array().items(fields);
+        self
+    }
+
+    /// Write the `/A` attribute to set whether the files are sorted in
+    /// ascending order.
+    pub fn ascending(&mut self, ascending: bool) -> &mut Self {
+        self.pair(Name(b"A"), ascending);
+        self
+    }
+
+    /// Write the `/A` attribute to set for each of the [keys](Self::keys)
+    /// whether the files are sorted by it in ascending order.
+    pub fn ascending_each(
+        &mut self,
+        ascending: impl IntoIterator<Item = bool>,
+    ) -> &mut Self {
+        self.insert(Name(b"A")).array().items(ascending);
+        self
+    }
+}
+
+deref!('a, CollectionSort<'a> => Dict<'a>, dict);
+
+/// Writer for a _collection item dictionary_. PDF 1.7+.
+///
+/// The dictionary maps the field names of the [collection
+/// schema](CollectionSchema) to the values for one file.
+///
+/// This struct is created by [`FileSpec::collection_item`].
+pub struct CollectionItem<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(CollectionItem: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"CollectionItem"));
+    Self { dict }
+});
+
+impl<'a> CollectionItem<'a> {
+    /// Write the value of a text field.
+    pub fn text(&mut self, field: Name, value: TextStr) -> &mut Self {
+        self.pair(field, value);
+        self
+    }
+
+    /// Write the value of a date field.
+    pub fn date(&mut self, field: Name, value: Date) -> &mut Self {
+        self.pair(field, value);
+        self
+    }
+
+    /// Write the value of a number field.
+    pub fn number(&mut self, field: Name, value: f32) -> &mut Self {
+        self.pair(field, value);
+        self
+    }
+}
+
+deref!('a, CollectionItem<'a> => Dict<'a>, dict);
@@ -1,17 +1,41 @@
+use std::collections::{BTreeMap, HashMap};
+
+#[cfg(feature = "standard14")]
+use crate::types::Standard14Font;
+
 use super::*;
 
 /// Writer for a _Type-1 font dictionary_.
 ///
 /// This struct is created by [`PdfWriter::type1_font`].
+///
+/// With the [`Legacy14`](crate::Profile::Legacy14) profile and the
+/// `standard14` feature, a standard 14 font also gets the widths of
+/// `standard14_widths` when it is finished, unless any of
+/// `/FirstChar`, `/LastChar` and `/Widths` was written. This requires the
+/// `WinAnsiEncoding` for non-symbolic fonts and the built-in encoding for
+/// symbolic ones, in which the widths are measured.
 pub struct Type1Font<'a> {
     dict: Dict<'a>,
+    #[cfg(feature = "standard14")]
+    standard14: Option<Standard14Font>,
+    #[cfg(feature = "standard14")]
+    win_ansi: bool,
 }
 
 writer!(Type1Font: |obj| {
     let mut dict = obj.dict();
+    #[cfg(feature = "standard14")]
+    dict.watch(&[b"FirstChar", b"LastChar", b"Widths", b"Encoding"]);
     dict.pair(Name(b"Type"), Name(b"Font"));
     dict.pair(Name(b"Subtype"), Name(b"Type1"));
-    Self { dict }
+    Self {
+        dict,
+        #[cfg(feature = "standard14")]
+        standard14: None,
+        #[cfg(feature = "standard14")]
+        win_ansi: false,
+    }
 });
 
 impl<'a> Type1Font<'a> {
@@ -26,10 +50,20 @@ impl<'a> Type1Font<'a> {
     /// Write the `/BaseFont` attribute. This is the PostScript name of the
     /// font. Required.
     pub fn base_font(&mut self, name: Name) -> &mut Self {
+        #[cfg(feature = "standard14")]
+        if self.dict.profile() == Profile::Legacy14 {
+            self.standard14 = Standard14Font::from_base_font(name.0);
+        }
         self.pair(Name(b"BaseFont"), name);
         self
     }
 
+    /// Write the `/BaseFont` attribute for a subset of the font with the
+    /// name `name`, prefixed with the subset tag.
+    pub fn base_font_subset(&mut self, tag: SubsetTag, name: Name) -> &mut Self {
+        self.base_font(Name(&tag.prefix(name)))
+    }
+
     /// Write the `FirstChar` attribute, defining the first character code in
     /// the font's widths array. Required (except for standard 14 fonts
     /// before PDF 1.5).
@@ -63,6 +97,10 @@ impl<'a> Type1Font<'a> {
     /// Write the `/Encoding` attribute as a predefined encoding. Either this or
     /// [`encoding_custom`](Self::encoding_custom) is required.
     pub fn encoding_predefined(&mut self, encoding: Name) -> &mut Self {
+        #[cfg(feature = "standard14")]
+        {
+            self.win_ansi = encoding == Name(b"WinAnsiEncoding");
+        }
         self.pair(Name(b"Encoding"), encoding);
         self
     }
@@ -82,6 +120,29 @@ impl<'a> Type1Font<'a> {
     }
 }
 
+#[cfg(feature = "standard14")]
+impl Drop for Type1Font<'_> {
+    fn drop(&mut self) {
+        let font = match self.standard14 {
+            Some(font) => font,
+            None => return,
+        };
+
+        let measured = if font.is_symbolic() {
+            !self.dict.written(b"Encoding")
+        } else {
+            self.win_ansi
+        };
+
+        let keys: [&[u8]; 3] = [b"FirstChar", b"LastChar", b"Widths"];
+        if measured && !keys.iter().any(|key| self.dict.written(key)) {
+            self.first_char(32)
+                .last_char(255)
+                .widths(crate::standard14_widths(font));
+        }
+    }
+}
+
 deref!('a, Type1Font<'a> => Dict<'a>, dict);
 
 /// Writer for a _Type-3 font dictionary_.
@@ -203,6 +264,9 @@ writer!(Encoding: |obj| {
 impl<'a> Encoding<'a> {
     /// Write the `BaseEncoding` attribute, from which this encoding is
     /// described through differences.
+    ///
+    /// Must be one of `StandardEncoding`, `MacRomanEncoding`, or
+    /// `WinAnsiEncoding`.
     pub fn base_encoding(&mut self, name: Name) -> &mut Self {
         self.pair(Name(b"BaseEncoding"), name);
         self
@@ -218,6 +282,10 @@ deref!('a, Encoding<'a> => Dict<'a>, dict);
 
 /// Writer for an _encoding differences array_.
 ///
+/// Each call to [`consecutive`](Self::consecutive) starts a new run of
+/// character codes, so the array can skip over codes that keep their meaning
+/// from the base encoding.
+///
 /// This struct is created by [`Encoding::differences`].
 pub struct Differences<'a> {
     array: Array<'a>,
@@ -265,6 +333,12 @@ impl<'a> Type0Font<'a> {
         self
     }
 
+    /// Write the `/BaseFont` attribute for a subset of the font with the
+    /// name `name`, prefixed with the subset tag.
+    pub fn base_font_subset(&mut self, tag: SubsetTag, name: Name) -> &mut Self {
+        self.base_font(Name(&tag.prefix(name)))
+    }
+
     /// Write the `/Encoding` attribute as a predefined encoding. Either this or
     /// [`encoding_cmap`](Self::encoding_cmap) is required.
     pub fn encoding_predefined(&mut self, encoding: Name) -> &mut Self {
@@ -324,6 +398,12 @@ impl<'a> CidFont<'a> {
         self
     }
 
+    /// Write the `/BaseFont` attribute for a subset of the font with the
+    /// name `name`, prefixed with the subset tag.
+    pub fn base_font_subset(&mut self, tag: SubsetTag, name: Name) -> &mut Self {
+        self.base_font(Name(&tag.prefix(name)))
+    }
+
     /// Write the `/CIDSystemInfo` dictionary. Required.
     pub fn system_info(&mut self, info: SystemInfo) -> &mut Self {
         info.write(self.insert(Name(b"CIDSystemInfo")));
@@ -347,6 +427,48 @@ impl<'a> CidFont<'a> {
         self.insert(Name(b"W")).start()
     }
 
+    /// Write the `/DW` attribute and, if any width differs from it, the `/W`
+    /// array from a [`WidthsBuilder`].
+    pub fn widths_from(&mut self, widths: &WidthsBuilder) -> &mut Self {
+        let default = widths.default_width();
+        self.default_width(default);
+
+        let segments = widths.segments(default);
+        if !segments.is_empty() {
+            let mut array = self.widths();
+            for segment in segments {
+                match segment {
+                    WidthSegment::List(start, widths) => {
+                        array.consecutive(start, widths);
+                    }
+                    WidthSegment::Range(first, last, width) => {
+                        array.same(first, last, width);
+                    }
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Write the `/DW2` attribute, specifying the default vertical metrics
+    /// for vertical writing.
+    ///
+    /// The `position` is the vertical component of the vector from the
+    /// horizontal to the vertical origin of a glyph, whose horizontal
+    /// component is always half the glyph's width. The `advance` is the
+    /// vertical displacement of a glyph and is negative because vertical text
+    /// progresses downwards. Defaults to `880` and `-1000`.
+    pub fn default_vertical_metrics(&mut self, position: f32, advance: f32) -> &mut Self {
+        self.insert(Name(b"DW2")).array().item(position).item(advance);
+        self
+    }
+
+    /// Start writing the `/W2` (vertical metrics) array for vertical writing.
+    pub fn vertical_widths(&mut self) -> VerticalWidths<'_> {
+        self.insert(Name(b"W2")).start()
+    }
+
     /// Write the `/CIDToGIDMap` attribute as a predefined name.
     pub fn cid_to_gid_map_predefined(&mut self, name: Name) -> &mut Self {
         self.pair(Name(b"CIDToGIDMap"), name);
@@ -355,14 +477,53 @@ impl<'a> CidFont<'a> {
 
     /// Write the `/CIDToGIDMap` attribute as a reference to a stream, whose
     /// bytes directly map from CIDs to glyph indices.
+    ///
+    /// The stream data can be built with [`CidToGidMap`].
     pub fn cid_to_gid_map_stream(&mut self, stream: Ref) -> &mut Self {
         self.pair(Name(b"CIDToGIDMap"), stream);
         self
     }
+
+    /// Write the `/CIDToGIDMap` attribute as `/Identity`, meaning that each
+    /// CID is equal to its glyph index.
+    pub fn cid_to_gid_map_identity(&mut self) -> &mut Self {
+        self.cid_to_gid_map_predefined(Name(b"Identity"))
+    }
 }
 
 deref!('a, CidFont<'a> => Dict<'a>, dict);
 
+/// The mapping from CIDs to glyph indices of a `CIDFontType2` font.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum CidToGidMap {
+    /// Each CID is equal to its glyph index. Write this with
+    /// [`CidFont::cid_to_gid_map_identity`].
+    Identity,
+    /// The data of a stream that maps each CID to its glyph index as two
+    /// big-endian bytes. Write this with [`PdfWriter::stream`] and
+    /// [`CidFont::cid_to_gid_map_stream`].
+    Stream(Vec<u8>),
+}
+
+impl CidToGidMap {
+    /// Build the mapping from glyph indices indexed by CID.
+    ///
+    /// Returns [`Identity`](Self::Identity) if every CID maps to the equal
+    /// glyph index.
+    pub fn new(gids: &[u16]) -> Self {
+        if gids.iter().enumerate().all(|(cid, &gid)| cid == usize::from(gid)) {
+            return Self::Identity;
+        }
+
+        let mut data = Vec::with_capacity(2 * gids.len());
+        for gid in gids {
+            data.extend(gid.to_be_bytes());
+        }
+
+        Self::Stream(data)
+    }
+}
+
 /// The subtype of a CID font.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum CidFontType {
@@ -414,6 +575,219 @@ impl<'a> Widths<'a> {
 
 deref!('a, Widths<'a> => Array<'a>, array);
 
+/// Writer for a _CID font vertical metrics array_.
+///
+/// Each CID's metrics consist of its vertical displacement `w1y` and the
+/// position vector `(vx, vy)` from its horizontal to its vertical origin. As
+/// with the [default](CidFont::default_vertical_metrics), the displacement is
+/// negative for text that progresses downwards, the `vx` is typically half the
+/// glyph's width, and the `vy` is typically the ascender.
+///
+/// This struct is created by [`CidFont::vertical_widths`].
+pub struct VerticalWidths<'a> {
+    array: Array<'a>,
+}
+
+writer!(VerticalWidths: |obj| Self { array: obj.array() });
+
+impl<'a> VerticalWidths<'a> {
+    /// Specifies individual metrics as `(w1y, vx, vy)` for a range of
+    /// consecutive CIDs starting at `start`.
+    pub fn consecutive(
+        &mut self,
+        start: u16,
+        metrics: impl IntoIterator<Item = (f32, f32, f32)>,
+    ) -> &mut Self {
+        self.item(i32::from(start));
+        let mut array = self.push().array();
+        for (w1y, vx, vy) in metrics {
+            array.item(w1y).item(vx).item(vy);
+        }
+        array.finish();
+        self
+    }
+
+    /// Specifies the same metrics for all CIDs between `first` and `last`.
+    pub fn same(
+        &mut self,
+        first: u16,
+        last: u16,
+        w1y: f32,
+        vx: f32,
+        vy: f32,
+    ) -> &mut Self {
+        self.item(i32::from(first));
+        self.item(i32::from(last));
+        self.item(w1y).item(vx).item(vy);
+        self
+    }
+}
+
+deref!('a, VerticalWidths<'a> => Array<'a>, array);
+
+/// A builder for the widths of a [CID font](CidFont).
+///
+/// Feed it the width of each CID in any order and write it with
+/// [`CidFont::widths_from`]. The most common width becomes the default width
+/// and the remaining widths are compressed into the shortest `/W` array,
+/// choosing between the list and the range form for each run of consecutive
+/// CIDs.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WidthsBuilder {
+    widths: BTreeMap<u16, f32>,
+}
+
+/// A part of a `/W` array.
+enum WidthSegment {
+    /// Individual widths for consecutive CIDs from a start CID.
+    List(u16, Vec<f32>),
+    /// The same width for all CIDs in a range.
+    Range(u16, u16, f32),
+}
+
+impl WidthsBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the width of a CID, replacing an earlier width of the same CID.
+    pub fn insert(&mut self, cid: u16, width: f32) -> &mut Self {
+        self.widths.insert(cid, width);
+        self
+    }
+
+    /// The most common width, or `1000.0` if there are no widths. If multiple
+    /// widths are equally common, the one of the lowest CID is chosen.
+    pub fn default_width(&self) -> f32 {
+        // Maps the bits of each width to its count and its lowest CID.
+        let mut counts = HashMap::new();
+        for (&cid, &width) in &self.widths {
+            counts.entry(width.to_bits()).or_insert((0, cid)).0 += 1;
+        }
+
+        counts
+            .into_iter()
+            .max_by_key(|&(_, (count, cid))| (count, std::cmp::Reverse(cid)))
+            .map_or(1000.0, |(bits, _)| f32::from_bits(bits))
+    }
+
+    /// Split the widths that differ from the default into the segments with
+    /// the fewest array items.
+    fn segments(&self, default: f32) -> Vec<WidthSegment> {
+        let mut segments = vec![];
+        let mut block: Vec<(u16, f32)> = vec![];
+        for (&cid, &width) in &self.widths {
+            if block.last().map_or(false, |&(last, _)| last + 1 != cid) {
+                split_block(&block, default, &mut segments);
+                block.clear();
+            }
+
+            block.push((cid, width));
+        }
+
+        split_block(&block, default, &mut segments);
+        segments
+    }
+}
+
+/// How the width at an index of a block is described.
+#[derive(Copy, Clone)]
+enum WidthChoice {
+    /// Left out because it is the default width.
+    Skip,
+    /// Included in a list.
+    List,
+    /// Starts a range.
+    Range,
+}
+
+/// Split a block of consecutive CIDs into segments.
+///
+/// A list costs two items for the start CID and the nested array plus one per
+/// width while a range always costs three items. Widths that equal the
+/// default can be left out, but may also be included in a list if that
+/// bridges two lists. The cheapest split is found from the back, tracking
+/// whether a list that could be extended is open.
+fn split_block(block: &[(u16, f32)], default: f32, segments: &mut Vec<WidthSegment>) {
+    let n = block.len();
+    if n == 0 {
+        return;
+    }
+
+    // The index after the run of equal widths starting at each index.
+    let mut run_end = vec![n; n];
+    for i in (0..n - 1).rev() {
+        if block[i].1 == block[i + 1].1 {
+            run_end[i] = run_end[i + 1];
+        } else {
+            run_end[i] = i + 1;
+        }
+    }
+
+    // The lowest cost for the widths from each index if a list is closed (0)
+    // or open (1) before it, and how the width there is described.
+    let mut cost = vec![[0; 2]; n + 1];
+    let mut choice = vec![[WidthChoice::Skip; 2]; n];
+    for i in (0..n).rev() {
+        for open in 0..2 {
+            let list = if open == 1 { 1 } else { 3 } + cost[i + 1][1];
+            let span = run_end[i] - i;
+            let (best, how) = if block[i].1 == default {
+                if open == 0 || cost[i + 1][0] <= list {
+                    (cost[i + 1][0], WidthChoice::Skip)
+                } else {
+                    (list, WidthChoice::List)
+                }
+            } else if span >= 2 && 3 + cost[run_end[i]][0] <= list {
+                (3 + cost[run_end[i]][0], WidthChoice::Range)
+            } else {
+                (list, WidthChoice::List)
+            };
+            cost[i][open] = best;
+            choice[i][open] = how;
+        }
+    }
+
+    let mut i = 0;
+    let mut open = 0;
+    while i < n {
+        let (cid, width) = block[i];
+        match choice[i][open] {
+            WidthChoice::Skip => {
+                i += 1;
+                open = 0;
+            }
+            WidthChoice::List => {
+                match segments.last_mut() {
+                    Some(WidthSegment::List(_, widths)) if open == 1 => {
+                        widths.push(width)
+                    }
+                    _ => segments.push(WidthSegment::List(cid, vec![width])),
+                }
+                i += 1;
+                open = 1;
+            }
+            WidthChoice::Range => {
+                let end = run_end[i];
+                segments.push(WidthSegment::Range(cid, block[end - 1].0, width));
+                i = end;
+                open = 0;
+            }
+        }
+    }
+}
+
+impl FromIterator<(u16, f32)> for WidthsBuilder {
+    fn from_iter<I: IntoIterator<Item = (u16, f32)>>(iter: I) -> Self {
+        let mut builder = Self::new();
+        for (cid, width) in iter {
+            builder.insert(cid, width);
+        }
+        builder
+    }
+}
+
 /// Writer for a _font descriptor dictionary_.
 ///
 /// This struct is created by [`PdfWriter::font_descriptor`].
@@ -434,6 +808,13 @@ impl<'a> FontDescriptor<'a> {
         self
     }
 
+    /// Write the `/FontName` attribute for a subset of the font with the
+    /// name `name`, prefixed with the subset tag. This must match the base
+    /// font name of the font dictionary.
+    pub fn name_subset(&mut self, tag: SubsetTag, name: Name) -> &mut Self {
+        self.name(Name(&tag.prefix(name)))
+    }
+
     /// Write the `/FontFamily` attribute. Recommended for Type 3 fonts in
     /// Tagged PDFs. PDF 1.5+.
     pub fn family(&mut self, family: Str) -> &mut Self {
@@ -561,6 +942,16 @@ impl<'a> FontDescriptor<'a> {
         self.pair(Name(b"CharSet"), names);
         self
     }
+
+    /// Write the `/CIDSet` attribute, referencing a stream that identifies
+    /// the CIDs present in the embedded font program of a CIDFont subset. The
+    /// stream data can be built with a [`CidSet`]. PDF 1.3+.
+    ///
+    /// Required by PDF/A-1 for subsets of CIDFonts.
+    pub fn cid_set(&mut self, id: Ref) -> &mut Self {
+        self.pair(Name(b"CIDSet"), id);
+        self
+    }
 }
 
 deref!('a, FontDescriptor<'a> => Dict<'a>, dict);
@@ -648,10 +1039,75 @@ impl<'a> Cmap<'a> {
         info.write(self.insert(Name(b"CIDSystemInfo")));
         self
     }
+
+    /// Write the `/WMode` attribute to set whether the character map is for
+    /// vertical writing. Should match the program.
+    pub fn vertical(&mut self, vertical: bool) -> &mut Self {
+        self.pair(Name(b"WMode"), i32::from(vertical));
+        self
+    }
+
+    /// Write the `/UseCMap` attribute as a predefined character map on which
+    /// this one is based. Should match the program's `usecmap`.
+    pub fn use_cmap_predefined(&mut self, name: Name) -> &mut Self {
+        self.pair(Name(b"UseCMap"), name);
+        self
+    }
+
+    /// Write the `/UseCMap` attribute as a reference to a character map stream
+    /// on which this one is based. Should match the program's `usecmap`.
+    pub fn use_cmap(&mut self, cmap: Ref) -> &mut Self {
+        self.pair(Name(b"UseCMap"), cmap);
+        self
+    }
 }
 
 deref!('a, Cmap<'a> => Stream<'a>, stream);
 
+/// A builder for the data of a [`/CIDSet`](FontDescriptor::cid_set) stream.
+///
+/// The data contains one bit per CID, with the high-order bit of the first
+/// byte standing for CID 0. A bit is set if the CID is present in the font
+/// program. The data is padded with zero bits to a whole number of bytes.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct CidSet {
+    bits: Vec<u8>,
+}
+
+impl CidSet {
+    /// Create a new, empty CID set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark a CID as present.
+    ///
+    /// PDF/A validators expect CID 0 (the `.notdef` glyph) to be present.
+    pub fn insert(&mut self, cid: u16) -> &mut Self {
+        let byte = usize::from(cid / 8);
+        if byte >= self.bits.len() {
+            self.bits.resize(byte + 1, 0);
+        }
+        self.bits[byte] |= 0x80 >> (cid % 8);
+        self
+    }
+
+    /// Finish building the CID set and return the stream data.
+    pub fn finish(self) -> Vec<u8> {
+        self.bits
+    }
+}
+
+impl FromIterator<u16> for CidSet {
+    fn from_iter<I: IntoIterator<Item = u16>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for cid in iter {
+            set.insert(cid);
+        }
+        set
+    }
+}
+
 /// A builder for a `/ToUnicode` character map stream.
 pub struct UnicodeCmap {
     buf: Vec<u8>,
@@ -662,51 +1118,8 @@ pub struct UnicodeCmap {
 impl UnicodeCmap {
     /// Create a new, empty unicode character map.
     pub fn new(name: Name, info: SystemInfo) -> Self {
-        // https://www.adobe.com/content/dam/acom/en/devnet/font/pdfs/5014.CIDFont_Spec.pdf
-
         let mut buf = Vec::new();
-
-        // Static header.
-        buf.extend(b"%!PS-Adobe-3.0 Resource-CMap\n");
-        buf.extend(b"%%DocumentNeededResources: procset CIDInit\n");
-        buf.extend(b"%%IncludeResource: procset CIDInit\n");
-
-        // Dynamic header.
-        buf.extend(b"%%BeginResource: CMap ");
-        buf.extend(name.0);
-        buf.push(b'\n');
-        buf.extend(b"%%Title: (");
-        buf.extend(name.0);
-        buf.push(b' ');
-        buf.extend(info.registry.0);
-        buf.push(b' ');
-        buf.extend(info.ordering.0);
-        buf.push(b' ');
-        buf.push_int(info.supplement);
-        buf.extend(b")\n");
-        buf.extend(b"%%Version: 1\n");
-        buf.extend(b"%%EndComments\n");
-
-        // General body.
-        buf.extend(b"/CIDInit /ProcSet findresource begin\n");
-        buf.extend(b"12 dict begin\n");
-        buf.extend(b"begincmap\n");
-        buf.extend(b"/CIDSystemInfo 3 dict dup begin\n");
-        buf.extend(b"    /Registry ");
-        buf.push_val(info.registry);
-        buf.extend(b" def\n");
-        buf.extend(b"    /Ordering ");
-        buf.push_val(info.ordering);
-        buf.extend(b" def\n");
-        buf.extend(b"    /Supplement ");
-        buf.push_val(info.supplement);
-        buf.extend(b" def\n");
-        buf.extend(b"end def\n");
-        buf.extend(b"/CMapName ");
-        buf.push_val(name);
-        buf.extend(b" def\n");
-        buf.extend(b"/CMapVersion 1 def\n");
-        buf.extend(b"/CMapType 0 def\n");
+        write_cmap_header(&mut buf, name, info, 0);
 
         // We just cover the whole unicode codespace.
         buf.extend(b"1 begincodespacerange\n");
@@ -751,14 +1164,7 @@ impl UnicodeCmap {
         // Flush the in-progress range.
         self.flush_range();
 
-        // End of body.
-        self.buf.extend(b"endcmap\n");
-        self.buf.extend(b"CMapName currentdict /CMap defineresource pop\n");
-        self.buf.extend(b"end\n");
-        self.buf.extend(b"end\n");
-        self.buf.extend(b"%%EndResource\n");
-        self.buf.extend(b"%%EOF");
-
+        write_cmap_footer(&mut self.buf);
         self.buf
     }
 
@@ -775,6 +1181,240 @@ impl UnicodeCmap {
     }
 }
 
+/// A builder for a character map stream that maps character codes to CIDs,
+/// e.g. for a custom [encoding](Type0Font::encoding_cmap) of a composite font.
+///
+/// The finished data is written with [`PdfWriter::cmap`], whose dictionary
+/// should repeat the name, system info, writing mode, and parent character
+/// map of the builder.
+///
+/// ```
+/// use pdf_writer::types::{CidCmap, SystemInfo};
+/// use pdf_writer::{Name, Str};
+///
+/// let info = SystemInfo {
+///     registry: Str(b"Adobe"),
+///     ordering: Str(b"Identity"),
+///     supplement: 0,
+/// };
+///
+/// // Map one-byte codes to CIDs 1 to 95 and two-byte codes to CIDs 1000+.
+/// let mut cmap = CidCmap::new(Name(b"Custom-H"), info);
+/// cmap.codespace_range(&[0x00], &[0x7F]);
+/// cmap.codespace_range(&[0x80, 0x00], &[0xFF, 0xFF]);
+/// cmap.cid_range(&[0x20], &[0x7E], 1);
+/// cmap.cid_char(&[0x80, 0x41], 1000);
+/// let data = cmap.finish();
+/// ```
+pub struct CidCmap {
+    header: Vec<u8>,
+    vertical: bool,
+    use_cmap: Option<Vec<u8>>,
+    codespace: Vec<u8>,
+    codespace_count: i32,
+    body: Vec<u8>,
+    mappings: Vec<u8>,
+    kind: &'static [u8],
+    count: i32,
+}
+
+impl CidCmap {
+    /// Create a new, empty CID character map.
+    pub fn new(name: Name, info: SystemInfo) -> Self {
+        let mut header = Vec::new();
+        write_cmap_header(&mut header, name, info, 1);
+        Self {
+            header,
+            vertical: false,
+            use_cmap: None,
+            codespace: vec![],
+            codespace_count: 0,
+            body: vec![],
+            mappings: vec![],
+            kind: b"cidchar",
+            count: 0,
+        }
+    }
+
+    /// Set whether the character map is for vertical writing (`/WMode 1`).
+    pub fn vertical(&mut self, vertical: bool) {
+        self.vertical = vertical;
+    }
+
+    /// Base the character map on another one, whose codespace ranges and
+    /// mappings are included before the ones of this map. This is typically
+    /// used for a vertical map that only overrides some glyphs of its
+    /// horizontal counterpart.
+    pub fn use_cmap(&mut self, name: Name) {
+        let mut buf = vec![];
+        push_program_name(&mut buf, name);
+        self.use_cmap = Some(buf);
+    }
+
+    /// Add a range of valid character codes. Codes are one to four bytes long.
+    ///
+    /// Panics if the codes differ in length.
+    pub fn codespace_range(&mut self, low: &[u8], high: &[u8]) {
+        assert_eq!(low.len(), high.len(), "codes must have the same length");
+        push_code(&mut self.codespace, low);
+        self.codespace.push(b' ');
+        push_code(&mut self.codespace, high);
+        self.codespace.push(b'\n');
+        self.codespace_count += 1;
+    }
+
+    /// Add a mapping from a single character code to a CID.
+    pub fn cid_char(&mut self, code: &[u8], cid: u16) {
+        self.start_mapping(b"cidchar");
+        push_code(&mut self.mappings, code);
+        self.mappings.push(b' ');
+        self.mappings.push_int(i32::from(cid));
+        self.mappings.push(b'\n');
+    }
+
+    /// Add a mapping from a range of character codes to consecutive CIDs,
+    /// starting at `cid` for the code `low`.
+    ///
+    /// Panics if the codes differ in length.
+    pub fn cid_range(&mut self, low: &[u8], high: &[u8], cid: u16) {
+        assert_eq!(low.len(), high.len(), "codes must have the same length");
+        self.start_mapping(b"cidrange");
+        push_code(&mut self.mappings, low);
+        self.mappings.push(b' ');
+        push_code(&mut self.mappings, high);
+        self.mappings.push(b' ');
+        self.mappings.push_int(i32::from(cid));
+        self.mappings.push(b'\n');
+    }
+
+    /// Finish building the character map.
+    pub fn finish(mut self) -> Vec<u8> {
+        self.flush_mappings();
+
+        let mut buf = self.header;
+        if self.vertical {
+            buf.extend(b"/WMode 1 def\n");
+        }
+
+        if let Some(name) = self.use_cmap {
+            buf.extend(name);
+            buf.extend(b" usecmap\n");
+        }
+
+        if self.codespace_count > 0 {
+            buf.push_int(self.codespace_count);
+            buf.extend(b" begincodespacerange\n");
+            buf.extend(self.codespace);
+            buf.extend(b"endcodespacerange\n");
+        }
+
+        buf.extend(self.body);
+        write_cmap_footer(&mut buf);
+        buf
+    }
+
+    /// Prepare for a mapping of the given kind.
+    fn start_mapping(&mut self, kind: &'static [u8]) {
+        // At most 100 lines per section and one kind of mapping per section.
+        if self.kind != kind || self.count >= 100 {
+            self.flush_mappings();
+            self.kind = kind;
+        }
+        self.count += 1;
+    }
+
+    fn flush_mappings(&mut self) {
+        if self.count > 0 {
+            self.body.push_int(self.count);
+            self.body.extend(b" begin");
+            self.body.extend(self.kind);
+            self.body.push(b'\n');
+            self.body.extend(&self.mappings);
+            self.body.extend(b"end");
+            self.body.extend(self.kind);
+            self.body.push(b'\n');
+        }
+
+        self.count = 0;
+        self.mappings.clear();
+    }
+}
+
+/// Write a character code as a hexadecimal string.
+fn push_code(buf: &mut Vec<u8>, code: &[u8]) {
+    buf.push(b'<');
+    for &byte in code {
+        buf.push_hex(byte);
+    }
+    buf.push(b'>');
+}
+
+/// Write a name into a character map program. Unlike in PDF, the PostScript
+/// syntax of the program does not support `#` escapes.
+fn push_program_name(buf: &mut Vec<u8>, name: Name) {
+    buf.push(b'/');
+    buf.extend(name.0);
+}
+
+/// Write the start of a character map program up to its type.
+fn write_cmap_header(buf: &mut Vec<u8>, name: Name, info: SystemInfo, cmap_type: i32) {
+    // https://www.adobe.com/content/dam/acom/en/devnet/font/pdfs/5014.CIDFont_Spec.pdf
+
+    // Static header.
+    buf.extend(b"%!PS-Adobe-3.0 Resource-CMap\n");
+    buf.extend(b"%%DocumentNeededResources: procset CIDInit\n");
+    buf.extend(b"%%IncludeResource: procset CIDInit\n");
+
+    // Dynamic header.
+    buf.extend(b"%%BeginResource: CMap ");
+    buf.extend(name.0);
+    buf.push(b'\n');
+    buf.extend(b"%%Title: (");
+    buf.extend(name.0);
+    buf.push(b' ');
+    buf.extend(info.registry.0);
+    buf.push(b' ');
+    buf.extend(info.ordering.0);
+    buf.push(b' ');
+    buf.push_int(info.supplement);
+    buf.extend(b")\n");
+    buf.extend(b"%%Version: 1\n");
+    buf.extend(b"%%EndComments\n");
+
+    // General body.
+    buf.extend(b"/CIDInit /ProcSet findresource begin\n");
+    buf.extend(b"12 dict begin\n");
+    buf.extend(b"begincmap\n");
+    buf.extend(b"/CIDSystemInfo 3 dict dup begin\n");
+    buf.extend(b"    /Registry ");
+    buf.push_val(info.registry);
+    buf.extend(b" def\n");
+    buf.extend(b"    /Ordering ");
+    buf.push_val(info.ordering);
+    buf.extend(b" def\n");
+    buf.extend(b"    /Supplement ");
+    buf.push_val(info.supplement);
+    buf.extend(b" def\n");
+    buf.extend(b"end def\n");
+    buf.extend(b"/CMapName ");
+    push_program_name(buf, name);
+    buf.extend(b" def\n");
+    buf.extend(b"/CMapVersion 1 def\n");
+    buf.extend(b"/CMapType ");
+    buf.push_int(cmap_type);
+    buf.extend(b" def\n");
+}
+
+/// Write the end of a character map program.
+fn write_cmap_footer(buf: &mut Vec<u8>) {
+    buf.extend(b"endcmap\n");
+    buf.extend(b"CMapName currentdict /CMap defineresource pop\n");
+    buf.extend(b"end\n");
+    buf.extend(b"end\n");
+    buf.extend(b"%%EndResource\n");
+    buf.extend(b"%%EOF");
+}
+
 /// Specifics about a character collection.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct SystemInfo<'a> {
@@ -794,3 +1434,60 @@ impl SystemInfo<'_> {
             .pair(Name(b"Supplement"), self.supplement);
     }
 }
+
+/// The six-letter tag that prefixes the base name of a subset font, e.g.
+/// `ABCDEF` in `ABCDEF+Helvetica`.
+///
+/// The same tag must be used for all dictionaries that refer to the same
+/// subset, i.e. the font dictionary, the descendant font, and the font
+/// descriptor.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct SubsetTag([u8; 6]);
+
+impl SubsetTag {
+    /// Create a tag from six uppercase ASCII letters.
+    ///
+    /// Returns `None` if the tag has a different length or contains other
+    /// characters.
+    pub fn new(tag: &[u8]) -> Option<Self> {
+        let tag: [u8; 6] = tag.try_into().ok()?;
+        tag.iter().all(u8::is_ascii_uppercase).then_some(Self(tag))
+    }
+
+    /// Derive a tag from a seed. The same seed always results in the same
+    /// tag.
+    pub fn derive(seed: u64) -> Self {
+        // Mix the bits so that similar seeds result in dissimilar tags.
+        let mut hash = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        hash ^= hash >> 29;
+
+        let mut tag = [0; 6];
+        for letter in &mut tag {
+            *letter = b'A' + (hash % 26) as u8;
+            hash /= 26;
+        }
+        Self(tag)
+    }
+
+    /// Derive a tag from the data of the subset font program.
+    pub fn from_data(data: &[u8]) -> Self {
+        let digest = crate::md5::md5(data);
+        let mut seed = [0; 8];
+        seed.copy_from_slice(&digest[..8]);
+        Self::derive(u64::from_be_bytes(seed))
+    }
+
+    /// The letters of the tag.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Prefix a font name with the tag.
+    pub(crate) fn prefix(self, name: Name) -> Vec<u8> {
+        let mut prefixed = Vec::with_capacity(7 + name.0.len());
+        prefixed.extend(self.0);
+        prefixed.push(b'+');
+        prefixed.extend(name.0);
+        prefixed
+    }
+}
@@ -0,0 +1,430 @@
+use super::*;
+use crate::types::Quadding;
+
+/// Writer for an _interactive form dictionary_. PDF 1.2+.
+///
+/// This struct is created by [`PdfWriter::form`]. The form must be referenced
+/// from the document catalog with [`Catalog::form`].
+pub struct Form<'a> {
+    dict: Dict<'a>,
+    fonts: &'a mut FormFonts,
+}
+
+impl<'a> Form<'a> {
+    pub(crate) fn start(obj: Obj<'a>, fonts: &'a mut FormFonts) -> Self {
+        Self { dict: obj.dict(), fonts }
+    }
+
+    /// Write the `/Fields` attribute to reference the root fields of the
+    /// document. Required.
+    pub fn fields(&mut self, fields: impl IntoIterator<Item = Ref>) -> &mut Self {
+        self.insert(Name(b"Fields")).array().items(fields);
+        self
+    }
+
+    /// Write the `/NeedAppearances` attribute to set whether the viewer
+    /// generates the appearance streams of all widget annotations. Deprecated
+    /// in PDF 2.0.
+    ///
+    /// If this is false, viewers construct missing appearances from the
+    /// [default resources](Self::default_resources) and the default
+    /// appearance strings of the fields.
+    pub fn need_appearances(&mut self, need: bool) -> &mut Self {
+        self.pair(Name(b"NeedAppearances"), need);
+        self
+    }
+
+    /// Start writing the `/DR` dictionary with the resources that are shared
+    /// by the appearances of all fields.
+    ///
+    /// Fonts that are used in default appearance strings must be present in
+    /// the [`/Font`](FormResources::fonts) dictionary of these resources.
+    /// [`PdfWriter::finish`] fails if this is not the case, also if the
+    /// resources or their fonts are missing entirely.
+    pub fn default_resources(&mut self) -> FormResources<'_> {
+        FormResources {
+            resources: self.dict.insert(Name(b"DR")).start(),
+            fonts: self.fonts,
+        }
+    }
+
+    /// Write the `/DA` attribute to set the default appearance of the text of
+    /// fields that do not specify their own, e.g. `/Helv 12 Tf 0 g`.
+    ///
+    /// The operators can be built with [`DefaultAppearance`].
+    pub fn default_appearance(&mut self, appearance: Str) -> &mut Self {
+        self.fonts.record_use(appearance);
+        self.pair(Name(b"DA"), appearance);
+        self
+    }
+
+    /// Write the `/Q` attribute to set the default justification of the text
+    /// of fields that do not specify their own.
+    pub fn quadding(&mut self, quadding: Quadding) -> &mut Self {
+        self.pair(Name(b"Q"), quadding.to_int());
+        self
+    }
+}
+
+deref!('a, Form<'a> => Dict<'a>, dict);
+
+/// Writer for the _default resources_ of an [interactive form](Form).
+///
+/// This is like a [`Resources`] writer, but keeps track of the registered
+/// fonts. It does not deref to the resources so that fonts can't be written
+/// without being registered.
+///
+/// This struct is created by [`Form::default_resources`].
+pub struct FormResources<'a> {
+    resources: Resources<'a>,
+    fonts: &'a mut FormFonts,
+}
+
+impl<'a> FormResources<'a> {
+    /// Start writing the `/Font` dictionary.
+    pub fn fonts(&mut self) -> FormFontsDict<'_> {
+        FormFontsDict { dict: self.resources.fonts(), fonts: self.fonts }
+    }
+
+    /// Start writing the `/XObject` dictionary.
+    pub fn x_objects(&mut self) -> Dict<'_> {
+        self.resources.x_objects()
+    }
+
+    /// Start writing the `/ColorSpace` dictionary.
+    pub fn color_spaces(&mut self) -> ColorSpaces<'_> {
+        self.resources.color_spaces()
+    }
+
+    /// Start writing the `/Pattern` dictionary.
+    pub fn patterns(&mut self) -> Dict<'_> {
+        self.resources.patterns()
+    }
+
+    /// Start writing the `/Shading` dictionary.
+    pub fn shadings(&mut self) -> Dict<'_> {
+        self.resources.shadings()
+    }
+
+    /// Start writing the `/ExtGState` dictionary.
+    pub fn ext_g_states(&mut self) -> Dict<'_> {
+        self.resources.ext_g_states()
+    }
+}
+
+/// Writer for the `/Font` dictionary in the [default resources](FormResources)
+/// of an interactive form.
+///
+/// In contrast to other dictionaries, this writer does not deref to a
+/// [`Dict`] so that all fonts are registered for the check of the default
+/// appearance strings.
+///
+/// This struct is created by [`FormResources::fonts`].
+pub struct FormFontsDict<'a> {
+    dict: Dict<'a>,
+    fonts: &'a mut FormFonts,
+}
+
+impl<'a> FormFontsDict<'a> {
+    /// Write a pair mapping a font name to a font dictionary.
+    pub fn pair(&mut self, name: Name, font: Ref) -> &mut Self {
+        self.insert(name).primitive(font);
+        self
+    }
+
+    /// Start writing a pair with an arbitrary value, e.g. a direct font
+    /// dictionary.
+    pub fn insert(&mut self, name: Name) -> Obj<'_> {
+        self.fonts.registered.push(name.0.to_vec());
+        self.dict.insert(name)
+    }
+
+    /// The number of written fonts.
+    pub fn len(&self) -> i32 {
+        self.dict.len()
+    }
+
+    /// Whether no fonts have been written so far.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Tracks which fonts are registered in the default resources of a form and
+/// which fonts are used in default appearance strings.
+#[derive(Debug, Default)]
+pub(crate) struct FormFonts {
+    registered: Vec<Vec<u8>>,
+    used: Vec<Vec<u8>>,
+}
+
+impl FormFonts {
+    /// Record the font used in a default appearance string.
+    fn record_use(&mut self, appearance: Str) {
+        let tokens: Vec<&[u8]> = appearance
+            .0
+            .split(|b| b.is_ascii_whitespace())
+            .filter(|t| !t.is_empty())
+            .collect();
+        for window in tokens.windows(3) {
+            if let [font, _, b"Tf"] = window {
+                if let Some(name) = font.strip_prefix(b"/") {
+                    self.used.push(decode_name(name));
+                }
+            }
+        }
+    }
+
+    /// Check that all used fonts were registered in the default resources.
+    pub(crate) fn check(&self) -> Result<(), PdfError> {
+        match self.used.iter().find(|name| !self.registered.contains(name)) {
+            Some(name) => Err(PdfError::MissingFormFont(name.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Resolve the `#xx` escape sequences in a name as it appears in a content
+/// stream.
+fn decode_name(name: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::with_capacity(name.len());
+    let mut i = 0;
+    while i < name.len() {
+        let hex = name
+            .get(i + 1..i + 3)
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit));
+        match hex {
+            Some(hex) if name[i] == b'#' => {
+                let hex = std::str::from_utf8(hex).unwrap();
+                decoded.push(u8::from_str_radix(hex, 16).unwrap());
+                i += 3;
+            }
+            _ => {
+                decoded.push(name[i]);
+                i += 1;
+            }
+        }
+    }
+    decoded
+}
+
+/// Writer for a _form field dictionary_. PDF 1.2+.
+///
+/// Fields with a single widget annotation may be merged with it into one
+/// dictionary. In that case, the annotation entries can be written through the
+/// dictionary this writer derefs to.
+///
+/// This struct is created by [`PdfWriter::form_field`].
+pub struct Field<'a> {
+    dict: Dict<'a>,
+    fonts: &'a mut FormFonts,
+}
+
+impl<'a> Field<'a> {
+    pub(crate) fn start(obj: Obj<'a>, fonts: &'a mut FormFonts) -> Self {
+        Self { dict: obj.dict(), fonts }
+    }
+
+    /// Write the `/FT` attribute to set the kind of field. Required for
+    /// terminal fields, inherited otherwise.
+    pub fn field_type(&mut self, kind: FieldType) -> &mut Self {
+        self.pair(Name(b"FT"), kind.to_name());
+        self
+    }
+
+    /// Write the `/Parent` attribute to reference the parent field.
+    pub fn parent(&mut self, parent: Ref) -> &mut Self {
+        self.pair(Name(b"Parent"), parent);
+        self
+    }
+
+    /// Write the `/Kids` attribute to reference the children of the field,
+    /// which are either fields or widget annotations.
+    pub fn children(&mut self, children: impl IntoIterator<Item = Ref>) -> &mut Self {
+        self.insert(Name(b"Kids")).array().items(children);
+        self
+    }
+
+    /// Write the `/T` attribute to set the partial name of the field. The
+    /// fully qualified name joins the partial names of all ancestors with
+    /// periods.
+    pub fn partial_name(&mut self, name: TextStr) -> &mut Self {
+        self.pair(Name(b"T"), name);
+        self
+    }
+
+    /// Write the `/TU` attribute to set the name of the field that is shown
+    /// in the user interface. PDF 1.3+.
+    pub fn alternate_name(&mut self, name: TextStr) -> &mut Self {
+        self.pair(Name(b"TU"), name);
+        self
+    }
+
+    /// Write the `/TM` attribute to set the name of the field that is used
+    /// when exporting its data. PDF 1.3+.
+    pub fn mapping_name(&mut self, name: TextStr) -> &mut Self {
+        self.pair(Name(b"TM"), name);
+        self
+    }
+
+    /// Write the `/Ff` attribute to set the characteristics of the field.
+    pub fn field_flags(&mut self, flags: FieldFlags) -> &mut Self {
+        self.pair(Name(b"Ff"), flags.bits() as i32);
+        self
+    }
+
+    /// Write the `/V` attribute to set the value of a text field.
+    ///
+    /// If a [`rich_value`](Self::rich_value) is also written, this must
+    /// contain the same text without formatting.
+    pub fn text_value(&mut self, value: TextStr) -> &mut Self {
+        self.pair(Name(b"V"), value);
+        self
+    }
+
+    /// Write the `/DV` attribute to set the value of a text field that it is
+    /// reset to.
+    pub fn text_default_value(&mut self, value: TextStr) -> &mut Self {
+        self.pair(Name(b"DV"), value);
+        self
+    }
+
+    /// Write the `/DA` attribute to set the appearance of the text of the
+    /// field, e.g. `/Helv 12 Tf 0 g`. Required for fields containing text,
+    /// unless inherited from the parent or [form](Form::default_appearance).
+    ///
+    /// The font must be present in the [default resources of the
+    /// form](Form::default_resources). The operators can be built with
+    /// [`DefaultAppearance`].
+    pub fn default_appearance(&mut self, appearance: Str) -> &mut Self {
+        self.fonts.record_use(appearance);
+        self.pair(Name(b"DA"), appearance);
+        self
+    }
+
+    /// Write the `/Q` attribute to set the justification of the text of the
+    /// field.
+    pub fn quadding(&mut self, quadding: Quadding) -> &mut Self {
+        self.pair(Name(b"Q"), quadding.to_int());
+        self
+    }
+
+    /// Write the `/DS` attribute to set the default style of the [rich
+    /// value](Self::rich_value) as a CSS2 style string. PDF 1.5+.
+    pub fn default_style(&mut self, style: TextStr) -> &mut Self {
+        self.pair(Name(b"DS"), style);
+        self
+    }
+
+    /// Write the `/RV` attribute to set the value of a rich text field as an
+    /// XHTML string. PDF 1.5+.
+    ///
+    /// The rich value must be consistent with the plain [text
+    /// value](Self::text_value), i.e. contain the same text with added
+    /// formatting. Viewers that support rich text regenerate the appearance
+    /// from it.
+    pub fn rich_value(&mut self, value: TextStr) -> &mut Self {
+        self.pair(Name(b"RV"), value);
+        self
+    }
+
+    /// Write the `/RV` attribute to reference a stream with the XHTML value
+    /// of a rich text field. See [`rich_value`](Self::rich_value) for
+    /// details. PDF 1.5+.
+    pub fn rich_value_ref(&mut self, stream: Ref) -> &mut Self {
+        self.pair(Name(b"RV"), stream);
+        self
+    }
+
+    /// Write the `/V` attribute to reference the [signature](Signature) that
+    /// is the value of a signature field. PDF 1.3+.
+    pub fn signature_value(&mut self, signature: Ref) -> &mut Self {
+        self.pair(Name(b"V"), signature);
+        self
+    }
+}
+
+deref!('a, Field<'a> => Dict<'a>, dict);
+
+/// The kind of a [form field](Field).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FieldType {
+    /// A push button, check box, or radio button.
+    Button,
+    /// A text field.
+    Text,
+    /// A list or combo box.
+    Choice,
+    /// A signature field. PDF 1.3+.
+    Signature,
+}
+
+impl FieldType {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::Button => Name(b"Btn"),
+            Self::Text => Name(b"Tx"),
+            Self::Choice => Name(b"Ch"),
+            Self::Signature => Name(b"Sig"),
+        }
+    }
+}
+
+bitflags::bitflags! {
+    /// Bitflags describing the characteristics of a [form field](Field).
+    pub struct FieldFlags: u32 {
+        /// The user may not change the value of the field.
+        const READ_ONLY = 1 << 0;
+        /// The field must have a value when the form is submitted.
+        const REQUIRED = 1 << 1;
+        /// The field is not exported when the form is submitted.
+        const NO_EXPORT = 1 << 2;
+        /// The text field may contain multiple lines.
+        const MULTILINE = 1 << 12;
+        /// The text field is a password field whose value is not shown.
+        const PASSWORD = 1 << 13;
+        /// The value of the text field is rich text. PDF 1.5+.
+        const RICH_TEXT = 1 << 25;
+    }
+}
+
+bitflags::bitflags! {
+    /// Bitflags describing how the data of a form is submitted by a
+    /// [submit-form action](Action::submit_flags).
+    ///
+    /// If none of `EXPORT_FORMAT`, `XFDF`, and `SUBMIT_PDF` is set, the data
+    /// is submitted as FDF.
+    pub struct SubmitFlags: u32 {
+        /// Submit all fields except for the [referenced ones](Action::fields)
+        /// instead of only the referenced ones.
+        const INCLUDE_EXCLUDE = 1 << 0;
+        /// Also submit fields without a value.
+        const INCLUDE_NO_VALUE_FIELDS = 1 << 1;
+        /// Submit the data in HTML form format instead of FDF.
+        const EXPORT_FORMAT = 1 << 2;
+        /// Submit the data with a GET request instead of a POST request. Only
+        /// permissible with `EXPORT_FORMAT`.
+        const GET_METHOD = 1 << 3;
+        /// Submit the coordinates of the mouse click that submitted the
+        /// form. Only permissible with `EXPORT_FORMAT`.
+        const SUBMIT_COORDINATES = 1 << 4;
+        /// Submit the data as XFDF instead of FDF. PDF 1.4+.
+        const XFDF = 1 << 5;
+        /// Include the incremental updates of the document in the FDF. PDF
+        /// 1.4+.
+        const INCLUDE_APPEND_SAVES = 1 << 6;
+        /// Include all markup annotations in the FDF. PDF 1.4+.
+        const INCLUDE_ANNOTATIONS = 1 << 7;
+        /// Submit the whole document as PDF. PDF 1.4+.
+        const SUBMIT_PDF = 1 << 8;
+        /// Convert dates to the standard format. PDF 1.4+.
+        const CANONICAL_FORMAT = 1 << 9;
+        /// Only include markup annotations by the current user with
+        /// `INCLUDE_ANNOTATIONS`. PDF 1.4+.
+        const EXCL_NON_USER_ANNOTS = 1 << 10;
+        /// Omit the `/F` entry of the FDF. PDF 1.4+.
+        const EXCL_F_KEY = 1 << 11;
+        /// Embed the form in the FDF as a stream. PDF 1.5+.
+        const EMBED_FORM = 1 << 13;
+    }
+}
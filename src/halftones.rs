@@ -0,0 +1,274 @@
+use super::*;
+
+/// Writer for a _type 1 halftone dictionary_.
+///
+/// Type 1 halftones define a screen through a frequency, an angle, and a spot
+/// function.
+///
+/// This struct is created by [`PdfWriter::halftone`] and
+/// [`CompositeHalftone::colorant`].
+pub struct Halftone<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(Halftone: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"Halftone"));
+    dict.pair(Name(b"HalftoneType"), 1);
+    Self { dict }
+});
+
+impl<'a> Halftone<'a> {
+    /// Write the `/HalftoneName` attribute to name the halftone.
+    pub fn name(&mut self, name: Str) -> &mut Self {
+        self.pair(Name(b"HalftoneName"), name);
+        self
+    }
+
+    /// Write the `/Frequency` attribute to set the screen frequency in halftone
+    /// cells per inch. Required.
+    pub fn frequency(&mut self, frequency: f32) -> &mut Self {
+        self.pair(Name(b"Frequency"), frequency);
+        self
+    }
+
+    /// Write the `/Angle` attribute to set the screen angle in degrees
+    /// counterclockwise from the device coordinate system. Required.
+    pub fn angle(&mut self, angle: f32) -> &mut Self {
+        self.pair(Name(b"Angle"), angle);
+        self
+    }
+
+    /// Write the `/SpotFunction` attribute as a predefined spot function.
+    /// Either this, [`spot_functions`](Self::spot_functions), or
+    /// [`spot_function_ref`](Self::spot_function_ref) is required.
+    pub fn spot_function(&mut self, func: SpotFunction) -> &mut Self {
+        self.pair(Name(b"SpotFunction"), func.to_name());
+        self
+    }
+
+    /// Write the `/SpotFunction` attribute as an array of predefined spot
+    /// functions. The viewer uses the first one it supports.
+    pub fn spot_functions(
+        &mut self,
+        funcs: impl IntoIterator<Item = SpotFunction>,
+    ) -> &mut Self {
+        self.insert(Name(b"SpotFunction"))
+            .array()
+            .items(funcs.into_iter().map(SpotFunction::to_name));
+        self
+    }
+
+    /// Write the `/SpotFunction` attribute as a reference to a function
+    /// mapping from the coordinates of a point in the halftone cell to its
+    /// priority.
+    pub fn spot_function_ref(&mut self, func: Ref) -> &mut Self {
+        self.pair(Name(b"SpotFunction"), func);
+        self
+    }
+
+    /// Write the `/AccurateScreens` attribute to request a more precise but
+    /// more expensive rendering of the screen.
+    pub fn accurate_screens(&mut self, accurate: bool) -> &mut Self {
+        self.pair(Name(b"AccurateScreens"), accurate);
+        self
+    }
+
+    /// Write the `/TransferFunction` attribute to set the transfer function
+    /// that overrides the one in the graphics state.
+    ///
+    /// Required if the halftone is used for a nonprimary color component
+    /// within a [composite halftone](CompositeHalftone).
+    pub fn transfer_function(&mut self, func: Ref) -> &mut Self {
+        self.pair(Name(b"TransferFunction"), func);
+        self
+    }
+
+    /// Write the `/TransferFunction` attribute as the identity function.
+    pub fn transfer_function_identity(&mut self) -> &mut Self {
+        self.pair(Name(b"TransferFunction"), Name(b"Identity"));
+        self
+    }
+}
+
+deref!('a, Halftone<'a> => Dict<'a>, dict);
+
+/// A predefined spot function for [type 1 halftones](Halftone).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[allow(missing_docs)]
+pub enum SpotFunction {
+    SimpleDot,
+    InvertedSimpleDot,
+    DoubleDot,
+    InvertedDoubleDot,
+    CosineDot,
+    Double,
+    InvertedDouble,
+    Line,
+    LineX,
+    LineY,
+    Round,
+    Ellipse,
+    EllipseA,
+    InvertedEllipseA,
+    EllipseB,
+    EllipseC,
+    InvertedEllipseC,
+    Square,
+    Cross,
+    Rhomboid,
+    Diamond,
+}
+
+impl SpotFunction {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::SimpleDot => Name(b"SimpleDot"),
+            Self::InvertedSimpleDot => Name(b"InvertedSimpleDot"),
+            Self::DoubleDot => Name(b"DoubleDot"),
+            Self::InvertedDoubleDot => Name(b"InvertedDoubleDot"),
+            Self::CosineDot => Name(b"CosineDot"),
+            Self::Double => Name(b"Double"),
+            Self::InvertedDouble => Name(b"InvertedDouble"),
+            Self::Line => Name(b"Line"),
+            Self::LineX => Name(b"LineX"),
+            Self::LineY => Name(b"LineY"),
+            Self::Round => Name(b"Round"),
+            Self::Ellipse => Name(b"Ellipse"),
+            Self::EllipseA => Name(b"EllipseA"),
+            Self::InvertedEllipseA => Name(b"InvertedEllipseA"),
+            Self::EllipseB => Name(b"EllipseB"),
+            Self::EllipseC => Name(b"EllipseC"),
+            Self::InvertedEllipseC => Name(b"InvertedEllipseC"),
+            Self::Square => Name(b"Square"),
+            Self::Cross => Name(b"Cross"),
+            Self::Rhomboid => Name(b"Rhomboid"),
+            Self::Diamond => Name(b"Diamond"),
+        }
+    }
+}
+
+/// Writer for a _type 5 halftone dictionary_.
+///
+/// Composite halftones set a separate halftone for each color component. The
+/// entry for the `Default` component is required and used for all components
+/// without their own entry.
+///
+/// This struct is created by [`PdfWriter::composite_halftone`].
+pub struct CompositeHalftone<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(CompositeHalftone: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"Halftone"));
+    dict.pair(Name(b"HalftoneType"), 5);
+    Self { dict }
+});
+
+impl<'a> CompositeHalftone<'a> {
+    /// Write the `/HalftoneName` attribute to name the halftone.
+    pub fn name(&mut self, name: Str) -> &mut Self {
+        self.pair(Name(b"HalftoneName"), name);
+        self
+    }
+
+    /// Start writing a type 1 halftone for the color component with the given
+    /// name, e.g. `Cyan` or `Default`.
+    pub fn colorant(&mut self, name: Name) -> Halftone<'_> {
+        self.insert(name).start()
+    }
+
+    /// Write a reference to the halftone for the color component with the
+    /// given name. This must be used for threshold halftones.
+    ///
+    /// The halftone must not be another composite halftone.
+    pub fn colorant_ref(&mut self, name: Name, halftone: Ref) -> &mut Self {
+        self.pair(name, halftone);
+        self
+    }
+}
+
+deref!('a, CompositeHalftone<'a> => Dict<'a>, dict);
+
+/// Writer for a _threshold halftone stream_ (type 6, 10, or 16).
+///
+/// The stream data contains the threshold array. It must be one byte per
+/// threshold for types 6 and 10 and two big-endian bytes per threshold for
+/// type 16. Exactly one of the [`dimensions`](Self::dimensions),
+/// [`squares`](Self::squares) and [`dimensions_16bit`](Self::dimensions_16bit)
+/// methods must be called to determine the halftone type.
+///
+/// This struct is created by [`PdfWriter::threshold_halftone`].
+pub struct ThresholdHalftone<'a> {
+    stream: Stream<'a>,
+}
+
+impl<'a> ThresholdHalftone<'a> {
+    /// Create a new threshold halftone writer.
+    pub(crate) fn start(mut stream: Stream<'a>) -> Self {
+        stream.pair(Name(b"Type"), Name(b"Halftone"));
+        Self { stream }
+    }
+
+    /// Write the `/HalftoneType` attribute as type 6 together with the
+    /// `/Width` and `/Height` attributes to set the size of the threshold
+    /// array.
+    pub fn dimensions(&mut self, width: i32, height: i32) -> &mut Self {
+        self.pair(Name(b"HalftoneType"), 6);
+        self.pair(Name(b"Width"), width);
+        self.pair(Name(b"Height"), height);
+        self
+    }
+
+    /// Write the `/HalftoneType` attribute as type 10 together with the
+    /// `/Xsquare` and `/Ysquare` attributes to set the side lengths of the two
+    /// squares that make up the angled threshold array.
+    pub fn squares(&mut self, x_square: i32, y_square: i32) -> &mut Self {
+        self.pair(Name(b"HalftoneType"), 10);
+        self.pair(Name(b"Xsquare"), x_square);
+        self.pair(Name(b"Ysquare"), y_square);
+        self
+    }
+
+    /// Write the `/HalftoneType` attribute as type 16 together with the
+    /// `/Width` and `/Height` attributes of the first rectangle of the
+    /// threshold array and, optionally, the `/Width2` and `/Height2`
+    /// attributes of the second rectangle.
+    pub fn dimensions_16bit(
+        &mut self,
+        width: i32,
+        height: i32,
+        second: Option<(i32, i32)>,
+    ) -> &mut Self {
+        self.pair(Name(b"HalftoneType"), 16);
+        self.pair(Name(b"Width"), width);
+        self.pair(Name(b"Height"), height);
+        if let Some((width2, height2)) = second {
+            self.pair(Name(b"Width2"), width2);
+            self.pair(Name(b"Height2"), height2);
+        }
+        self
+    }
+
+    /// Write the `/HalftoneName` attribute to name the halftone.
+    pub fn name(&mut self, name: Str) -> &mut Self {
+        self.pair(Name(b"HalftoneName"), name);
+        self
+    }
+
+    /// Write the `/TransferFunction` attribute to set the transfer function
+    /// that overrides the one in the graphics state.
+    pub fn transfer_function(&mut self, func: Ref) -> &mut Self {
+        self.pair(Name(b"TransferFunction"), func);
+        self
+    }
+
+    /// Write the `/TransferFunction` attribute as the identity function.
+    pub fn transfer_function_identity(&mut self) -> &mut Self {
+        self.pair(Name(b"TransferFunction"), Name(b"Identity"));
+        self
+    }
+}
+
+deref!('a, ThresholdHalftone<'a> => Stream<'a>, stream);
@@ -0,0 +1,271 @@
+//! Writers for the legacy multimedia features of PDF.
+//!
+//! Sound and movie annotations were deprecated in PDF 2.0 in favor of
+//! [screen annotations](crate::types::AnnotationType::Screen) with rendition
+//! actions and rich media annotations. They remain available here for
+//! workflows that still require them.
+
+use super::*;
+
+/// Writer for a _sound stream_. PDF 1.2+. Deprecated in PDF 2.0.
+///
+/// The stream data contains the sound samples. Samples with multiple channels
+/// are interleaved.
+///
+/// This struct is created by [`PdfWriter::sound`].
+pub struct SoundStream<'a> {
+    stream: Stream<'a>,
+}
+
+impl<'a> SoundStream<'a> {
+    /// Create a new sound stream writer.
+    pub(crate) fn start(mut stream: Stream<'a>) -> Self {
+        stream.pair(Name(b"Type"), Name(b"Sound"));
+        Self { stream }
+    }
+
+    /// Write the `/R` attribute to set the sampling rate in samples per
+    /// second. Required.
+    pub fn rate(&mut self, rate: f32) -> &mut Self {
+        self.pair(Name(b"R"), rate);
+        self
+    }
+
+    /// Write the `/C` attribute to set the number of channels. Defaults to
+    /// `1`.
+    pub fn channels(&mut self, channels: i32) -> &mut Self {
+        self.pair(Name(b"C"), channels);
+        self
+    }
+
+    /// Write the `/B` attribute to set the number of bits per sample and
+    /// channel. Defaults to `8`.
+    pub fn bits_per_sample(&mut self, bits: i32) -> &mut Self {
+        self.pair(Name(b"B"), bits);
+        self
+    }
+
+    /// Write the `/E` attribute to set the encoding of the samples. Defaults
+    /// to `Raw`.
+    pub fn encoding(&mut self, encoding: SoundEncoding) -> &mut Self {
+        self.pair(Name(b"E"), encoding.to_name());
+        self
+    }
+
+    /// Write the `/CO` attribute to set the name of the sound compression
+    /// format that is applied in addition to the encoding.
+    pub fn compression(&mut self, format: Name) -> &mut Self {
+        self.pair(Name(b"CO"), format);
+        self
+    }
+}
+
+deref!('a, SoundStream<'a> => Stream<'a>, stream);
+
+/// How the samples of a [sound stream](SoundStream) are encoded.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum SoundEncoding {
+    /// Unsigned values from zero to the maximum value.
+    Raw,
+    /// Two's complement values.
+    Signed,
+    /// µ-law encoded samples.
+    MuLaw,
+    /// A-law encoded samples.
+    ALaw,
+}
+
+impl SoundEncoding {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::Raw => Name(b"Raw"),
+            Self::Signed => Name(b"Signed"),
+            Self::MuLaw => Name(b"muLaw"),
+            Self::ALaw => Name(b"ALaw"),
+        }
+    }
+}
+
+/// Sound and movie annotations. PDF 1.2+. Deprecated in PDF 2.0.
+impl<'a> Annotation<'a> {
+    /// Write the `/Sound` attribute to reference the [sound
+    /// stream](SoundStream) that is played when a sound annotation is
+    /// activated. Required for the subtype `Sound`.
+    pub fn sound(&mut self, sound: Ref) -> &mut Self {
+        self.pair(Name(b"Sound"), sound);
+        self
+    }
+
+    /// Start writing the `/Movie` dictionary to set the movie that is played
+    /// when a movie annotation is activated. Required for the subtype
+    /// `Movie`.
+    pub fn movie(&mut self) -> Movie<'_> {
+        self.insert(Name(b"Movie")).start()
+    }
+
+    /// Start writing the `/A` dictionary to set how the movie of a movie
+    /// annotation is played.
+    pub fn movie_activation(&mut self) -> MovieActivation<'_> {
+        self.insert(Name(b"A")).start()
+    }
+
+    /// Write the `/A` attribute to set whether the movie of a movie annotation
+    /// is played with the default activation settings when the annotation is
+    /// activated.
+    pub fn movie_playable(&mut self, playable: bool) -> &mut Self {
+        self.pair(Name(b"A"), playable);
+        self
+    }
+}
+
+/// Writer for a _movie dictionary_. PDF 1.2+. Deprecated in PDF 2.0.
+///
+/// This struct is created by [`Annotation::movie`].
+pub struct Movie<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(Movie: |obj| Self { dict: obj.dict() });
+
+impl<'a> Movie<'a> {
+    /// Start writing the `/F` dictionary to set the file containing the movie.
+    /// Required.
+    pub fn file_spec(&mut self) -> FileSpec<'_> {
+        self.insert(Name(b"F")).start()
+    }
+
+    /// Write the `/Aspect` attribute to set the width and height of the movie
+    /// in pixels.
+    pub fn aspect(&mut self, width: i32, height: i32) -> &mut Self {
+        self.insert(Name(b"Aspect")).array().items([width, height]);
+        self
+    }
+
+    /// Write the `/Rotate` attribute to set the clockwise rotation of the
+    /// movie in degrees. Must be a multiple of 90.
+    pub fn rotate(&mut self, degrees: i32) -> &mut Self {
+        self.pair(Name(b"Rotate"), degrees);
+        self
+    }
+
+    /// Write the `/Poster` attribute to set whether the viewer retrieves a
+    /// poster image from the movie file.
+    pub fn poster(&mut self, poster: bool) -> &mut Self {
+        self.pair(Name(b"Poster"), poster);
+        self
+    }
+
+    /// Write the `/Poster` attribute to reference an [image
+    /// XObject](ImageXObject) that is shown as the poster image.
+    pub fn poster_image(&mut self, image: Ref) -> &mut Self {
+        self.pair(Name(b"Poster"), image);
+        self
+    }
+}
+
+deref!('a, Movie<'a> => Dict<'a>, dict);
+
+/// Writer for a _movie activation dictionary_. PDF 1.2+. Deprecated in PDF
+/// 2.0.
+///
+/// This struct is created by [`Annotation::movie_activation`].
+pub struct MovieActivation<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(MovieActivation: |obj| Self { dict: obj.dict() });
+
+impl<'a> MovieActivation<'a> {
+    /// Write the `/Start` attribute to set the starting time of the movie in
+    /// seconds.
+    pub fn start(&mut self, seconds: i32) -> &mut Self {
+        self.pair(Name(b"Start"), seconds);
+        self
+    }
+
+    /// Write the `/Duration` attribute to set for how many seconds the movie
+    /// is played.
+    pub fn duration(&mut self, seconds: i32) -> &mut Self {
+        self.pair(Name(b"Duration"), seconds);
+        self
+    }
+
+    /// Write the `/Rate` attribute to set the playback rate. Negative values
+    /// play the movie backwards. Defaults to `1.0`.
+    pub fn rate(&mut self, rate: f32) -> &mut Self {
+        self.pair(Name(b"Rate"), rate);
+        self
+    }
+
+    /// Write the `/Volume` attribute to set the volume between `-1.0` and
+    /// `1.0`. Negative values mute the sound. Defaults to `1.0`.
+    pub fn volume(&mut self, volume: f32) -> &mut Self {
+        self.pair(Name(b"Volume"), volume);
+        self
+    }
+
+    /// Write the `/ShowControls` attribute to set whether a controller bar is
+    /// shown while the movie is played.
+    pub fn show_controls(&mut self, show: bool) -> &mut Self {
+        self.pair(Name(b"ShowControls"), show);
+        self
+    }
+
+    /// Write the `/Mode` attribute to set how often the movie is played.
+    pub fn mode(&mut self, mode: MoviePlayMode) -> &mut Self {
+        self.pair(Name(b"Mode"), mode.to_name());
+        self
+    }
+
+    /// Write the `/Synchronous` attribute to set whether the viewer waits for
+    /// the movie to finish before allowing further interaction.
+    pub fn synchronous(&mut self, synchronous: bool) -> &mut Self {
+        self.pair(Name(b"Synchronous"), synchronous);
+        self
+    }
+
+    /// Write the `/FWScale` attribute to play the movie in a floating window
+    /// scaled by the given numerator and denominator.
+    pub fn floating_window_scale(
+        &mut self,
+        numerator: i32,
+        denominator: i32,
+    ) -> &mut Self {
+        self.insert(Name(b"FWScale")).array().items([numerator, denominator]);
+        self
+    }
+
+    /// Write the `/FWPosition` attribute to set the position of the floating
+    /// window relative to the screen with values between `0.0` and `1.0`.
+    /// Defaults to `(0.5, 0.5)`, i.e. centered.
+    pub fn floating_window_position(&mut self, x: f32, y: f32) -> &mut Self {
+        self.insert(Name(b"FWPosition")).array().items([x, y]);
+        self
+    }
+}
+
+deref!('a, MovieActivation<'a> => Dict<'a>, dict);
+
+/// How often the movie of a movie annotation is played.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum MoviePlayMode {
+    /// Play the movie once and stop.
+    Once,
+    /// Play the movie and leave the movie controller open.
+    Open,
+    /// Play the movie repeatedly from beginning to end.
+    Repeat,
+    /// Play the movie forward and backward continuously.
+    Palindrome,
+}
+
+impl MoviePlayMode {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::Once => Name(b"Once"),
+            Self::Open => Name(b"Open"),
+            Self::Repeat => Name(b"Repeat"),
+            Self::Palindrome => Name(b"Palindrome"),
+        }
+    }
+}
@@ -44,7 +44,7 @@ The following example creates a PDF with a single, empty A4 page.
 ```
 use pdf_writer::{PdfWriter, Rect, Ref};
 
-# fn main() -> std::io::Result<()> {
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
 // Define some indirect reference ids we'll use.
 let catalog_id = Ref::new(1);
 let page_tree_id = Ref::new(2);
@@ -60,7 +60,7 @@ writer.page(page_id)
     .resources();
 
 // Finish with cross-reference table and trailer and write to file.
-std::fs::write("target/empty.pdf", writer.finish())?;
+std::fs::write("target/empty.pdf", writer.finish()?)?;
 # Ok(())
 # }
 ```
@@ -70,7 +70,8 @@ For more examples, check out the [examples folder] in the repository.
 # Note
 This crate is rather low-level. It does not allocate or validate indirect reference
 ids for you and it does not check you write all required fields for an object. Refer
-to the [PDF specification] to make sure you create valid PDFs.
+to the [PDF specification] to make sure you create valid PDFs. For simple pages, the
+optional [`PageBuilder`] and [`RefAllocator`] take care of some of the bookkeeping.
 
 [page]: writers::Page
 [image]: writers::ImageXObject
@@ -84,60 +85,105 @@ to the [PDF specification] to make sure you create valid PDFs.
 
 #[macro_use]
 mod macros;
+mod actions;
 mod annotations;
 mod attributes;
 mod buf;
+mod builder;
+mod chunk;
 mod color;
 mod content;
 mod files;
 mod font;
+mod forms;
 mod functions;
+mod halftones;
+pub mod legacy;
+mod md5;
+mod measure;
+mod media;
 mod object;
+mod signatures;
+#[cfg(feature = "standard14")]
+mod standard14;
 mod structure;
+mod three_d;
 mod transitions;
+mod validate;
+mod web_capture;
 mod xobject;
 
 /// Strongly typed writers for specific PDF structures.
 pub mod writers {
     use super::*;
-    pub use annotations::{Action, Annotation, BorderStyle};
+    pub use actions::{Action, OcgState};
+    pub use annotations::{
+        Annotation, Appearance, AppearanceCharacteristics, BorderStyle, FixedPrint,
+        IconFit,
+    };
     pub use attributes::{
-        Attributes, FieldAttributes, LayoutAttributes, ListAttributes, TableAttributes,
-        UserProperty,
+        Attributes, FieldAttributes, LayoutAttributes, ListAttributes, StructAttributes,
+        TableAttributes, UserProperty,
     };
     pub use color::{
         ColorSpace, DeviceN, DeviceNAttrs, DeviceNMixingHints, DeviceNProcess,
-        FunctionShading, IccProfile, OutputIntent, Separation, SeparationInfo,
-        ShadingPattern, StreamShading, StreamShadingType, TilingPattern,
+        FunctionShading, IccProfile, OutputIntent, OutputProfileRef, Separation,
+        SeparationInfo, ShadingPattern, StreamShading, StreamShadingType, TilingPattern,
     };
     pub use content::{
-        Artifact, ExtGraphicsState, MarkContent, Operation, PositionedItems,
+        Artifact, ColorSpaces, ExtGraphicsState, MarkContent, Operation, PositionedItems,
         PropertyList, Resources, ShowPositioned, SoftMask,
     };
-    pub use files::{EmbeddedFile, EmbeddingParams, FileSpec};
+    pub use files::{
+        Collection, CollectionField, CollectionItem, CollectionSchema, CollectionSort,
+        EmbeddedFile, EmbeddingParams, FileSpec,
+    };
     pub use font::{
         CidFont, Cmap, Differences, Encoding, FontDescriptor, Type0Font, Type1Font,
-        Type3Font, Widths,
+        Type3Font, VerticalWidths, Widths,
     };
+    pub use forms::{Field, Form, FormFontsDict, FormResources};
     pub use functions::{
         ExponentialFunction, PostScriptFunction, SampledFunction, StitchingFunction,
     };
-    pub use object::{NameTree, NameTreeEntries, NumberTree, NumberTreeEntries};
+    pub use halftones::{CompositeHalftone, Halftone, ThresholdHalftone};
+    pub use measure::{CoordinateSystem, Measure, NumberFormat, PointData, Viewport};
+    pub use media::{
+        MediaClip, MediaPlayParameters, MediaPlaySettings, Rendition,
+        RichMediaConfiguration, RichMediaContent, RichMediaInstance, RichMediaSettings,
+    };
+    pub use object::{
+        DecodeParms, DecodeParmsArray, NameTree, NameTreeEntries, NumberTree,
+        NumberTreeEntries,
+    };
+    pub use signatures::{Perms, SigRef, Signature, TransformParams};
     pub use structure::{
-        Catalog, ClassMap, Destination, DeveloperExtension, DocumentInfo, MarkInfo,
-        MarkedRef, Metadata, Names, ObjectRef, Outline, OutlineItem, Page, PageLabel,
-        Pages, RoleMap, StructChildren, StructElement, StructTreeRoot, ViewerPreferences,
+        ApplicationData, Bead, BoxColorInfo, BoxStyle, Catalog, ClassMap, Destination,
+        DeveloperExtension, DocumentInfo, MarkInfo, MarkedRef, Metadata, Names,
+        ObjectRef, Outline, OutlineItem, Page, PageLabel, Pages, PieceInfo, Requirement,
+        RequirementHandler, RoleMap, StructChildren, StructElement, StructTreeRoot,
+        TemplatePage, Thread, Trailer, ViewerPreferences,
+    };
+    pub use three_d::{
+        Activation3D, Animation3D, Background3D, Projection3D, Stream3D, View3D,
     };
     pub use transitions::Transition;
-    pub use xobject::{FormXObject, Group, ImageXObject, Reference};
+    pub use web_capture::{
+        WebCaptureCommand, WebCaptureContentSet, WebCaptureInfo, WebCaptureSource,
+    };
+    pub use xobject::{
+        AlternateImage, FormXObject, Group, ImageXObject, Opi, Opi13, Opi20, Reference,
+    };
 }
 
 /// Types used by specific PDF structures.
 pub mod types {
     use super::*;
+    pub use actions::ActionType;
     pub use annotations::{
-        ActionType, AnnotationFlags, AnnotationIcon, AnnotationType, BorderType,
-        HighlightEffect,
+        AnnotationFlags, AnnotationIcon, AnnotationType, BorderType, Color,
+        FreeTextIntent, HighlightEffect, IconScaleWhen, OpaqueColor, Quadding,
+        TextPosition,
     };
     pub use attributes::{
         AttributeOwner, BlockAlign, FieldRole, FieldState, InlineAlign,
@@ -152,35 +198,83 @@ pub mod types {
         LineCapStyle, LineJoinStyle, MaskType, OverprintMode, ProcSet, RenderingIntent,
         TextRenderingMode,
     };
-    pub use font::UnicodeCmap;
-    pub use font::{CidFontType, FontFlags, FontStretch, SystemInfo};
+    pub use files::{CollectionFieldType, CollectionView};
+    pub use font::{CidCmap, CidSet, UnicodeCmap};
+    pub use font::{
+        CidFontType, CidToGidMap, FontFlags, FontStretch, SubsetTag, SystemInfo,
+        WidthsBuilder,
+    };
+    pub use forms::{FieldFlags, FieldType, SubmitFlags};
     pub use functions::{InterpolationOrder, PostScriptOp};
+    pub use halftones::SpotFunction;
+    pub use measure::{CoordinateSystemType, FractionFormat, MeasureType};
+    pub use media::{
+        MediaFit, RenditionOperation, RenditionType, RichMediaActivation,
+        RichMediaDeactivation, RichMediaType, TempFilePermission,
+    };
+    pub use signatures::{FieldMdpAction, MdpPermissions, TransformMethod};
+    #[cfg(feature = "standard14")]
+    pub use standard14::Standard14Font;
     pub use structure::{
-        Direction, NumberingStyle, OutlineItemFlags, PageLayout, PageMode, StructRole,
-        TabOrder, TrappingStatus,
+        Direction, GuideStyle, NumberingStyle, OutlineItemFlags, PageLayout, PageMode,
+        RequirementHandlerType, RequirementType, StructRole, TabOrder, TrappingStatus,
+    };
+    pub use three_d::{
+        ActivationTrigger3D, AnimationType3D, ArtworkState3D, Clipping3D,
+        DeactivationTrigger3D, Projection3DType, RelativeView3D, Stream3DType,
     };
     pub use transitions::{TransitionAngle, TransitionStyle};
-    pub use xobject::SMaskInData;
+    pub use web_capture::{WebCaptureContentType, WebCaptureFlags};
+    pub use xobject::{OpiColorType, SMaskInData};
 }
 
-pub use content::Content;
+pub use annotations::DefaultAppearance;
+pub use builder::{PageBuilder, RefAllocator};
+pub use chunk::Chunk;
+pub use content::{Content, NestingLimitError};
 pub use object::{
     Array, Date, Dict, Filter, Finish, Name, Null, Obj, Primitive, Rect, Ref, Rewrite,
-    Str, Stream, TextStr, TypedArray, TypedDict, Writer,
+    Str, Stream, TextStr, TypedArray, TypedDict, WrappedStr, Writer,
 };
+#[cfg(feature = "standard14")]
+pub use standard14::{standard14_widths, text_width};
+pub use validate::Warning;
 
+use std::collections::hash_map::DefaultHasher;
 use std::fmt::{self, Debug, Formatter};
+use std::hash::{Hash, Hasher};
 use std::io::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use buf::BufExt;
+use forms::FormFonts;
+use legacy::SoundStream;
+use validate::{Recorder, Structure};
 use writers::*;
 
 /// The root writer.
+///
+/// In [deterministic mode](Self::set_deterministic), which is the default,
+/// the output only depends on what is written: The writer never derives
+/// values like dates or file identifiers from the current time or from
+/// randomness and all names it generates itself, e.g. in the
+/// [`PageBuilder`], are assigned in a fixed order. Writing the same document
+/// twice thus results in identical bytes.
 pub struct PdfWriter {
     buf: Vec<u8>,
     offsets: Vec<(Ref, usize)>,
     catalog_id: Option<Ref>,
     info_id: Option<Ref>,
+    form_fonts: FormFonts,
+    structure: Structure,
+    pretty: bool,
+    array_wrap: usize,
+    deterministic: bool,
+    next_struct_parent: i32,
+    profile: Profile,
+    hybrid_xref: Option<Ref>,
+    compressed: Vec<(Ref, Ref, u16)>,
 }
 
 /// Core methods.
@@ -200,6 +294,15 @@ impl PdfWriter {
             offsets: vec![],
             catalog_id: None,
             info_id: None,
+            form_fonts: FormFonts::default(),
+            structure: Structure::default(),
+            pretty: false,
+            array_wrap: 8,
+            deterministic: true,
+            next_struct_parent: 0,
+            profile: Profile::default(),
+            hybrid_xref: None,
+            compressed: vec![],
         }
     }
 
@@ -218,30 +321,285 @@ impl PdfWriter {
         }
     }
 
+    /// Set the compatibility profile and the matching PDF version.
+    ///
+    /// The typed writers consult the profile to emit redundant entries that
+    /// old consumers like print drivers require. With the
+    /// [`Legacy14`](Profile::Legacy14) profile, resource dictionaries get the
+    /// [default procedure sets](Resources::default_proc_set), annotations with
+    /// a [border style](Annotation::border_style) additionally get a
+    /// `/Border` array and standard 14 fonts get their widths. The profile is
+    /// also the version that [`validate`](Self::validate) checks features
+    /// against.
+    ///
+    /// The profile only applies to objects started after it was set. Objects
+    /// written into a [`Chunk`] use the profile of the chunk.
+    ///
+    /// _Default value_: [`Profile::Modern17`], which writes exactly what it is
+    /// told.
+    pub fn set_profile(&mut self, profile: Profile) {
+        let (major, minor) = profile.version();
+        self.set_version(major, minor);
+        self.profile = profile;
+    }
+
+    /// The compatibility profile of the document.
+    pub fn profile(&self) -> Profile {
+        self.profile
+    }
+
+    /// Set whether objects are pretty printed.
+    ///
+    /// Dictionaries, including stream dictionaries, are always written with
+    /// one pair per line. With pretty printing, arrays with more than [a few
+    /// items](Self::set_array_wrap_threshold) are additionally written with
+    /// one item per line and empty dictionaries are written as `<< >>`. This
+    /// makes diffs of long arrays like page tree kids easier to read.
+    ///
+    /// _Default value_: `false`.
+    pub fn set_pretty(&mut self, pretty: bool) {
+        self.pretty = pretty;
+    }
+
+    /// Set how many items an array may have before it is written with one
+    /// item per line when [pretty printing](Self::set_pretty).
+    ///
+    /// _Default value_: 8.
+    pub fn set_array_wrap_threshold(&mut self, count: usize) {
+        assert!(count > 0, "the array wrap threshold must be positive");
+        self.array_wrap = count;
+    }
+
+    /// Set whether the output must only depend on what is written.
+    ///
+    /// If deterministic mode is turned off, the trailer gets a file
+    /// identifier (`/ID`) that is derived from the document and the current
+    /// time unless one was written with [`Trailer::id`]. Features that derive
+    /// other values from the current time or from randomness will also only
+    /// be active if deterministic mode is turned off.
+    ///
+    /// _Default value_: `true`.
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.deterministic = deterministic;
+    }
+
+    /// Whether the output must only depend on what is written.
+    pub fn deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Write a hybrid-reference file with a cross-reference stream with the
+    /// given id in addition to the cross-reference table. PDF 1.5+.
+    ///
+    /// A hybrid-reference file stays readable for viewers that predate
+    /// cross-reference streams: They only read the classic table, in which
+    /// the objects of [object streams](Self::compressed_object) are free.
+    /// Newer viewers additionally load the entries of the stream. When the
+    /// document is finished, the stream is written right before the table and
+    /// its offset is written as the [`/XRefStm`](Trailer::xref_stm) of the
+    /// trailer. For an incremental update, the offset of the previous
+    /// revision's table must still be written with [`Trailer::prev`].
+    pub fn set_hybrid_xref(&mut self, id: Ref) {
+        self.hybrid_xref = Some(id);
+    }
+
     /// The number of bytes that were written so far.
     #[inline]
+    #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         self.buf.len()
     }
 
-    /// Write the cross-reference table and file trailer and return the
+    /// The ids of the indirect objects that were written so far, in the order
+    /// they were written.
+    pub fn written_ids(&self) -> impl ExactSizeIterator<Item = Ref> + '_ {
+        self.offsets.iter().map(|&(id, _)| id)
+    }
+
+    /// Whether an indirect object with the given id was written, e.g. to
+    /// decide whether a shared resource still needs to be written.
+    ///
+    /// This is a linear search over the written objects.
+    pub fn has(&self, id: Ref) -> bool {
+        self.offset(id).is_some()
+    }
+
+    /// The byte offset at which the indirect object with the given id starts
+    /// in the output or `None` if it wasn't written.
+    ///
+    /// Together with [`len`](Self::len), this can be used to measure the size
+    /// of an object. If the id was used twice, this is the offset of the
+    /// first object.
+    pub fn offset(&self, id: Ref) -> Option<usize> {
+        self.offsets
+            .iter()
+            .find(|&&(other, _)| other == id)
+            .map(|&(_, offset)| offset)
+    }
+
+    /// The number of bytes the underlying buffer can hold without
+    /// reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Reserve capacity for at least `additional` more bytes to avoid
+    /// reallocations when the size of the remaining output is known.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.buf.reserve(additional);
+    }
+
+    /// Check the document for consistency, then write the cross-reference
+    /// table and file trailer and return the underlying buffer.
+    ///
+    /// Fails if any indirect reference id was used twice, if an object was
+    /// not finished, e.g. because its writer was leaked, if the document has
+    /// no catalog, if a font used in the default appearance of a form field
+    /// is missing from the default resources of the [form](Self::form), if
+    /// the document has no pages or if an object is referenced, but was not
+    /// written.
+    ///
+    /// Pages are known from the typed [`Page`] and [`Pages`] writers and
+    /// references from the trailer and from everything written through
+    /// [`Obj::primitive`], like [`Dict::pair`], in indirect objects and
+    /// chunks. References in [raw](Obj::raw) bytes and content streams are
+    /// not seen. For an incremental update, i.e. if [`Trailer::prev`] is
+    /// written, the pages and references are not checked because they may be
+    /// part of the previous revision. Use [`into_buf`](Self::into_buf) to skip
+    /// the checks.
+    pub fn finish(self) -> Result<Vec<u8>, PdfError> {
+        self.finish_with(|_| {})
+    }
+
+    /// Check the document for consistency, then write the cross-reference
+    /// table and file trailer with additional trailer entries and return the
     /// underlying buffer.
     ///
-    /// Panics if any indirect reference id was used twice.
-    pub fn finish(mut self) -> Vec<u8> {
+    /// ```
+    /// use pdf_writer::{PdfWriter, Ref, Str};
+    ///
+    /// let mut writer = PdfWriter::new();
+    /// writer.catalog(Ref::new(1)).pages(Ref::new(2));
+    /// writer.pages(Ref::new(2)).kids([Ref::new(3)]).count(1);
+    /// writer.page(Ref::new(3)).parent(Ref::new(2));
+    /// let buf = writer.finish_with(|trailer| {
+    ///     trailer.id([Str(b"original"), Str(b"current")]);
+    /// });
+    /// assert!(buf.is_ok());
+    /// ```
+    ///
+    /// See [`finish`](Self::finish) for the performed checks.
+    pub fn finish_with(
+        mut self,
+        trailer: impl FnOnce(&mut Trailer<'_>),
+    ) -> Result<Vec<u8>, PdfError> {
+        if let Some(id) = self.unfinished() {
+            return Err(PdfError::UnfinishedObject(id));
+        }
+
+        self.form_fonts.check()?;
+
+        self.offsets.sort();
+        if let Some(pair) = self.offsets.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return Err(PdfError::DuplicateRef(pair[0].0));
+        }
+
+        let written =
+            |id: Ref| self.offsets.binary_search_by_key(&id, |&(id, _)| id).is_ok();
+        let compressed = self.compressed.iter().map(|&(id, _, _)| id);
+        if let Some(id) = compressed.chain(self.hybrid_xref).find(|&id| written(id)) {
+            return Err(PdfError::DuplicateRef(id));
+        }
+
+        let (root, refs, prev) = self.write_xref_and_trailer(trailer);
+        if root.is_none() {
+            return Err(PdfError::MissingCatalog);
+        }
+
+        if prev {
+            return Ok(self.buf);
+        }
+
+        if !self.structure.has_pages() {
+            return Err(PdfError::NoPages);
+        }
+
+        for &id in refs.iter().chain(self.structure.refs()) {
+            if self.offsets.binary_search_by_key(&id, |&(id, _)| id).is_err()
+                && !self.compressed.iter().any(|&(compressed, _, _)| compressed == id)
+            {
+                return Err(PdfError::MissingObject(id));
+            }
+        }
+
+        Ok(self.buf)
+    }
+
+    /// Write the cross-reference table and file trailer and return the
+    /// underlying buffer without checking the document for consistency.
+    ///
+    /// Panics if any indirect reference id was used twice or if an object was
+    /// not finished, e.g. because its writer was leaked. In debug builds,
+    /// also panics if a font used in the default appearance of a form field
+    /// is missing from the default resources of the [form](Self::form).
+    pub fn into_buf(self) -> Vec<u8> {
+        self.into_buf_with(|_| {})
+    }
+
+    /// Write the cross-reference table and file trailer with additional
+    /// trailer entries and return the underlying buffer without checking the
+    /// document for consistency.
+    ///
+    /// Panics like [`into_buf`](Self::into_buf).
+    pub fn into_buf_with(mut self, trailer: impl FnOnce(&mut Trailer<'_>)) -> Vec<u8> {
+        if let Some(id) = self.unfinished() {
+            panic!("{}", PdfError::UnfinishedObject(id));
+        }
+
+        if cfg!(debug_assertions) {
+            if let Err(err) = self.form_fonts.check() {
+                panic!("{}", err);
+            }
+        }
+
+        self.write_xref_and_trailer(trailer);
+        self.buf
+    }
+
+    /// Find an indirect object that was started, but not finished.
+    ///
+    /// Since writers borrow the whole buffer, objects are written one after
+    /// another and each must end right before the next one starts.
+    fn unfinished(&self) -> Option<Ref> {
+        let ends = self.offsets.iter().skip(1).map(|&(_, offset)| offset);
+        self.offsets
+            .iter()
+            .zip(ends.chain(std::iter::once(self.buf.len())))
+            .find(|&(&(_, start), end)| !self.buf[start..end].ends_with(b"endobj\n\n"))
+            .map(|(&(id, _), _)| id)
+    }
+
+    /// Write the cross-reference table and file trailer.
+    ///
+    /// Returns the document catalog, the objects referenced by the trailer
+    /// entries written through the closure and whether `/Prev` was written.
+    fn write_xref_and_trailer(
+        &mut self,
+        trailer: impl FnOnce(&mut Trailer<'_>),
+    ) -> (Option<Ref>, Vec<Ref>, bool) {
+        let xref_stm = self.hybrid_xref.map(|id| self.write_xref_stream(id));
+
         self.offsets.sort();
 
-        let xref_len = 1 + self.offsets.last().map_or(0, |p| p.0.get());
+        let xref_len = self.xref_len();
         let xref_offset = self.buf.len();
 
         self.buf.extend(b"xref\n0 ");
         self.buf.push_int(xref_len);
         self.buf.push(b'\n');
 
-        if self.offsets.is_empty() {
-            write!(self.buf, "0000000000 65535 f\r\n").unwrap();
-        }
-
         let mut written = 0;
         for (i, (object_id, offset)) in self.offsets.iter().enumerate() {
             if written > object_id.get() {
@@ -264,28 +622,47 @@ impl PdfWriter {
 
                 let gen = if free_id == 0 { "65535" } else { "00000" };
                 write!(self.buf, "{:010} {} f\r\n", next % xref_len, gen).unwrap();
-                written += 1;
             }
 
             write!(self.buf, "{:010} 00000 n\r\n", offset).unwrap();
-            written += 1;
+            written = object_id.get() + 1;
+        }
+
+        // Objects in object streams may come after the last written object.
+        for free_id in written..xref_len {
+            let gen = if free_id == 0 { "65535" } else { "00000" };
+            write!(self.buf, "{:010} {} f\r\n", (free_id + 1) % xref_len, gen).unwrap();
         }
 
         // Write the trailer dictionary.
         self.buf.extend(b"trailer\n");
 
-        let mut trailer = Obj::direct(&mut self.buf, 0).dict();
-        trailer.pair(Name(b"Size"), xref_len);
+        let file_id = (!self.deterministic).then(|| time_based_file_id(&self.buf));
+        let mut dict = Obj::direct(&mut self.buf, 0).dict();
+        dict.watch(&[b"ID"]);
+        dict.pair(Name(b"Size"), xref_len);
 
         if let Some(catalog_id) = self.catalog_id {
-            trailer.pair(Name(b"Root"), catalog_id);
+            dict.pair(Name(b"Root"), catalog_id);
         }
 
         if let Some(info_id) = self.info_id {
-            trailer.pair(Name(b"Info"), info_id);
+            dict.pair(Name(b"Info"), info_id);
+        }
+
+        if let Some(offset) = xref_stm {
+            dict.pair(Name(b"XRefStm"), offset);
         }
 
-        trailer.finish();
+        let mut writer = Trailer::start_with_dict(dict);
+        trailer(&mut writer);
+        if let Some(id) = file_id.filter(|_| !writer.written(b"ID")) {
+            writer.id([Str(&id), Str(&id)]);
+        }
+        let root = self.catalog_id.or(writer.root);
+        let refs = std::mem::take(&mut writer.refs);
+        let prev = writer.prev;
+        writer.finish();
 
         // Write where the cross-reference table starts.
         self.buf.extend(b"\nstartxref\n");
@@ -293,16 +670,116 @@ impl PdfWriter {
 
         // Write the end of file marker.
         self.buf.extend(b"\n%%EOF");
-        self.buf
+
+        (root, refs, prev)
     }
+
+    /// The number of entries in the cross-reference table, i.e. one more
+    /// than the highest written or compressed object id.
+    fn xref_len(&self) -> i32 {
+        let written = self.offsets.iter().map(|&(id, _)| id);
+        let compressed = self.compressed.iter().map(|&(id, _, _)| id);
+        1 + written.chain(compressed).max().map_or(0, Ref::get)
+    }
+
+    /// Write the cross-reference stream of a hybrid-reference file and
+    /// return its offset.
+    ///
+    /// The stream lists the objects in object streams, which the classic
+    /// table marks as free.
+    fn write_xref_stream(&mut self, id: Ref) -> i32 {
+        let offset = self.buf.len();
+        self.compressed.sort();
+
+        let mut data = Vec::with_capacity(7 * self.compressed.len());
+        let mut index = vec![];
+        for &(object_id, object_stream, i) in &self.compressed {
+            data.push(2);
+            data.extend(object_stream.get().to_be_bytes());
+            data.extend(i.to_be_bytes());
+
+            // Group consecutive ids into subsections.
+            match index.last_mut() {
+                Some((start, len)) if *start + *len == object_id.get() => *len += 1,
+                _ => index.push((object_id.get(), 1)),
+            }
+        }
+
+        let size = self.xref_len().max(id.get() + 1);
+        let mut stream = self.stream(id, &data);
+        stream.pair(Name(b"Type"), Name(b"XRef"));
+        stream.pair(Name(b"Size"), size);
+        stream
+            .insert(Name(b"Index"))
+            .array()
+            .items(index.into_iter().flat_map(|(start, len)| [start, len]));
+        stream.insert(Name(b"W")).array().items([1, 4, 2]);
+        stream.finish();
+
+        offset as i32
+    }
+}
+
+/// Derive a file identifier from the document and the current time.
+///
+/// A counter makes sure that documents finished at the same time get
+/// different identifiers.
+fn time_based_file_id(buf: &[u8]) -> [u8; 16] {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_nanos());
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    let mut id = [0; 16];
+    for (i, part) in id.chunks_mut(8).enumerate() {
+        let mut hasher = DefaultHasher::new();
+        (i, time, count, buf).hash(&mut hasher);
+        part.copy_from_slice(&Hasher::finish(&hasher).to_be_bytes());
+    }
+    id
 }
 
 /// Indirect objects and streams.
 impl PdfWriter {
     /// Start writing an indirectly referenceable object.
     pub fn indirect(&mut self, id: Ref) -> Obj<'_> {
+        self.indirect_with_form_fonts(id).0
+    }
+
+    /// Write an indirectly referenceable primitive object in one go, e.g. the
+    /// length of a stream that is only known after the stream was written.
+    ///
+    /// ```
+    /// use pdf_writer::{PdfWriter, Ref};
+    ///
+    /// let mut writer = PdfWriter::new();
+    /// writer.indirect_primitive(Ref::new(5), 12345);
+    /// ```
+    pub fn indirect_primitive<T: Primitive>(&mut self, id: Ref, value: T) {
+        self.indirect(id).primitive(value);
+    }
+
+    /// Start writing an indirect object and also return the fonts used by
+    /// the interactive form, which form and field writers check.
+    fn indirect_with_form_fonts(&mut self, id: Ref) -> (Obj<'_>, &mut FormFonts) {
         self.offsets.push((id, self.buf.len()));
-        Obj::indirect(&mut self.buf, id)
+        let array_wrap = self.pretty_array_wrap();
+        let obj = Obj::indirect(&mut self.buf, id)
+            .pretty(array_wrap)
+            .profile(self.profile)
+            .record(&mut self.structure, id);
+        (obj, &mut self.form_fonts)
+    }
+
+    /// How many items an array may have before it is wrapped, zero if not
+    /// pretty printing.
+    fn pretty_array_wrap(&self) -> usize {
+        if self.pretty {
+            self.array_wrap
+        } else {
+            0
+        }
     }
 
     /// Start writing an indirectly referenceable stream.
@@ -357,6 +834,26 @@ impl PdfWriter {
     pub fn stream<'a>(&'a mut self, id: Ref, data: &'a [u8]) -> Stream<'a> {
         Stream::start(self.indirect(id), data)
     }
+
+    /// Register an object that is stored at the given index of an object
+    /// stream. PDF 1.5+.
+    ///
+    /// The object stream with the `/Type /ObjStm` must be written by hand
+    /// with [`stream`](Self::stream). The object is only listed in the
+    /// cross-reference stream of a [hybrid-reference
+    /// file](Self::set_hybrid_xref) and is free in the table.
+    pub fn compressed_object(&mut self, id: Ref, object_stream: Ref, index: u16) {
+        self.compressed.push((id, object_stream, index));
+    }
+
+    /// Add the objects of a [`Chunk`] to the document.
+    pub fn extend(&mut self, chunk: &Chunk) {
+        let base = self.buf.len();
+        self.offsets
+            .extend(chunk.offsets.iter().map(|&(id, offset)| (id, base + offset)));
+        self.buf.extend(&chunk.buf);
+        self.structure.extend(&chunk.structure);
+    }
 }
 
 /// Document structure.
@@ -390,6 +887,68 @@ impl PdfWriter {
         self.indirect(id).start()
     }
 
+    /// Start writing a template page with the given media box. PDF 1.3+.
+    ///
+    /// Template pages are not part of the page tree and thus have no
+    /// `/Parent`. They must instead be named in the [template name
+    /// tree](Names::templates) so that scripts in interactive forms can spawn
+    /// pages from them. Since a template page inherits nothing, it always has
+    /// its own media box and resources.
+    pub fn template_page(&mut self, id: Ref, media_box: Rect) -> TemplatePage<'_> {
+        TemplatePage::start(self.indirect(id), media_box)
+    }
+
+    /// Start writing an annotation dictionary as an indirect object.
+    pub fn annotation(&mut self, id: Ref) -> Annotation<'_> {
+        self.indirect(id).start()
+    }
+
+    /// Start writing an action dictionary as an indirect object.
+    pub fn action(&mut self, id: Ref) -> Action<'_> {
+        self.indirect(id).start()
+    }
+
+    /// Start writing a JavaScript action dictionary as an indirect object.
+    /// PDF 1.3+.
+    ///
+    /// Document-level scripts are named in the [JavaScript name
+    /// tree](Names::javascript) and run when the document is opened.
+    pub fn javascript(&mut self, id: Ref) -> Action<'_> {
+        let mut action = self.action(id);
+        action.action_type(types::ActionType::JavaScript);
+        action
+    }
+
+    /// Start writing the interactive form dictionary. PDF 1.2+.
+    pub fn form(&mut self, id: Ref) -> Form<'_> {
+        let (obj, form_fonts) = self.indirect_with_form_fonts(id);
+        Form::start(obj, form_fonts)
+    }
+
+    /// Start writing a signature dictionary. PDF 1.3+.
+    ///
+    /// The signature must be referenced as the value of a signature field with
+    /// [`Field::signature_value`].
+    pub fn signature(&mut self, id: Ref) -> Signature<'_> {
+        self.indirect(id).start()
+    }
+
+    /// Start writing a form field. PDF 1.2+.
+    pub fn form_field(&mut self, id: Ref) -> Field<'_> {
+        let (obj, form_fonts) = self.indirect_with_form_fonts(id);
+        Field::start(obj, form_fonts)
+    }
+
+    /// Start writing a web capture content set. PDF 1.3+.
+    pub fn web_capture_content_set(&mut self, id: Ref) -> WebCaptureContentSet<'_> {
+        self.indirect(id).start()
+    }
+
+    /// Start writing a web capture command dictionary. PDF 1.3+.
+    pub fn web_capture_command(&mut self, id: Ref) -> WebCaptureCommand<'_> {
+        self.indirect(id).start()
+    }
+
     /// Start writing an outline.
     pub fn outline(&mut self, id: Ref) -> Outline<'_> {
         self.indirect(id).start()
@@ -400,8 +959,29 @@ impl PdfWriter {
         self.indirect(id).start()
     }
 
+    /// Start writing an article thread.
+    pub fn thread(&mut self, id: Ref) -> Thread<'_> {
+        self.indirect(id).start()
+    }
+
+    /// Start writing an article thread bead.
+    pub fn bead(&mut self, id: Ref) -> Bead<'_> {
+        self.indirect(id).start()
+    }
+
+    /// Start writing an explicit destination array.
+    ///
+    /// Indirect destinations can be associated with names through the
+    /// [`/Dests`](Names::destinations) name tree.
+    pub fn destination(&mut self, id: Ref) -> Destination<'_> {
+        self.indirect(id).start()
+    }
+
     /// Start writing a named destination dictionary.
-    pub fn destinations(&mut self, id: Ref) -> TypedDict<'_, Destination> {
+    ///
+    /// This is the legacy way of naming destinations used before PDF 1.2 and
+    /// is referenced by [`Catalog::destinations`].
+    pub fn destinations(&mut self, id: Ref) -> TypedDict<'_, Destination<'_>> {
         self.indirect(id).dict().typed()
     }
 
@@ -415,11 +995,46 @@ impl PdfWriter {
         EmbeddedFile::start(self.stream(id, bytes))
     }
 
+    /// Start writing an embedded file stream whose data is compressed with
+    /// the `FlateDecode` filter.
+    ///
+    /// The `/Filter` and the uncompressed [`/Size`](EmbeddingParams::size) in
+    /// the [parameters](EmbeddedFile::params) are written automatically.
+    /// Requires the `flate` feature.
+    #[cfg(feature = "flate")]
+    pub fn embedded_file_compressed(
+        &mut self,
+        id: Ref,
+        bytes: &[u8],
+    ) -> EmbeddedFile<'_> {
+        let size = i32::try_from(bytes.len()).unwrap_or_else(|_| {
+            panic!("data length (is `{}`) must be <= i32::MAX", bytes.len());
+        });
+        let compressed = miniz_oxide::deflate::compress_to_vec_zlib(bytes, 6);
+        let mut stream = Stream::start_owned(self.indirect(id), compressed);
+        stream.filter(Filter::FlateDecode);
+        EmbeddedFile::start_with_size(stream, size)
+    }
+
     /// Start writing a structure tree element.
     pub fn struct_element(&mut self, id: Ref) -> StructElement<'_> {
         self.indirect(id).start()
     }
 
+    /// Return the next unused key into the structural parent tree, starting
+    /// at `0`.
+    ///
+    /// The keys are meant for [`Page::struct_parents`],
+    /// [`Annotation::struct_parent`] and the like, which must all be unique
+    /// within the document. The [parent tree](StructTreeRoot::parent_tree)
+    /// should then cover all keys below
+    /// [`parent_tree_next_key`](StructTreeRoot::parent_tree_next_key).
+    pub fn next_struct_parent(&mut self) -> i32 {
+        let key = self.next_struct_parent;
+        self.next_struct_parent += 1;
+        key
+    }
+
     /// Start writing a metadata stream.
     pub fn metadata<'a>(&'a mut self, id: Ref, bytes: &'a [u8]) -> Metadata<'a> {
         Metadata::start(self.stream(id, bytes))
@@ -457,6 +1072,35 @@ impl PdfWriter {
     pub fn ext_graphics(&mut self, id: Ref) -> ExtGraphicsState<'_> {
         self.indirect(id).start()
     }
+
+    /// Start writing a type 1 halftone dictionary.
+    pub fn halftone(&mut self, id: Ref) -> Halftone<'_> {
+        self.indirect(id).start()
+    }
+
+    /// Start writing a type 5 halftone dictionary.
+    pub fn composite_halftone(&mut self, id: Ref) -> CompositeHalftone<'_> {
+        self.indirect(id).start()
+    }
+
+    /// Start writing a threshold halftone stream (type 6, 10, or 16).
+    pub fn threshold_halftone<'a>(
+        &'a mut self,
+        id: Ref,
+        thresholds: &'a [u8],
+    ) -> ThresholdHalftone<'a> {
+        ThresholdHalftone::start(self.stream(id, thresholds))
+    }
+
+    /// Start writing a sound stream. PDF 1.2+. Deprecated in PDF 2.0.
+    pub fn sound<'a>(&'a mut self, id: Ref, samples: &'a [u8]) -> SoundStream<'a> {
+        SoundStream::start(self.stream(id, samples))
+    }
+
+    /// Start writing a 3D stream with U3D or PRC artwork. PDF 1.6+.
+    pub fn stream_3d<'a>(&'a mut self, id: Ref, artwork: &'a [u8]) -> Stream3D<'a> {
+        Stream3D::start(self.stream(id, artwork))
+    }
 }
 
 /// Fonts.
@@ -489,7 +1133,9 @@ impl PdfWriter {
     /// Start writing a character map stream.
     ///
     /// If you want to use this for a `/ToUnicode` CMap, you can create the
-    /// bytes using a [`UnicodeCmap`](types::UnicodeCmap) builder.
+    /// bytes using a [`UnicodeCmap`](types::UnicodeCmap) builder. The bytes of
+    /// an encoding CMap can be created with a [`CidCmap`](types::CidCmap)
+    /// builder.
     pub fn cmap<'a>(&'a mut self, id: Ref, cmap: &'a [u8]) -> Cmap<'a> {
         Cmap::start(self.stream(id, cmap))
     }
@@ -589,8 +1235,87 @@ impl PdfWriter {
     }
 }
 
+impl Default for PdfWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Debug for PdfWriter {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.pad("PdfWriter(..)")
     }
 }
+
+/// An inconsistency in a document that was detected by
+/// [`PdfWriter::finish`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum PdfError {
+    /// An indirect reference id was used for multiple objects.
+    DuplicateRef(Ref),
+    /// An object was started, but not finished, e.g. because its writer was
+    /// leaked with [`std::mem::forget`].
+    UnfinishedObject(Ref),
+    /// No document catalog was written.
+    MissingCatalog,
+    /// An object is referenced, but was not written.
+    MissingObject(Ref),
+    /// No page was written.
+    NoPages,
+    /// A font with this name is used in the default appearance of a form
+    /// field, but is missing from the default resources of the form.
+    MissingFormFont(Vec<u8>),
+}
+
+impl fmt::Display for PdfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateRef(id) => {
+                write!(f, "duplicate indirect reference id: {}", id.get())
+            }
+            Self::UnfinishedObject(id) => {
+                write!(f, "object {} started but not finished", id.get())
+            }
+            Self::MissingCatalog => f.write_str("document catalog is missing"),
+            Self::MissingObject(id) => {
+                write!(f, "object {} is referenced, but was not written", id.get())
+            }
+            Self::NoPages => f.write_str("document has no pages"),
+            Self::MissingFormFont(name) => write!(
+                f,
+                "font `/{}` used in default appearance is missing from the form's \
+                 default resources",
+                String::from_utf8_lossy(name),
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PdfError {}
+
+/// Which consumers a document targets.
+///
+/// This is set with [`PdfWriter::set_profile`] and [`Chunk::set_profile`].
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Profile {
+    /// PDF 1.4 for old viewers and print drivers. The typed writers add
+    /// redundant entries that these consumers require.
+    Legacy14,
+    /// PDF 1.7 without redundant entries.
+    #[default]
+    Modern17,
+    /// PDF 2.0 without redundant entries.
+    Pdf20,
+}
+
+impl Profile {
+    /// The PDF version of the profile.
+    pub(crate) fn version(self) -> (u8, u8) {
+        match self {
+            Self::Legacy14 => (1, 4),
+            Self::Modern17 => (1, 7),
+            Self::Pdf20 => (2, 0),
+        }
+    }
+}
@@ -34,3 +34,17 @@ macro_rules! deref {
         }
     };
 }
+
+/// Create a [`Name`](crate::Name) from a string literal.
+///
+/// ```
+/// use pdf_writer::{name, Name};
+///
+/// assert_eq!(name!("Type"), Name(b"Type"));
+/// ```
+#[macro_export]
+macro_rules! name {
+    ($name:literal) => {
+        $crate::Name($name.as_bytes())
+    };
+}
@@ -0,0 +1,457 @@
+use super::*;
+
+/// Writer for a _measure dictionary_. PDF 1.6+.
+///
+/// A measure dictionary specifies how positions and distances in a region of a
+/// page or an XObject map to real-world coordinates.
+///
+/// This struct is created by [`Viewport::measure`], [`ImageXObject::measure`],
+/// and [`FormXObject::measure`].
+pub struct Measure<'a> {
+    dict: Dict<'a>,
+    geo_points: Option<usize>,
+    layout_points: Option<usize>,
+}
+
+writer!(Measure: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"Measure"));
+    Self { dict, geo_points: None, layout_points: None }
+});
+
+impl<'a> Measure<'a> {
+    /// Write the `/Subtype` attribute to set the type of coordinate system.
+    pub fn subtype(&mut self, kind: MeasureType) -> &mut Self {
+        self.pair(Name(b"Subtype"), kind.to_name());
+        self
+    }
+}
+
+/// Rectilinear measures. PDF 1.6+.
+impl<'a> Measure<'a> {
+    /// Write the `/R` attribute to set the scale ratio as text, e.g.
+    /// `1 in = 10 ft`. Required.
+    pub fn scale_ratio(&mut self, ratio: TextStr) -> &mut Self {
+        self.pair(Name(b"R"), ratio);
+        self
+    }
+
+    /// Start writing the `/X` array to set how distances along the x-axis
+    /// are converted and formatted. Required.
+    ///
+    /// The first format converts from default user space units, each
+    /// following one from the units of the previous format.
+    pub fn x_axis(&mut self) -> TypedArray<'_, NumberFormat<'_>> {
+        self.insert(Name(b"X")).array().typed()
+    }
+
+    /// Start writing the `/Y` array to set how distances along the y-axis
+    /// are converted and formatted if they differ from the
+    /// [x-axis](Self::x_axis).
+    pub fn y_axis(&mut self) -> TypedArray<'_, NumberFormat<'_>> {
+        self.insert(Name(b"Y")).array().typed()
+    }
+
+    /// Start writing the `/D` array to set how distances in any direction are
+    /// formatted. Required.
+    pub fn distance(&mut self) -> TypedArray<'_, NumberFormat<'_>> {
+        self.insert(Name(b"D")).array().typed()
+    }
+
+    /// Start writing the `/A` array to set how areas are formatted. Required.
+    pub fn area(&mut self) -> TypedArray<'_, NumberFormat<'_>> {
+        self.insert(Name(b"A")).array().typed()
+    }
+
+    /// Start writing the `/T` array to set how angles are formatted.
+    pub fn angle(&mut self) -> TypedArray<'_, NumberFormat<'_>> {
+        self.insert(Name(b"T")).array().typed()
+    }
+
+    /// Start writing the `/S` array to set how slopes are formatted.
+    pub fn slope(&mut self) -> TypedArray<'_, NumberFormat<'_>> {
+        self.insert(Name(b"S")).array().typed()
+    }
+
+    /// Write the `/O` attribute to set the origin of the measurement
+    /// coordinate system in default user space units.
+    pub fn origin(&mut self, x: f32, y: f32) -> &mut Self {
+        self.insert(Name(b"O")).array().items([x, y]);
+        self
+    }
+}
+
+/// Geospatial measures. PDF 2.0+.
+impl<'a> Measure<'a> {
+    /// Write the `/Bounds` attribute to set the region in which the geospatial
+    /// mapping is valid as a polygon in the unit square of the region.
+    ///
+    /// Panics if the number of coordinates is odd or if there are less than
+    /// three points.
+    pub fn bounds(&mut self, points: impl IntoIterator<Item = f32>) -> &mut Self {
+        let mut array = self.insert(Name(b"Bounds")).array();
+        let mut len = 0;
+        for value in points {
+            array.item(value);
+            len += 1;
+        }
+        assert!(len % 2 == 0, "bounds must consist of coordinate pairs");
+        assert!(len >= 6, "bounds must have at least three points");
+        array.finish();
+        self
+    }
+
+    /// Start writing the `/GCS` dictionary to set the geographic coordinate
+    /// system of the [geographic points](Self::geo_points). Required.
+    pub fn gcs(&mut self) -> CoordinateSystem<'_> {
+        self.insert(Name(b"GCS")).start()
+    }
+
+    /// Start writing the `/DCS` dictionary to set the coordinate system in
+    /// which the viewer shall display positions.
+    pub fn dcs(&mut self) -> CoordinateSystem<'_> {
+        self.insert(Name(b"DCS")).start()
+    }
+
+    /// Write the `/PDU` attribute to set the preferred units for displaying
+    /// lengths (e.g. `KM`), areas (e.g. `SQKM`) and angles (e.g. `DEG`).
+    pub fn preferred_units(
+        &mut self,
+        linear: Name,
+        area: Name,
+        angular: Name,
+    ) -> &mut Self {
+        self.insert(Name(b"PDU")).array().items([linear, area, angular]);
+        self
+    }
+
+    /// Write the `/GPTS` attribute to set the geographic coordinates of the
+    /// control points as latitude-longitude pairs. Required.
+    ///
+    /// Panics if the number of coordinates is odd or does not match the
+    /// [layout points](Self::layout_points).
+    pub fn geo_points(&mut self, points: impl IntoIterator<Item = f32>) -> &mut Self {
+        let len = self.write_points(Name(b"GPTS"), points);
+        if let Some(layout) = self.layout_points {
+            assert_eq!(len, layout, "geo and layout points must have the same length");
+        }
+        self.geo_points = Some(len);
+        self
+    }
+
+    /// Write the `/LPTS` attribute to set the positions of the control points
+    /// as coordinate pairs in the unit square of the region.
+    ///
+    /// Panics if the number of coordinates is odd or does not match the
+    /// [geographic points](Self::geo_points).
+    pub fn layout_points(&mut self, points: impl IntoIterator<Item = f32>) -> &mut Self {
+        let len = self.write_points(Name(b"LPTS"), points);
+        if let Some(geo) = self.geo_points {
+            assert_eq!(len, geo, "geo and layout points must have the same length");
+        }
+        self.layout_points = Some(len);
+        self
+    }
+
+    /// Write an array of coordinate pairs and return its length.
+    fn write_points(
+        &mut self,
+        key: Name,
+        points: impl IntoIterator<Item = f32>,
+    ) -> usize {
+        let mut array = self.insert(key).array();
+        let mut len = 0;
+        for value in points {
+            array.item(value);
+            len += 1;
+        }
+        assert!(len % 2 == 0, "points must consist of coordinate pairs");
+        len
+    }
+}
+
+deref!('a, Measure<'a> => Dict<'a>, dict);
+
+/// The type of coordinate system a measure dictionary maps to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum MeasureType {
+    /// A rectilinear coordinate system with a scale ratio. PDF 1.6+.
+    Rectilinear,
+    /// A geospatial coordinate system. PDF 2.0+.
+    Geospatial,
+}
+
+impl MeasureType {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::Rectilinear => Name(b"RL"),
+            Self::Geospatial => Name(b"GEO"),
+        }
+    }
+}
+
+/// Writer for a _number format dictionary_. PDF 1.6+.
+///
+/// An array of this struct is created by the rectilinear methods of
+/// [`Measure`], e.g. [`Measure::x_axis`].
+pub struct NumberFormat<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(NumberFormat: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"NumberFormat"));
+    Self { dict }
+});
+
+impl<'a> NumberFormat<'a> {
+    /// Write the `/U` attribute to set the label of the unit, e.g. `ft`.
+    /// Required.
+    pub fn unit(&mut self, label: TextStr) -> &mut Self {
+        self.pair(Name(b"U"), label);
+        self
+    }
+
+    /// Write the `/C` attribute to set the factor with which values are
+    /// multiplied to convert them into this unit. Required.
+    pub fn conversion_factor(&mut self, factor: f32) -> &mut Self {
+        self.pair(Name(b"C"), factor);
+        self
+    }
+
+    /// Write the `/F` attribute to set how fractional values are shown.
+    pub fn fractions(&mut self, fractions: FractionFormat) -> &mut Self {
+        self.pair(Name(b"F"), fractions.to_name());
+        self
+    }
+
+    /// Write the `/D` attribute to set the precision of decimal values as a
+    /// power of ten (e.g. `100` for two decimal places) or the denominator of
+    /// fractional values.
+    pub fn denominator(&mut self, denominator: i32) -> &mut Self {
+        self.pair(Name(b"D"), denominator);
+        self
+    }
+
+    /// Write the `/FD` attribute to set whether fractions are shown as is
+    /// instead of being reduced to lowest terms.
+    pub fn fixed_denominator(&mut self, fixed: bool) -> &mut Self {
+        self.pair(Name(b"FD"), fixed);
+        self
+    }
+
+    /// Write the `/RT` attribute to set the thousands separator.
+    pub fn thousands_separator(&mut self, separator: TextStr) -> &mut Self {
+        self.pair(Name(b"RT"), separator);
+        self
+    }
+
+    /// Write the `/RD` attribute to set the decimal separator.
+    pub fn decimal_separator(&mut self, separator: TextStr) -> &mut Self {
+        self.pair(Name(b"RD"), separator);
+        self
+    }
+
+    /// Write the `/PS` attribute to set the text shown before the value.
+    pub fn prefix(&mut self, text: TextStr) -> &mut Self {
+        self.pair(Name(b"PS"), text);
+        self
+    }
+
+    /// Write the `/SS` attribute to set the text shown after the value.
+    pub fn suffix(&mut self, text: TextStr) -> &mut Self {
+        self.pair(Name(b"SS"), text);
+        self
+    }
+
+    /// Write the `/O` attribute to set whether the [unit](Self::unit) is shown
+    /// before instead of after the value.
+    pub fn unit_prefix(&mut self, prefix: bool) -> &mut Self {
+        self.pair(Name(b"O"), if prefix { Name(b"P") } else { Name(b"S") });
+        self
+    }
+}
+
+deref!('a, NumberFormat<'a> => Dict<'a>, dict);
+
+/// How a [number format](NumberFormat) shows fractional values.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FractionFormat {
+    /// Show a decimal value with the [precision](NumberFormat::denominator).
+    Decimal,
+    /// Show a fraction with the [denominator](NumberFormat::denominator).
+    Fraction,
+    /// Round to an integer.
+    Round,
+    /// Truncate to an integer.
+    Truncate,
+}
+
+impl FractionFormat {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::Decimal => Name(b"D"),
+            Self::Fraction => Name(b"F"),
+            Self::Round => Name(b"R"),
+            Self::Truncate => Name(b"T"),
+        }
+    }
+}
+
+/// Writer for a _viewport dictionary_. PDF 1.6+.
+///
+/// A viewport is a region of a page with its own measurement coordinate
+/// system. A page can have multiple viewports, e.g. for multiple drawings at
+/// different scales.
+///
+/// An array of this struct is created by [`Page::viewports`].
+pub struct Viewport<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(Viewport: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"Viewport"));
+    Self { dict }
+});
+
+impl<'a> Viewport<'a> {
+    /// Write the `/BBox` attribute to set the region of the page that the
+    /// viewport covers. Required.
+    pub fn bbox(&mut self, bbox: Rect) -> &mut Self {
+        self.pair(Name(b"BBox"), bbox);
+        self
+    }
+
+    /// Write the `/Name` attribute to set a descriptive name of the viewport.
+    pub fn name(&mut self, name: TextStr) -> &mut Self {
+        self.pair(Name(b"Name"), name);
+        self
+    }
+
+    /// Start writing the `/Measure` dictionary to set the measurement
+    /// coordinate system of the viewport.
+    pub fn measure(&mut self) -> Measure<'_> {
+        self.insert(Name(b"Measure")).start()
+    }
+
+    /// Start writing the `/PtData` dictionary to attach extended geospatial
+    /// data to the viewport. PDF 2.0+.
+    pub fn point_data(&mut self) -> PointData<'_> {
+        self.insert(Name(b"PtData")).start()
+    }
+}
+
+deref!('a, Viewport<'a> => Dict<'a>, dict);
+
+/// Writer for a _coordinate system dictionary_. PDF 2.0+.
+///
+/// Either an EPSG code or a WKT string shall be written.
+///
+/// This struct is created by [`Measure::gcs`] and [`Measure::dcs`].
+pub struct CoordinateSystem<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(CoordinateSystem: |obj| Self { dict: obj.dict() });
+
+impl<'a> CoordinateSystem<'a> {
+    /// Write the `/Type` attribute to set whether this is a geographic or a
+    /// projected coordinate system. Required.
+    pub fn kind(&mut self, kind: CoordinateSystemType) -> &mut Self {
+        self.pair(Name(b"Type"), kind.to_name());
+        self
+    }
+
+    /// Write the `/EPSG` attribute to identify the coordinate system by its
+    /// EPSG reference code.
+    pub fn epsg(&mut self, code: i32) -> &mut Self {
+        self.pair(Name(b"EPSG"), code);
+        self
+    }
+
+    /// Write the `/WKT` attribute to describe the coordinate system as an OGC
+    /// well-known text string.
+    pub fn wkt(&mut self, wkt: Str) -> &mut Self {
+        self.pair(Name(b"WKT"), wkt);
+        self
+    }
+}
+
+deref!('a, CoordinateSystem<'a> => Dict<'a>, dict);
+
+/// The type of a geospatial coordinate system.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum CoordinateSystemType {
+    /// A geographic coordinate system of latitudes and longitudes.
+    Geographic,
+    /// A projected coordinate system on a plane.
+    Projected,
+}
+
+impl CoordinateSystemType {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::Geographic => Name(b"GEOGCS"),
+            Self::Projected => Name(b"PROJCS"),
+        }
+    }
+}
+
+/// Writer for a _point data dictionary_. PDF 2.0+.
+///
+/// Point data attaches a cloud of extended geospatial data points (e.g. with
+/// altitudes) to an XObject.
+///
+/// This struct is created by [`Viewport::point_data`],
+/// [`ImageXObject::point_data`], and [`FormXObject::point_data`].
+pub struct PointData<'a> {
+    dict: Dict<'a>,
+    names: Option<usize>,
+}
+
+writer!(PointData: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"PtData"));
+    dict.pair(Name(b"Subtype"), Name(b"Cloud"));
+    Self { dict, names: None }
+});
+
+impl<'a> PointData<'a> {
+    /// Write the `/Names` attribute to set the meaning of the values of each
+    /// point, e.g. `LAT`, `LON` and `ALT`. Required.
+    pub fn names<'n>(&mut self, names: impl IntoIterator<Item = Name<'n>>) -> &mut Self {
+        let mut array = self.insert(Name(b"Names")).array();
+        let mut len = 0;
+        for name in names {
+            array.item(name);
+            len += 1;
+        }
+        array.finish();
+        self.names = Some(len);
+        self
+    }
+
+    /// Write the `/XPTS` attribute with the values of each point. Required.
+    ///
+    /// Panics if the [names](Self::names) were not written before or if a
+    /// point does not have one value per name.
+    pub fn points<P>(&mut self, points: impl IntoIterator<Item = P>) -> &mut Self
+    where
+        P: IntoIterator<Item = f32>,
+    {
+        let names = self.names.expect("names must be written before points");
+        let mut array = self.dict.insert(Name(b"XPTS")).array();
+        for point in points {
+            let mut inner = array.push().array();
+            for value in point {
+                inner.item(value);
+            }
+            assert_eq!(inner.len() as usize, names, "point must have one value per name");
+        }
+        array.finish();
+        self
+    }
+}
+
+deref!('a, PointData<'a> => Dict<'a>, dict);
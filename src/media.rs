@@ -0,0 +1,505 @@
+use super::*;
+
+/// Writer for a _rendition dictionary_. PDF 1.5+.
+///
+/// A rendition describes what media to play and how to play it.
+///
+/// This struct is created by [`Action::rendition`].
+pub struct Rendition<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(Rendition: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"Rendition"));
+    Self { dict }
+});
+
+impl<'a> Rendition<'a> {
+    /// Write the `/S` attribute to set the rendition type. Required.
+    pub fn subtype(&mut self, kind: RenditionType) -> &mut Self {
+        self.pair(Name(b"S"), kind.to_name());
+        self
+    }
+
+    /// Write the `/N` attribute to set the name of the rendition that may be
+    /// shown in the user interface.
+    pub fn name(&mut self, text: TextStr) -> &mut Self {
+        self.pair(Name(b"N"), text);
+        self
+    }
+
+    /// Start writing the `/C` dictionary to set the media that shall be played.
+    /// Only permissible for media renditions.
+    pub fn media_clip(&mut self) -> MediaClip<'_> {
+        self.insert(Name(b"C")).start()
+    }
+
+    /// Start writing the `/P` dictionary to set how the media shall be
+    /// played. Only permissible for media renditions.
+    pub fn media_play_parameters(&mut self) -> MediaPlayParameters<'_> {
+        self.insert(Name(b"P")).start()
+    }
+}
+
+deref!('a, Rendition<'a> => Dict<'a>, dict);
+
+/// The kind of a [rendition](Rendition).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RenditionType {
+    /// A rendition that plays a single media clip.
+    Media,
+    /// A rendition that selects one of multiple renditions.
+    Selector,
+}
+
+impl RenditionType {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::Media => Name(b"MR"),
+            Self::Selector => Name(b"SR"),
+        }
+    }
+}
+
+/// Writer for a _media clip data dictionary_. PDF 1.5+.
+///
+/// This struct is created by [`Rendition::media_clip`].
+pub struct MediaClip<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(MediaClip: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"MediaClip"));
+    dict.pair(Name(b"S"), Name(b"MCD"));
+    Self { dict }
+});
+
+impl<'a> MediaClip<'a> {
+    /// Write the `/N` attribute to set the name of the media clip that may be
+    /// shown in the user interface.
+    pub fn name(&mut self, text: TextStr) -> &mut Self {
+        self.pair(Name(b"N"), text);
+        self
+    }
+
+    /// Start writing the `/D` dictionary to set the file with the media data.
+    /// Required.
+    pub fn data(&mut self) -> FileSpec<'_> {
+        self.insert(Name(b"D")).start()
+    }
+
+    /// Write the `/D` attribute to reference a [file
+    /// specification](FileSpec) with the media data.
+    pub fn data_ref(&mut self, file_spec: Ref) -> &mut Self {
+        self.pair(Name(b"D"), file_spec);
+        self
+    }
+
+    /// Write the `/CT` attribute to set the MIME type of the media data, e.g.
+    /// `video/mp4`.
+    pub fn content_type(&mut self, mime: Str) -> &mut Self {
+        self.pair(Name(b"CT"), mime);
+        self
+    }
+
+    /// Write the `/P` dictionary to set under which circumstances the viewer
+    /// may write the media data to a temporary file for playback.
+    pub fn temp_file(&mut self, permission: TempFilePermission) -> &mut Self {
+        self.insert(Name(b"P"))
+            .dict()
+            .pair(Name(b"Type"), Name(b"MediaPermissions"))
+            .pair(Name(b"TF"), permission.to_str());
+        self
+    }
+}
+
+deref!('a, MediaClip<'a> => Dict<'a>, dict);
+
+/// Writer for a _media play parameters dictionary_. PDF 1.5+.
+///
+/// This struct is created by [`Rendition::media_play_parameters`].
+pub struct MediaPlayParameters<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(MediaPlayParameters: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"MediaPlayParams"));
+    Self { dict }
+});
+
+impl<'a> MediaPlayParameters<'a> {
+    /// Start writing the `/MH` dictionary with the settings that must be
+    /// honored for the rendition to be playable.
+    pub fn must_honor(&mut self) -> MediaPlaySettings<'_> {
+        self.insert(Name(b"MH")).start()
+    }
+
+    /// Start writing the `/BE` dictionary with the settings that the viewer
+    /// should honor if it can.
+    pub fn best_effort(&mut self) -> MediaPlaySettings<'_> {
+        self.insert(Name(b"BE")).start()
+    }
+}
+
+deref!('a, MediaPlayParameters<'a> => Dict<'a>, dict);
+
+/// Writer for the settings in a [media play parameters
+/// dictionary](MediaPlayParameters). PDF 1.5+.
+///
+/// This struct is created by [`MediaPlayParameters::must_honor`] and
+/// [`MediaPlayParameters::best_effort`].
+pub struct MediaPlaySettings<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(MediaPlaySettings: |obj| Self { dict: obj.dict() });
+
+impl<'a> MediaPlaySettings<'a> {
+    /// Write the `/V` attribute to set the volume as a percentage of the
+    /// recorded volume between `0` and `100`. Defaults to `100`.
+    pub fn volume(&mut self, percent: i32) -> &mut Self {
+        self.pair(Name(b"V"), percent);
+        self
+    }
+
+    /// Write the `/C` attribute to set whether controls are shown while the
+    /// media is played. Defaults to `false`.
+    pub fn show_controls(&mut self, show: bool) -> &mut Self {
+        self.pair(Name(b"C"), show);
+        self
+    }
+
+    /// Write the `/F` attribute to set how the media is fitted into the
+    /// annotation rectangle or floating window. Defaults to
+    /// [`MediaFit::Default`].
+    pub fn fit(&mut self, fit: MediaFit) -> &mut Self {
+        self.pair(Name(b"F"), fit.to_int());
+        self
+    }
+
+    /// Write the `/D` dictionary to set for how many seconds the media is
+    /// played. Defaults to the intrinsic duration of the media.
+    pub fn duration(&mut self, seconds: f32) -> &mut Self {
+        let mut duration = self.insert(Name(b"D")).dict();
+        duration.pair(Name(b"Type"), Name(b"MediaDuration"));
+        duration.pair(Name(b"S"), Name(b"T"));
+        duration
+            .insert(Name(b"T"))
+            .dict()
+            .pair(Name(b"Type"), Name(b"Timespan"))
+            .pair(Name(b"S"), Name(b"S"))
+            .pair(Name(b"V"), seconds);
+        duration.finish();
+        self
+    }
+
+    /// Write the `/D` dictionary to play the media indefinitely.
+    pub fn duration_infinite(&mut self) -> &mut Self {
+        self.insert(Name(b"D"))
+            .dict()
+            .pair(Name(b"Type"), Name(b"MediaDuration"))
+            .pair(Name(b"S"), Name(b"F"));
+        self
+    }
+
+    /// Write the `/A` attribute to set whether the media is played
+    /// automatically when activated. Defaults to `true`.
+    pub fn auto_play(&mut self, auto: bool) -> &mut Self {
+        self.pair(Name(b"A"), auto);
+        self
+    }
+
+    /// Write the `/RC` attribute to set how often the media is played. Zero
+    /// repeats the media indefinitely. Defaults to `1.0`.
+    pub fn repeat_count(&mut self, count: f32) -> &mut Self {
+        self.pair(Name(b"RC"), count);
+        self
+    }
+}
+
+deref!('a, MediaPlaySettings<'a> => Dict<'a>, dict);
+
+/// How media is fitted into its [play area](MediaPlaySettings::fit).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum MediaFit {
+    /// Scale the media uniformly so that it fits entirely.
+    Meet,
+    /// Scale the media uniformly so that it fills the area entirely, cropping
+    /// the excess.
+    Slice,
+    /// Scale the media non-uniformly so that it fills the area exactly.
+    Fill,
+    /// Keep the size of the media and provide scroll bars if necessary.
+    Scroll,
+    /// Keep the size of the media and crop the excess.
+    Hidden,
+    /// Use the viewer's default.
+    Default,
+}
+
+impl MediaFit {
+    pub(crate) fn to_int(self) -> i32 {
+        match self {
+            Self::Meet => 0,
+            Self::Slice => 1,
+            Self::Fill => 2,
+            Self::Scroll => 3,
+            Self::Hidden => 4,
+            Self::Default => 5,
+        }
+    }
+}
+
+/// Under which circumstances the media data of a [media clip](MediaClip) may
+/// be written to a temporary file.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TempFilePermission {
+    /// Never write a temporary file.
+    Never,
+    /// Only write a temporary file if the document permits content extraction.
+    Extract,
+    /// Only write a temporary file if the document permits content access.
+    Access,
+    /// Always write a temporary file if necessary.
+    Always,
+}
+
+impl TempFilePermission {
+    pub(crate) fn to_str(self) -> Str<'static> {
+        match self {
+            Self::Never => Str(b"TEMPNEVER"),
+            Self::Extract => Str(b"TEMPEXTRACT"),
+            Self::Access => Str(b"TEMPACCESS"),
+            Self::Always => Str(b"TEMPALWAYS"),
+        }
+    }
+}
+
+/// What a rendition action does with its [rendition](Rendition).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RenditionOperation {
+    /// Play the rendition, stopping any rendition that is already playing in
+    /// the screen annotation.
+    Play,
+    /// Stop the rendition.
+    Stop,
+    /// Pause the rendition.
+    Pause,
+    /// Resume a paused rendition.
+    Resume,
+    /// Play the rendition or resume it if it is paused.
+    PlayOrResume,
+}
+
+impl RenditionOperation {
+    pub(crate) fn to_int(self) -> i32 {
+        match self {
+            Self::Play => 0,
+            Self::Stop => 1,
+            Self::Pause => 2,
+            Self::Resume => 3,
+            Self::PlayOrResume => 4,
+        }
+    }
+}
+
+/// Writer for a _rich media content dictionary_. PDF 2.0+.
+///
+/// This struct is created by [`Annotation::rich_media_content`].
+pub struct RichMediaContent<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(RichMediaContent: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"RichMediaContent"));
+    Self { dict }
+});
+
+impl<'a> RichMediaContent<'a> {
+    /// Start writing the `/Assets` name tree to map file names to the [file
+    /// specifications](FileSpec) of the media files.
+    pub fn assets(&mut self) -> NameTree<'_, Ref> {
+        self.insert(Name(b"Assets")).start()
+    }
+
+    /// Start writing the `/Configurations` array to set the ways in which the
+    /// assets can be presented. The first configuration is the default.
+    pub fn configurations(&mut self) -> TypedArray<'_, RichMediaConfiguration<'_>> {
+        self.insert(Name(b"Configurations")).array().typed()
+    }
+}
+
+deref!('a, RichMediaContent<'a> => Dict<'a>, dict);
+
+/// Writer for a _rich media configuration dictionary_. PDF 2.0+.
+///
+/// An array of this struct is created by [`RichMediaContent::configurations`].
+pub struct RichMediaConfiguration<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(RichMediaConfiguration: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"RichMediaConfiguration"));
+    Self { dict }
+});
+
+impl<'a> RichMediaConfiguration<'a> {
+    /// Write the `/Subtype` attribute to set the kind of scene.
+    pub fn subtype(&mut self, kind: RichMediaType) -> &mut Self {
+        self.pair(Name(b"Subtype"), kind.to_name());
+        self
+    }
+
+    /// Write the `/Name` attribute to set the name of the configuration that
+    /// may be shown in the user interface.
+    pub fn name(&mut self, text: TextStr) -> &mut Self {
+        self.pair(Name(b"Name"), text);
+        self
+    }
+
+    /// Start writing the `/Instances` array to set which assets are shown.
+    pub fn instances(&mut self) -> TypedArray<'_, RichMediaInstance<'_>> {
+        self.insert(Name(b"Instances")).array().typed()
+    }
+}
+
+deref!('a, RichMediaConfiguration<'a> => Dict<'a>, dict);
+
+/// Writer for a _rich media instance dictionary_. PDF 2.0+.
+///
+/// An array of this struct is created by [`RichMediaConfiguration::instances`].
+pub struct RichMediaInstance<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(RichMediaInstance: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"RichMediaInstance"));
+    Self { dict }
+});
+
+impl<'a> RichMediaInstance<'a> {
+    /// Write the `/Subtype` attribute to set the kind of media.
+    pub fn subtype(&mut self, kind: RichMediaType) -> &mut Self {
+        self.pair(Name(b"Subtype"), kind.to_name());
+        self
+    }
+
+    /// Write the `/Asset` attribute to reference the [file
+    /// specification](FileSpec) of the asset. The file specification must
+    /// also be in the [`assets`](RichMediaContent::assets) name tree.
+    pub fn asset(&mut self, file_spec: Ref) -> &mut Self {
+        self.pair(Name(b"Asset"), file_spec);
+        self
+    }
+}
+
+deref!('a, RichMediaInstance<'a> => Dict<'a>, dict);
+
+/// The kind of media in a rich media annotation.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RichMediaType {
+    /// 3D artwork.
+    ThreeD,
+    /// A Flash file.
+    Flash,
+    /// An audio file.
+    Sound,
+    /// A video file.
+    Video,
+}
+
+impl RichMediaType {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::ThreeD => Name(b"3D"),
+            Self::Flash => Name(b"Flash"),
+            Self::Sound => Name(b"Sound"),
+            Self::Video => Name(b"Video"),
+        }
+    }
+}
+
+/// Writer for a _rich media settings dictionary_. PDF 2.0+.
+///
+/// This struct is created by [`Annotation::rich_media_settings`].
+pub struct RichMediaSettings<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(RichMediaSettings: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"RichMediaSettings"));
+    Self { dict }
+});
+
+impl<'a> RichMediaSettings<'a> {
+    /// Write the `/Activation` dictionary to set when the annotation is
+    /// activated.
+    pub fn activation(&mut self, condition: RichMediaActivation) -> &mut Self {
+        self.insert(Name(b"Activation"))
+            .dict()
+            .pair(Name(b"Type"), Name(b"RichMediaActivation"))
+            .pair(Name(b"Condition"), condition.to_name());
+        self
+    }
+
+    /// Write the `/Deactivation` dictionary to set when the annotation is
+    /// deactivated.
+    pub fn deactivation(&mut self, condition: RichMediaDeactivation) -> &mut Self {
+        self.insert(Name(b"Deactivation"))
+            .dict()
+            .pair(Name(b"Type"), Name(b"RichMediaDeactivation"))
+            .pair(Name(b"Condition"), condition.to_name());
+        self
+    }
+}
+
+deref!('a, RichMediaSettings<'a> => Dict<'a>, dict);
+
+/// When a rich media annotation is activated.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RichMediaActivation {
+    /// When the user explicitly activates the annotation.
+    Explicit,
+    /// When the page containing the annotation is opened.
+    PageOpen,
+    /// When the page containing the annotation becomes visible.
+    PageVisible,
+}
+
+impl RichMediaActivation {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::Explicit => Name(b"XA"),
+            Self::PageOpen => Name(b"PO"),
+            Self::PageVisible => Name(b"PV"),
+        }
+    }
+}
+
+/// When a rich media annotation is deactivated.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RichMediaDeactivation {
+    /// When the user explicitly deactivates the annotation.
+    Explicit,
+    /// When the page containing the annotation is closed.
+    PageClose,
+    /// When the page containing the annotation becomes invisible.
+    PageInvisible,
+}
+
+impl RichMediaDeactivation {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::Explicit => Name(b"XD"),
+            Self::PageClose => Name(b"PC"),
+            Self::PageInvisible => Name(b"PI"),
+        }
+    }
+}
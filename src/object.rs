@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::convert::TryFrom;
 use std::marker::PhantomData;
 use std::mem::ManuallyDrop;
@@ -13,6 +14,7 @@ impl Type for i32 {}
 impl Type for f32 {}
 impl Type for Str<'_> {}
 impl Type for TextStr<'_> {}
+impl Type for WrappedStr<'_> {}
 impl Type for Name<'_> {}
 impl Type for Null {}
 impl Type for Ref {}
@@ -22,9 +24,37 @@ impl Type for Date {}
 impl<'a, T: Writer<'a>> Type for T {}
 
 /// A primitive PDF object.
+///
+/// All methods that write values, like [`Array::item`] and [`Dict::pair`],
+/// accept any primitive. The trait can be implemented for custom types by
+/// writing them in terms of other primitives.
+///
+/// ```
+/// use pdf_writer::{Chunk, Name, Primitive, Ref};
+///
+/// struct Percent(i32);
+///
+/// impl Primitive for Percent {
+///     fn write(self, buf: &mut Vec<u8>) {
+///         (self.0 as f32 / 100.0).write(buf);
+///     }
+/// }
+///
+/// let mut chunk = Chunk::new();
+/// chunk.indirect(Ref::new(1)).dict().pair(Name(b"Opacity"), Percent(50));
+/// assert_eq!(chunk.as_bytes(), b"1 0 obj\n<<\n  /Opacity 0.5\n>>\nendobj\n\n");
+/// ```
 pub trait Primitive {
     /// Write the object into a buffer.
     fn write(self, buf: &mut Vec<u8>);
+
+    /// The indirect reference this primitive is, if any. Used to check that
+    /// all referenced objects were written.
+    #[doc(hidden)]
+    #[inline]
+    fn reference(&self) -> Option<Ref> {
+        None
+    }
 }
 
 impl<T: Primitive> Primitive for &T
@@ -35,6 +65,11 @@ where
     fn write(self, buf: &mut Vec<u8>) {
         (*self).write(buf);
     }
+
+    #[inline]
+    fn reference(&self) -> Option<Ref> {
+        (**self).reference()
+    }
 }
 
 impl Primitive for bool {
@@ -92,6 +127,67 @@ impl Primitive for Str<'_> {
     }
 }
 
+impl<'a> Str<'a> {
+    /// Wrap the string onto multiple lines with at most `max` bytes of the
+    /// string per line.
+    ///
+    /// Literal strings are broken with a backslash followed by a newline,
+    /// which readers drop when parsing the string. Hexadecimal strings are
+    /// broken with plain newlines as whitespace is ignored in them. This can
+    /// help with legacy tools that do not handle very long lines.
+    pub fn wrapped(self, max: usize) -> WrappedStr<'a> {
+        assert!(max > 0, "maximum line length must be positive");
+        WrappedStr { string: self, max }
+    }
+}
+
+impl<'a> From<&'a [u8]> for Str<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl<'a> From<&'a str> for Str<'a> {
+    fn from(string: &'a str) -> Self {
+        Self(string.as_bytes())
+    }
+}
+
+/// A [string object](Str) that is spread over multiple lines.
+///
+/// This struct is created by [`Str::wrapped`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct WrappedStr<'a> {
+    string: Str<'a>,
+    max: usize,
+}
+
+impl Primitive for WrappedStr<'_> {
+    fn write(self, buf: &mut Vec<u8>) {
+        let start = buf.len();
+        self.string.write(buf);
+
+        let (open, close) = (buf[start], buf[buf.len() - 1]);
+        let inner = buf.split_off(start + 1);
+        let inner = &inner[..inner.len() - 1];
+        let (max, separator): (usize, &[u8]) = if open == b'(' {
+            (self.max, b"\\\n")
+        } else {
+            // Keep the two digits of a byte together.
+            ((self.max / 2).max(1) * 2, b"\n")
+        };
+
+        for (i, piece) in inner.chunks(max).enumerate() {
+            if i > 0 {
+                buf.extend(separator);
+            }
+            buf.extend(piece);
+        }
+
+        buf.push(close);
+    }
+}
+
 /// A unicode text string object.
 ///
 /// This is written as a [`Str`] containing a byte order mark followed by
@@ -116,10 +212,26 @@ impl Primitive for TextStr<'_> {
 
 /// A name object.
 ///
-/// Written as `/Thing`.
+/// Written as `/Thing`. Characters other than ASCII letters and numbers are
+/// escaped when writing.
+///
+/// Names can also be created with `"Thing".into()` or with the [`name!`]
+/// macro.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Name<'a>(pub &'a [u8]);
 
+impl<'a> From<&'a [u8]> for Name<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl<'a> From<&'a str> for Name<'a> {
+    fn from(string: &'a str) -> Self {
+        Self(string.as_bytes())
+    }
+}
+
 impl Primitive for Name<'_> {
     fn write(self, buf: &mut Vec<u8>) {
         buf.push(b'/');
@@ -176,6 +288,11 @@ impl Primitive for Ref {
         buf.push_int(self.0.get());
         buf.extend(b" 0 R");
     }
+
+    #[inline]
+    fn reference(&self) -> Option<Ref> {
+        Some(*self)
+    }
 }
 
 /// A rectangle, specified by two opposite corners.
@@ -198,6 +315,65 @@ impl Rect {
         Self { x1, y1, x2, y2 }
     }
 
+    /// Create a new rectangle from its lower-left corner and its size.
+    #[inline]
+    pub fn from_xywh(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self::new(x, y, x + width, y + height)
+    }
+
+    /// The horizontal extent of the rectangle.
+    #[inline]
+    pub fn width(self) -> f32 {
+        (self.x2 - self.x1).abs()
+    }
+
+    /// The vertical extent of the rectangle.
+    #[inline]
+    pub fn height(self) -> f32 {
+        (self.y2 - self.y1).abs()
+    }
+
+    /// The same rectangle with the lower-left corner first and the
+    /// upper-right corner second.
+    ///
+    /// Writers of attributes like a page's media box or an annotation's
+    /// rectangle normalize automatically since some viewers misrender
+    /// rectangles with swapped corners.
+    #[inline]
+    pub fn normalized(self) -> Self {
+        Self::new(
+            self.x1.min(self.x2),
+            self.y1.min(self.y2),
+            self.x1.max(self.x2),
+            self.y1.max(self.y2),
+        )
+    }
+
+    /// The area covered by both rectangles, normalized, or `None` if they
+    /// don't overlap. Rectangles that only touch intersect in a rectangle
+    /// without area.
+    #[inline]
+    pub fn intersect(self, other: Self) -> Option<Self> {
+        let (a, b) = (self.normalized(), other.normalized());
+        let rect =
+            Self::new(a.x1.max(b.x1), a.y1.max(b.y1), a.x2.min(b.x2), a.y2.min(b.y2));
+        (rect.x1 <= rect.x2 && rect.y1 <= rect.y2).then_some(rect)
+    }
+
+    /// The smallest normalized rectangle that covers both rectangles.
+    #[inline]
+    pub fn union(self, other: Self) -> Self {
+        let (a, b) = (self.normalized(), other.normalized());
+        Self::new(a.x1.min(b.x1), a.y1.min(b.y1), a.x2.max(b.x2), a.y2.max(b.y2))
+    }
+
+    /// Whether the point lies within the rectangle or on its boundary.
+    #[inline]
+    pub fn contains(self, x: f32, y: f32) -> bool {
+        let rect = self.normalized();
+        rect.x1 <= x && x <= rect.x2 && rect.y1 <= y && y <= rect.y2
+    }
+
     /// Convert this rectangle into 8 floats describing the four corners of the
     /// rectangle in counterclockwise order.
     #[inline]
@@ -323,7 +499,8 @@ impl Primitive for Date {
     fn write(self, buf: &mut Vec<u8>) {
         write!(buf, "(D:{:04}", self.year).unwrap();
 
-        self.month
+        let utc_offset_hour = self
+            .month
             .and_then(|month| {
                 write!(buf, "{:02}", month).unwrap();
                 self.day
@@ -343,16 +520,17 @@ impl Primitive for Date {
             .and_then(|second| {
                 write!(buf, "{:02}", second).unwrap();
                 self.utc_offset_hour
-            })
-            .map(|utc_offset_hour| {
-                if utc_offset_hour == 0 && self.utc_offset_minute == 0 {
-                    buf.push(b'Z');
-                } else {
-                    write!(buf, "{:+03}'{:02}", utc_offset_hour, self.utc_offset_minute)
-                        .unwrap();
-                }
             });
 
+        if let Some(utc_offset_hour) = utc_offset_hour {
+            if utc_offset_hour == 0 && self.utc_offset_minute == 0 {
+                buf.push(b'Z');
+            } else {
+                write!(buf, "{:+03}'{:02}", utc_offset_hour, self.utc_offset_minute)
+                    .unwrap();
+            }
+        }
+
         buf.push(b')');
     }
 }
@@ -363,13 +541,39 @@ pub struct Obj<'a> {
     buf: &'a mut Vec<u8>,
     indirect: bool,
     indent: u8,
+    array_wrap: usize,
+    profile: Profile,
+    record: Option<Recorder<'a>>,
 }
 
 impl<'a> Obj<'a> {
     /// Start a new direct object.
     #[inline]
     pub(crate) fn direct(buf: &'a mut Vec<u8>, indent: u8) -> Self {
-        Self { buf, indirect: false, indent }
+        Self {
+            buf,
+            indirect: false,
+            indent,
+            array_wrap: 0,
+            profile: Profile::default(),
+            record: None,
+        }
+    }
+
+    /// Pretty print the object, writing arrays with more than the given
+    /// number of items with one item per line. Zero disables pretty printing.
+    #[inline]
+    pub(crate) fn pretty(mut self, array_wrap: usize) -> Self {
+        self.array_wrap = array_wrap;
+        self
+    }
+
+    /// Let typed writers emit or omit redundant entries for the given
+    /// compatibility profile.
+    #[inline]
+    pub(crate) fn profile(mut self, profile: Profile) -> Self {
+        self.profile = profile;
+        self
     }
 
     /// Start a new indirect object.
@@ -377,18 +581,50 @@ impl<'a> Obj<'a> {
     pub(crate) fn indirect(buf: &'a mut Vec<u8>, id: Ref) -> Self {
         buf.push_int(id.get());
         buf.extend(b" 0 obj\n");
-        Self { buf, indirect: true, indent: 0 }
+        Self {
+            buf,
+            indirect: true,
+            indent: 0,
+            array_wrap: 0,
+            profile: Profile::default(),
+            record: None,
+        }
+    }
+
+    /// Let typed writers record the document structure checked by
+    /// [`PdfWriter::validate`](crate::PdfWriter::validate) and the indirect
+    /// references checked by [`PdfWriter::finish`](crate::PdfWriter::finish).
+    #[inline]
+    pub(crate) fn record(mut self, structure: &'a mut Structure, id: Ref) -> Self {
+        self.record = Some(Recorder::new(structure, id));
+        self
     }
 
     /// Write a primitive object.
     #[inline]
-    pub fn primitive<T: Primitive>(self, value: T) {
+    pub fn primitive<T: Primitive>(mut self, value: T) {
+        if let (Some(record), Some(id)) = (&mut self.record, value.reference()) {
+            record.reference(id);
+        }
         value.write(self.buf);
         if self.indirect {
             self.buf.extend(b"\nendobj\n\n");
         }
     }
 
+    /// Write the given bytes verbatim as the object.
+    ///
+    /// This is an escape hatch for constructs that are not covered by the
+    /// other methods. You are responsible for the bytes forming a valid PDF
+    /// object.
+    #[inline]
+    pub fn raw(self, bytes: &[u8]) {
+        self.buf.extend(bytes);
+        if self.indirect {
+            self.buf.extend(b"\nendobj\n\n");
+        }
+    }
+
     /// Start writing an array.
     #[inline]
     pub fn array(self) -> Array<'a> {
@@ -443,6 +679,10 @@ pub struct Array<'a> {
     buf: &'a mut Vec<u8>,
     indirect: bool,
     indent: u8,
+    array_wrap: usize,
+    profile: Profile,
+    record: Option<Recorder<'a>>,
+    separators: Vec<usize>,
     len: i32,
 }
 
@@ -452,6 +692,10 @@ writer!(Array: |obj| {
         buf: obj.buf,
         indirect: obj.indirect,
         indent: obj.indent,
+        array_wrap: obj.array_wrap,
+        profile: obj.profile,
+        record: obj.record,
+        separators: vec![],
         len: 0,
     }
 });
@@ -463,14 +707,66 @@ impl<'a> Array<'a> {
         self.len
     }
 
+    /// Whether no items have been written so far.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Start writing an arbitrary item.
     #[inline]
     pub fn push(&mut self) -> Obj<'_> {
         if self.len != 0 {
-            self.buf.push(b' ');
+            let len = self.len as usize;
+            if self.array_wrap == 0 {
+                self.buf.push(b' ');
+            } else if len < self.array_wrap {
+                // Remember where the items are separated in case the array
+                // turns out to be long enough to be wrapped.
+                self.separators.push(self.buf.len());
+                self.buf.push(b' ');
+            } else {
+                if len == self.array_wrap {
+                    self.wrap_previous();
+                }
+                self.push_line_break();
+            }
         }
         self.len += 1;
-        Obj::direct(self.buf, self.indent)
+        let mut obj = Obj::direct(self.buf, self.indent)
+            .pretty(self.array_wrap)
+            .profile(self.profile);
+        obj.record = self.record.as_mut().map(Recorder::reborrow);
+        obj
+    }
+
+    /// The recorder of the indirect object the array belongs to, if any.
+    #[inline]
+    pub(crate) fn recorder(&mut self) -> Option<&mut Recorder<'a>> {
+        self.record.as_mut()
+    }
+
+    /// Put each of the items written so far on its own line.
+    fn wrap_previous(&mut self) {
+        let separators = std::mem::take(&mut self.separators);
+        let first = match separators.first() {
+            Some(&first) => first,
+            None => return,
+        };
+        let tail = self.buf.split_off(first);
+        let mut last = 0;
+        for &sep in &separators {
+            self.buf.extend(&tail[last..sep - first]);
+            self.push_line_break();
+            last = sep - first + 1;
+        }
+        self.buf.extend(&tail[last..]);
+    }
+
+    /// Start a new line for the next item.
+    fn push_line_break(&mut self) {
+        self.buf.push(b'\n');
+        self.buf.push_indent(self.indent.saturating_add(2));
     }
 
     /// Write an item with a primitive value.
@@ -482,6 +778,16 @@ impl<'a> Array<'a> {
         self
     }
 
+    /// Write an item with the given bytes verbatim. You are responsible for
+    /// the bytes forming a valid PDF object.
+    ///
+    /// This is a shorthand for `array.push().raw(bytes)`.
+    #[inline]
+    pub fn item_raw(&mut self, bytes: &[u8]) -> &mut Self {
+        self.push().raw(bytes);
+        self
+    }
+
     /// Write a sequence of items with primitive values.
     #[inline]
     pub fn items<T: Primitive>(
@@ -543,6 +849,12 @@ where
         self.array.len()
     }
 
+    /// Whether no items have been written so far.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Write an item.
     #[inline]
     pub fn item(&mut self, value: T) -> &mut Self
@@ -567,11 +879,11 @@ where
     ///
     /// Returns `T` but with its lifetime rewritten from `'a` to `'b`.
     #[inline]
-    pub fn push<'b>(&'b mut self) -> <T as Rewrite>::Output
+    pub fn push<'b>(&'b mut self) -> <T as Rewrite<'b>>::Output
     where
         T: Writer<'a> + Rewrite<'b>,
     {
-        <T as Rewrite>::Output::start(self.array.push())
+        <T as Rewrite<'b>>::Output::start(self.array.push())
     }
 }
 
@@ -580,6 +892,11 @@ pub struct Dict<'a> {
     buf: &'a mut Vec<u8>,
     indirect: bool,
     indent: u8,
+    array_wrap: usize,
+    profile: Profile,
+    record: Option<Recorder<'a>>,
+    watched: &'static [&'static [u8]],
+    seen: u32,
     len: i32,
 }
 
@@ -589,6 +906,11 @@ writer!(Dict: |obj| {
         buf: obj.buf,
         indirect: obj.indirect,
         indent: obj.indent.saturating_add(2),
+        array_wrap: obj.array_wrap,
+        profile: obj.profile,
+        record: obj.record,
+        watched: &[],
+        seen: 0,
         len: 0,
     }
 });
@@ -600,20 +922,61 @@ impl<'a> Dict<'a> {
         self.len
     }
 
+    /// Whether no pairs have been written so far.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Start writing a pair with an arbitrary value.
     #[inline]
     pub fn insert(&mut self, key: Name) -> Obj<'_> {
-        self.len += 1;
-        self.buf.push(b'\n');
-
-        for _ in 0..self.indent {
-            self.buf.push(b' ');
+        if let Some(i) = self.watched.iter().position(|&watched| watched == key.0) {
+            self.seen |= 1 << i;
         }
 
+        self.len += 1;
+        self.buf.push(b'\n');
+        self.buf.push_indent(self.indent);
         self.buf.push_val(key);
         self.buf.push(b' ');
 
-        Obj::direct(self.buf, self.indent)
+        let mut obj = Obj::direct(self.buf, self.indent)
+            .pretty(self.array_wrap)
+            .profile(self.profile);
+        obj.record = self.record.as_mut().map(|record| record.reborrow_pair(key));
+        obj
+    }
+
+    /// The recorder of the indirect object the dictionary belongs to, if any.
+    #[inline]
+    pub(crate) fn recorder(&mut self) -> Option<&mut Recorder<'a>> {
+        self.record.as_mut()
+    }
+
+    /// The compatibility profile of the document the dictionary is written
+    /// into.
+    #[inline]
+    pub(crate) fn profile(&self) -> Profile {
+        self.profile
+    }
+
+    /// Track whether the given keys are written, no matter whether through
+    /// a typed writer or through the generic methods of the dictionary.
+    ///
+    /// At most 32 keys can be watched.
+    #[inline]
+    pub(crate) fn watch(&mut self, keys: &'static [&'static [u8]]) {
+        debug_assert!(keys.len() <= 32);
+        self.watched = keys;
+    }
+
+    /// Whether a key passed to [`watch`](Self::watch) was written.
+    #[inline]
+    pub(crate) fn written(&self, key: &[u8]) -> bool {
+        let i = self.watched.iter().position(|&watched| watched == key);
+        debug_assert!(i.is_some(), "key is not watched");
+        i.map_or(false, |i| self.seen & (1 << i) != 0)
     }
 
     /// Write a pair with a primitive value.
@@ -625,6 +988,26 @@ impl<'a> Dict<'a> {
         self
     }
 
+    /// Write a pair with the given bytes verbatim as the value. You are
+    /// responsible for the bytes forming a valid PDF object.
+    ///
+    /// This is a shorthand for `dict.insert(key).raw(bytes)`.
+    #[inline]
+    pub fn pair_raw(&mut self, key: Name, bytes: &[u8]) -> &mut Self {
+        self.insert(key).raw(bytes);
+        self
+    }
+
+    /// Write a pair with a `null` value.
+    ///
+    /// In contrast to omitting the key, this overrides an inherited value,
+    /// e.g. the `/Rotate` of a [page](crate::writers::Page) inherited from its
+    /// page tree. This is a shorthand for `dict.pair(key, Null)`.
+    #[inline]
+    pub fn pair_null(&mut self, key: Name) -> &mut Self {
+        self.pair(key, Null)
+    }
+
     /// Write a sequence of pairs with primitive values.
     pub fn pairs<'n, T: Primitive>(
         &mut self,
@@ -648,9 +1031,9 @@ impl Drop for Dict<'_> {
     fn drop(&mut self) {
         if self.len != 0 {
             self.buf.push(b'\n');
-            for _ in 0..self.indent - 2 {
-                self.buf.push(b' ');
-            }
+            self.buf.push_indent(self.indent - 2);
+        } else if self.array_wrap != 0 {
+            self.buf.push(b' ');
         }
         self.buf.extend(b">>");
         if self.indirect {
@@ -691,6 +1074,12 @@ where
         self.dict.len()
     }
 
+    /// Whether no pairs have been written so far.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Write a key-value pair.
     #[inline]
     pub fn pair(&mut self, key: Name, value: T) -> &mut Self
@@ -718,18 +1107,24 @@ where
     ///
     /// Returns `T` but with its lifetime rewritten from `'a` to `'b`.
     #[inline]
-    pub fn insert<'b>(&'b mut self, key: Name) -> <T as Rewrite>::Output
+    pub fn insert<'b>(&'b mut self, key: Name) -> <T as Rewrite<'b>>::Output
     where
         T: Writer<'a> + Rewrite<'b>,
     {
-        <T as Rewrite>::Output::start(self.dict.insert(key))
+        <T as Rewrite<'b>>::Output::start(self.dict.insert(key))
     }
 }
 
 /// Writer for an indirect stream object.
+///
+/// The stream data is only borrowed while the stream dictionary is written.
+/// When the stream is finished, the data is copied into the output buffer in
+/// a single step, so no intermediate copies are made.
 pub struct Stream<'a> {
     dict: ManuallyDrop<Dict<'a>>,
-    data: &'a [u8],
+    data: Cow<'a, [u8]>,
+    filtered: bool,
+    chain_len: Option<usize>,
 }
 
 impl<'a> Stream<'a> {
@@ -739,7 +1134,21 @@ impl<'a> Stream<'a> {
     /// `i32::MAX`.
     pub(crate) fn start(obj: Obj<'a>, data: &'a [u8]) -> Self {
         assert!(obj.indirect);
+        Self::start_any(obj, Cow::Borrowed(data))
+    }
+
+    /// Start writing a stream with data that is owned by the writer, e.g.
+    /// because it was compressed on the fly.
+    ///
+    /// Panics if the object writer is not indirect or the stream length exceeds
+    /// `i32::MAX`.
+    #[cfg(feature = "flate")]
+    pub(crate) fn start_owned(obj: Obj<'a>, data: Vec<u8>) -> Self {
+        assert!(obj.indirect);
+        Self::start_any(obj, Cow::Owned(data))
+    }
 
+    fn start_any(obj: Obj<'a>, data: Cow<'a, [u8]>) -> Self {
         let mut dict = obj.dict();
         dict.pair(
             Name(b"Length"),
@@ -748,23 +1157,77 @@ impl<'a> Stream<'a> {
             }),
         );
 
-        Self { dict: ManuallyDrop::new(dict), data }
+        Self {
+            dict: ManuallyDrop::new(dict),
+            data,
+            filtered: false,
+            chain_len: None,
+        }
     }
 
     /// Write the `/Filter` attribute.
     pub fn filter(&mut self, filter: Filter) -> &mut Self {
         self.pair(Name(b"Filter"), filter.to_name());
+        self.filtered = true;
+        self
+    }
+
+    /// Write the `/Filter` attribute as an array of filters. The filters are
+    /// applied in order when decoding, so the first filter is the one that was
+    /// applied last when encoding.
+    pub fn filters(&mut self, filters: impl IntoIterator<Item = Filter>) -> &mut Self {
+        let mut len = 0;
+        self.insert(Name(b"Filter"))
+            .array()
+            .items(filters.into_iter().inspect(|_| len += 1).map(Filter::to_name));
+        self.filtered = true;
+        self.chain_len = Some(len);
         self
     }
+
+    /// The raw stream data.
+    pub(crate) fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Whether a filter was set through [`filter`](Self::filter) or
+    /// [`filters`](Self::filters).
+    pub(crate) fn is_filtered(&self) -> bool {
+        self.filtered
+    }
+
+    /// Start writing the `/DecodeParms` dictionary with the parameters of the
+    /// stream's single [filter](Self::filter).
+    pub fn decode_parms(&mut self) -> DecodeParms<'_> {
+        self.insert(Name(b"DecodeParms")).start()
+    }
+
+    /// Start writing the `/DecodeParms` array with one entry for each filter
+    /// of a [filter chain](Self::filters).
+    ///
+    /// The filter chain must be written first. In debug builds, panics if the
+    /// number of entries differs from the number of filters.
+    pub fn decode_parms_array(&mut self) -> DecodeParmsArray<'_> {
+        debug_assert!(
+            self.chain_len.is_some(),
+            "filter chain must be written before its decode parameters",
+        );
+        let expected = self.chain_len;
+        let mut parms: DecodeParmsArray = self.insert(Name(b"DecodeParms")).start();
+        parms.expected = expected;
+        parms
+    }
 }
 
 impl Drop for Stream<'_> {
     fn drop(&mut self) {
-        self.dict.buf.extend(b"\n>>");
-        self.dict.buf.extend(b"\nstream\n");
-        self.dict.buf.extend(self.data.as_ref());
-        self.dict.buf.extend(b"\nendstream");
-        self.dict.buf.extend(b"\nendobj\n\n");
+        const HEAD: &[u8] = b"\n>>\nstream\n";
+        const TAIL: &[u8] = b"\nendstream\nendobj\n\n";
+        let buf = &mut self.dict.buf;
+        buf.reserve(HEAD.len() + self.data.len() + TAIL.len());
+        buf.extend_from_slice(HEAD);
+        buf.extend_from_slice(&self.data);
+        buf.extend_from_slice(TAIL);
     }
 }
 
@@ -803,6 +1266,200 @@ impl Filter {
     }
 }
 
+/// Writer for a _filter parameter dictionary_.
+///
+/// Which attributes are allowed depends on the filter the parameters belong
+/// to.
+///
+/// This struct is created by [`Stream::decode_parms`] and
+/// [`DecodeParmsArray::push`].
+pub struct DecodeParms<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(DecodeParms: |obj| Self { dict: obj.dict() });
+
+/// Parameters for the `LZWDecode` and `FlateDecode` filters.
+impl DecodeParms<'_> {
+    /// Write the `/Predictor` attribute to set the predictor algorithm that
+    /// was applied before compression. `1` means no prediction, `2` is TIFF
+    /// predictor 2 and `10` to `15` are the PNG predictors.
+    pub fn predictor(&mut self, predictor: i32) -> &mut Self {
+        self.pair(Name(b"Predictor"), predictor);
+        self
+    }
+
+    /// Write the `/Colors` attribute to set the number of interleaved color
+    /// components per sample for a predictor.
+    pub fn colors(&mut self, colors: i32) -> &mut Self {
+        self.pair(Name(b"Colors"), colors);
+        self
+    }
+
+    /// Write the `/BitsPerComponent` attribute to set the number of bits per
+    /// color component for a predictor.
+    pub fn bits_per_component(&mut self, bits: i32) -> &mut Self {
+        self.pair(Name(b"BitsPerComponent"), bits);
+        self
+    }
+
+    /// Write the `/Columns` attribute to set the number of samples per row for
+    /// a predictor.
+    pub fn columns(&mut self, columns: i32) -> &mut Self {
+        self.pair(Name(b"Columns"), columns);
+        self
+    }
+
+    /// Write the `/EarlyChange` attribute to set whether the code length is
+    /// increased one code early. `LZWDecode` only.
+    pub fn early_change(&mut self, early: bool) -> &mut Self {
+        self.pair(Name(b"EarlyChange"), early as i32);
+        self
+    }
+}
+
+/// Parameters for the `CCITTFaxDecode` filter.
+impl DecodeParms<'_> {
+    /// Write the `/K` attribute to set the encoding scheme. Negative values
+    /// mean pure two-dimensional encoding, zero means pure one-dimensional
+    /// encoding and positive values mean mixed encoding.
+    pub fn k(&mut self, k: i32) -> &mut Self {
+        self.pair(Name(b"K"), k);
+        self
+    }
+
+    /// Write the `/EndOfLine` attribute to set whether end-of-line bit
+    /// patterns are present.
+    pub fn end_of_line(&mut self, eol: bool) -> &mut Self {
+        self.pair(Name(b"EndOfLine"), eol);
+        self
+    }
+
+    /// Write the `/EncodedByteAlign` attribute to set whether encoded lines
+    /// begin on byte boundaries.
+    pub fn encoded_byte_align(&mut self, align: bool) -> &mut Self {
+        self.pair(Name(b"EncodedByteAlign"), align);
+        self
+    }
+
+    /// Write the `/Rows` attribute to set the height of the image in pixels.
+    pub fn rows(&mut self, rows: i32) -> &mut Self {
+        self.pair(Name(b"Rows"), rows);
+        self
+    }
+
+    /// Write the `/EndOfBlock` attribute to set whether the data is terminated
+    /// by an end-of-block pattern.
+    pub fn end_of_block(&mut self, eob: bool) -> &mut Self {
+        self.pair(Name(b"EndOfBlock"), eob);
+        self
+    }
+
+    /// Write the `/BlackIs1` attribute to set whether one bits are black
+    /// pixels.
+    pub fn black_is_1(&mut self, black: bool) -> &mut Self {
+        self.pair(Name(b"BlackIs1"), black);
+        self
+    }
+
+    /// Write the `/DamagedRowsBeforeError` attribute to set how many damaged
+    /// rows are tolerated.
+    pub fn damaged_rows_before_error(&mut self, rows: i32) -> &mut Self {
+        self.pair(Name(b"DamagedRowsBeforeError"), rows);
+        self
+    }
+}
+
+/// Parameters for the `JBIG2Decode`, `DCTDecode` and `Crypt` filters.
+impl DecodeParms<'_> {
+    /// Write the `/JBIG2Globals` attribute to reference a stream with global
+    /// segments shared between multiple images. `JBIG2Decode` only.
+    pub fn jbig2_globals(&mut self, globals: Ref) -> &mut Self {
+        self.pair(Name(b"JBIG2Globals"), globals);
+        self
+    }
+
+    /// Write the `/ColorTransform` attribute to set whether the color
+    /// components were transformed before encoding. `DCTDecode` only.
+    pub fn color_transform(&mut self, transform: bool) -> &mut Self {
+        self.pair(Name(b"ColorTransform"), transform as i32);
+        self
+    }
+
+    /// Write the `/Name` attribute to set which crypt filter to use. `Crypt`
+    /// only.
+    pub fn crypt_filter(&mut self, name: Name) -> &mut Self {
+        self.pair(Name(b"Name"), name);
+        self
+    }
+}
+
+deref!('a, DecodeParms<'a> => Dict<'a>, dict);
+
+/// Writer for a _filter parameter array_.
+///
+/// The array must contain one entry per filter of the [filter
+/// chain](Stream::filters), in the same order. Filters without parameters get
+/// a `null` entry.
+///
+/// This struct is created by [`Stream::decode_parms_array`].
+pub struct DecodeParmsArray<'a> {
+    array: Array<'a>,
+    expected: Option<usize>,
+}
+
+writer!(DecodeParmsArray: |obj| Self { array: obj.array(), expected: None });
+
+impl<'a> DecodeParmsArray<'a> {
+    /// Write a `null` entry for a filter that has no parameters.
+    pub fn null(&mut self) -> &mut Self {
+        self.check_next();
+        self.array.item(Null);
+        self
+    }
+
+    /// Start writing the parameters for the next filter.
+    pub fn push(&mut self) -> DecodeParms<'_> {
+        self.check_next();
+        self.array.push().start()
+    }
+
+    /// The number of written entries.
+    pub fn len(&self) -> i32 {
+        self.array.len()
+    }
+
+    /// Whether no entries have been written so far.
+    pub fn is_empty(&self) -> bool {
+        self.array.is_empty()
+    }
+
+    /// Assert in debug builds that there is a filter left for the next entry.
+    fn check_next(&self) {
+        if let Some(expected) = self.expected {
+            debug_assert!(
+                (self.array.len() as usize) < expected,
+                "more decode parameters than filters (expected {})",
+                expected,
+            );
+        }
+    }
+}
+
+impl Drop for DecodeParmsArray<'_> {
+    fn drop(&mut self) {
+        if let Some(expected) = self.expected {
+            if cfg!(debug_assertions) && !std::thread::panicking() {
+                assert_eq!(
+                    self.array.len() as usize,
+                    expected,
+                    "decode parameters must be written for every filter",
+                );
+            }
+        }
+    }
+}
+
 /// Writer for a _name tree node_.
 ///
 /// Name trees associate a large number of names with PDF objects. They are
@@ -841,7 +1498,10 @@ impl<T> NameTree<'_, T> {
 
     /// Write the `/Limits` array to set the range of names in this node. This
     /// is required for every node except the root node.
-    pub fn limits(&mut self, min: Name, max: Name) -> &mut Self {
+    ///
+    /// The limits are the smallest and largest key in the subtree of this
+    /// node, compared byte-wise.
+    pub fn limits(&mut self, min: Str, max: Str) -> &mut Self {
         self.dict.insert(Name(b"Limits")).array().typed().items([min, max]);
         self
     }
@@ -849,17 +1509,20 @@ impl<T> NameTree<'_, T> {
 
 /// Writer for a _name tree names_ array.
 ///
-/// The children must be added in ascending lexical order. Their minimum and
-/// maximum keys must not exceed the `/Limits` property of the parent [`NameTree`]
-/// node. This struct is created by [`NameTree::names`].
+/// The children must be added in ascending lexical order, comparing the raw
+/// bytes of the keys. Viewers fail to look up names in unsorted trees, so this
+/// is checked in debug builds. Their minimum and maximum keys must not exceed
+/// the `/Limits` property of the parent [`NameTree`] node. This struct is
+/// created by [`NameTree::names`].
 pub struct NameTreeEntries<'a, T> {
     arr: Array<'a>,
+    last: Option<Vec<u8>>,
     phantom: PhantomData<T>,
 }
 
 impl<'a, T> Writer<'a> for NameTreeEntries<'a, T> {
     fn start(obj: Obj<'a>) -> Self {
-        Self { arr: obj.array(), phantom: PhantomData }
+        Self { arr: obj.array(), last: None, phantom: PhantomData }
     }
 }
 
@@ -872,13 +1535,48 @@ where
     T: Primitive,
 {
     /// Insert a name-value pair.
+    ///
+    /// In debug builds, panics if `key` is not greater than the previously
+    /// inserted key.
     pub fn insert(&mut self, key: Str, value: T) -> &mut Self {
+        self.check_order(key);
         self.arr.item(key);
         self.arr.item(value);
         self
     }
 }
 
+impl NameTreeEntries<'_, Ref> {
+    /// Insert a name that maps to a destination written directly into the
+    /// tree instead of being referenced.
+    ///
+    /// This is used in the [destinations name
+    /// tree](crate::writers::Names::destinations).
+    ///
+    /// In debug builds, panics if `key` is not greater than the previously
+    /// inserted key.
+    pub fn insert_destination(&mut self, key: Str) -> Destination<'_> {
+        self.check_order(key);
+        self.arr.item(key);
+        self.arr.push().start()
+    }
+}
+
+impl<T> NameTreeEntries<'_, T> {
+    /// Assert in debug builds that the keys are inserted in ascending order.
+    fn check_order(&mut self, key: Str) {
+        if cfg!(debug_assertions) {
+            if let Some(last) = &self.last {
+                assert!(
+                    last.as_slice() < key.0,
+                    "name tree keys must be inserted in ascending order",
+                );
+            }
+            self.last = Some(key.0.to_vec());
+        }
+    }
+}
+
 /// Writer for a _number tree node_.
 ///
 /// Number trees associate a many integers with PDF objects. They are search
@@ -955,6 +1653,24 @@ where
     }
 }
 
+impl NumberTreeEntries<'_, Ref> {
+    /// Insert a number that maps to an array of references.
+    ///
+    /// This is used in the [parent tree](crate::writers::StructTreeRoot::parent_tree)
+    /// to map the `/StructParents` key of a page or content stream to the
+    /// structure elements of its marked-content sequences, indexed by their
+    /// marked-content identifiers.
+    pub fn insert_refs(
+        &mut self,
+        key: i32,
+        refs: impl IntoIterator<Item = Ref>,
+    ) -> &mut Self {
+        self.arr.item(key);
+        self.arr.push().array().items(refs);
+        self
+    }
+}
+
 /// Finish objects in postfix-style.
 ///
 /// In many cases you can use writers in builder-pattern style so that they are
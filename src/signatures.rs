@@ -0,0 +1,318 @@
+use super::*;
+
+/// Writer for a _signature dictionary_. PDF 1.3+.
+///
+/// The signature is the value of a [signature field](Field::signature_value).
+/// This crate does not compute signatures: The byte range and contents are
+/// typically written with placeholders, which are patched once the output is
+/// complete and has been signed externally.
+///
+/// This struct is created by [`PdfWriter::signature`].
+///
+/// # Example
+/// A certification signature that allows filling in forms, but no other
+/// changes.
+/// ```
+/// use pdf_writer::types::{MdpPermissions, TransformMethod};
+/// use pdf_writer::{Finish, Name, PdfWriter, Ref, Str};
+///
+/// let mut writer = PdfWriter::new();
+/// let mut catalog = writer.catalog(Ref::new(1));
+/// catalog.permissions().doc_mdp(Ref::new(2));
+/// catalog.finish();
+///
+/// let mut signature = writer.signature(Ref::new(2));
+/// signature.filter(Name(b"Adobe.PPKLite"));
+/// signature.sub_filter(Name(b"adbe.pkcs7.detached"));
+/// signature.byte_range([(0, 0), (0, 0)]);
+/// signature.contents(Str(&[0; 64]));
+/// signature.reference().push()
+///     .transform_method(TransformMethod::DocMdp)
+///     .transform_params()
+///     .permissions(MdpPermissions::FormFilling);
+/// ```
+pub struct Signature<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(Signature: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"Sig"));
+    Self { dict }
+});
+
+impl<'a> Signature<'a> {
+    /// Write the `/Filter` attribute to set the name of the preferred
+    /// signature handler, e.g. `Adobe.PPKLite`. Required.
+    pub fn filter(&mut self, filter: Name) -> &mut Self {
+        self.pair(Name(b"Filter"), filter);
+        self
+    }
+
+    /// Write the `/SubFilter` attribute to set the encoding of the signature,
+    /// e.g. `adbe.pkcs7.detached`.
+    pub fn sub_filter(&mut self, sub_filter: Name) -> &mut Self {
+        self.pair(Name(b"SubFilter"), sub_filter);
+        self
+    }
+
+    /// Write the `/Contents` attribute to set the signature value.
+    pub fn contents(&mut self, contents: Str) -> &mut Self {
+        self.pair(Name(b"Contents"), contents);
+        self
+    }
+
+    /// Write the `/ByteRange` attribute to set which parts of the file are
+    /// covered by the signature as pairs of byte offsets and lengths.
+    pub fn byte_range(
+        &mut self,
+        ranges: impl IntoIterator<Item = (i32, i32)>,
+    ) -> &mut Self {
+        let mut array = self.insert(Name(b"ByteRange")).array();
+        for (offset, len) in ranges {
+            array.item(offset).item(len);
+        }
+        array.finish();
+        self
+    }
+
+    /// Start writing the `/Reference` array with signature reference
+    /// dictionaries for modification detection. PDF 1.5+.
+    pub fn reference(&mut self) -> TypedArray<'_, SigRef<'_>> {
+        self.insert(Name(b"Reference")).array().typed()
+    }
+
+    /// Write the `/Name` attribute to set the name of the signer.
+    pub fn name(&mut self, name: TextStr) -> &mut Self {
+        self.pair(Name(b"Name"), name);
+        self
+    }
+
+    /// Write the `/M` attribute to set the time of signing.
+    pub fn date(&mut self, date: Date) -> &mut Self {
+        self.pair(Name(b"M"), date);
+        self
+    }
+
+    /// Write the `/Location` attribute to set where the document was signed.
+    pub fn location(&mut self, location: TextStr) -> &mut Self {
+        self.pair(Name(b"Location"), location);
+        self
+    }
+
+    /// Write the `/Reason` attribute to set why the document was signed.
+    pub fn reason(&mut self, reason: TextStr) -> &mut Self {
+        self.pair(Name(b"Reason"), reason);
+        self
+    }
+
+    /// Write the `/ContactInfo` attribute to set how the signer can be
+    /// contacted.
+    pub fn contact_info(&mut self, info: TextStr) -> &mut Self {
+        self.pair(Name(b"ContactInfo"), info);
+        self
+    }
+}
+
+deref!('a, Signature<'a> => Dict<'a>, dict);
+
+/// Writer for a _signature reference dictionary_. PDF 1.5+.
+///
+/// This struct is created by [`Signature::reference`].
+pub struct SigRef<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(SigRef: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"SigRef"));
+    Self { dict }
+});
+
+impl<'a> SigRef<'a> {
+    /// Write the `/TransformMethod` attribute to set which parts of the
+    /// document are covered by the signature. Required.
+    pub fn transform_method(&mut self, method: TransformMethod) -> &mut Self {
+        self.pair(Name(b"TransformMethod"), method.to_name());
+        self
+    }
+
+    /// Start writing the `/TransformParams` dictionary to set the parameters
+    /// of the transform method.
+    pub fn transform_params(&mut self) -> TransformParams<'_> {
+        self.insert(Name(b"TransformParams")).start()
+    }
+
+    /// Write the `/Data` attribute to reference the object the transform
+    /// method is applied to. Required for the
+    /// [`FieldMdp`](TransformMethod::FieldMdp) method, which must reference
+    /// the document catalog.
+    pub fn data(&mut self, data: Ref) -> &mut Self {
+        self.pair(Name(b"Data"), data);
+        self
+    }
+}
+
+deref!('a, SigRef<'a> => Dict<'a>, dict);
+
+/// How the parts of a document that are covered by a signature are
+/// determined.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TransformMethod {
+    /// Detect modifications to the whole document for a certification
+    /// signature.
+    DocMdp,
+    /// Grant the usage rights of a reader extension.
+    Ur3,
+    /// Detect modifications to a set of form fields.
+    FieldMdp,
+}
+
+impl TransformMethod {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::DocMdp => Name(b"DocMDP"),
+            Self::Ur3 => Name(b"UR3"),
+            Self::FieldMdp => Name(b"FieldMDP"),
+        }
+    }
+}
+
+/// Writer for a _transform parameters dictionary_. PDF 1.5+.
+///
+/// Which attributes apply depends on the [transform
+/// method](SigRef::transform_method) of the signature reference.
+///
+/// This struct is created by [`SigRef::transform_params`].
+pub struct TransformParams<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(TransformParams: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"TransformParams"));
+    Self { dict }
+});
+
+impl<'a> TransformParams<'a> {
+    /// Write the `/P` attribute to set which changes are permitted without
+    /// invalidating a certification signature. Only for the
+    /// [`DocMdp`](TransformMethod::DocMdp) method. Defaults to
+    /// [`FormFilling`](MdpPermissions::FormFilling).
+    pub fn permissions(&mut self, permissions: MdpPermissions) -> &mut Self {
+        self.pair(Name(b"P"), permissions.to_int());
+        self
+    }
+
+    /// Write the `/P` attribute to set whether the usage rights also apply
+    /// to documents that are modified by someone other than the author. Only
+    /// for the [`Ur3`](TransformMethod::Ur3) method.
+    pub fn restrict_usage_rights(&mut self, restrict: bool) -> &mut Self {
+        self.pair(Name(b"P"), restrict);
+        self
+    }
+
+    /// Write the `/Action` attribute to set which fields the
+    /// [`fields`](Self::fields) refer to. Required for the
+    /// [`FieldMdp`](TransformMethod::FieldMdp) method.
+    pub fn field_action(&mut self, action: FieldMdpAction) -> &mut Self {
+        self.pair(Name(b"Action"), action.to_name());
+        self
+    }
+
+    /// Write the `/Fields` attribute to set the fully qualified names of the
+    /// fields for the [`field_action`](Self::field_action). Only for the
+    /// [`FieldMdp`](TransformMethod::FieldMdp) method.
+    pub fn fields<'n>(
+        &mut self,
+        fields: impl IntoIterator<Item = TextStr<'n>>,
+    ) -> &mut Self {
+        self.insert(Name(b"Fields")).array().items(fields);
+        self
+    }
+
+    /// Write the `/V` attribute to set the version of the transform method,
+    /// which is `1.2` for [`DocMdp`](TransformMethod::DocMdp) and
+    /// [`FieldMdp`](TransformMethod::FieldMdp) and `2.2` for
+    /// [`Ur3`](TransformMethod::Ur3).
+    pub fn version(&mut self, major: u8, minor: u8) -> &mut Self {
+        self.pair(Name(b"V"), Name(format!("{}.{}", major, minor).as_bytes()));
+        self
+    }
+}
+
+deref!('a, TransformParams<'a> => Dict<'a>, dict);
+
+/// Which changes to a certified document are permitted.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum MdpPermissions {
+    /// No changes are permitted.
+    NoChanges,
+    /// Filling in forms, instantiating page templates, and signing are
+    /// permitted.
+    FormFilling,
+    /// Additionally to form filling, annotations may be created, modified,
+    /// and deleted.
+    FormFillingAndAnnotations,
+}
+
+impl MdpPermissions {
+    pub(crate) fn to_int(self) -> i32 {
+        match self {
+            Self::NoChanges => 1,
+            Self::FormFilling => 2,
+            Self::FormFillingAndAnnotations => 3,
+        }
+    }
+}
+
+/// Which fields are locked by a field modification detection signature.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FieldMdpAction {
+    /// All fields are locked.
+    All,
+    /// Only the listed fields are locked.
+    Include,
+    /// All fields but the listed ones are locked.
+    Exclude,
+}
+
+impl FieldMdpAction {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::All => Name(b"All"),
+            Self::Include => Name(b"Include"),
+            Self::Exclude => Name(b"Exclude"),
+        }
+    }
+}
+
+/// Writer for a _permissions dictionary_. PDF 1.5+.
+///
+/// This struct is created by [`Catalog::permissions`].
+pub struct Perms<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(Perms: |obj| Self { dict: obj.dict() });
+
+impl<'a> Perms<'a> {
+    /// Write the `/DocMDP` attribute to reference the [signature](Signature)
+    /// that certifies the document. Its [reference](Signature::reference)
+    /// must contain a [`DocMdp`](TransformMethod::DocMdp) transform.
+    pub fn doc_mdp(&mut self, signature: Ref) -> &mut Self {
+        self.pair(Name(b"DocMDP"), signature);
+        self
+    }
+
+    /// Write the `/UR3` attribute to reference the [signature](Signature)
+    /// that grants the usage rights of a reader extension. Its
+    /// [reference](Signature::reference) must contain a
+    /// [`Ur3`](TransformMethod::Ur3) transform.
+    pub fn ur3(&mut self, signature: Ref) -> &mut Self {
+        self.pair(Name(b"UR3"), signature);
+        self
+    }
+}
+
+deref!('a, Perms<'a> => Dict<'a>, dict);
@@ -1,4 +1,5 @@
-use crate::color::SeparationInfo;
+use crate::color::{OutputIntent, SeparationInfo};
+use crate::types::OpaqueColor;
 
 use super::*;
 
@@ -11,6 +12,9 @@ pub struct Catalog<'a> {
 
 writer!(Catalog: |obj| {
     let mut dict = obj.dict();
+    if let Some(record) = dict.recorder() {
+        record.catalog();
+    }
     dict.pair(Name(b"Type"), Name(b"Catalog"));
     Self { dict }
 });
@@ -65,6 +69,62 @@ impl<'a> Catalog<'a> {
         self
     }
 
+    /// Start writing the `/OpenAction` attribute to specify an action that
+    /// shall be performed when the document is opened. PDF 1.1+.
+    pub fn open_action(&mut self) -> Action<'_> {
+        self.insert(Name(b"OpenAction")).start()
+    }
+
+    /// Write the `/OpenAction` attribute to reference an [action](Action)
+    /// written with [`PdfWriter::action`] that shall be performed when the
+    /// document is opened, e.g. a [JavaScript action](PdfWriter::javascript).
+    /// PDF 1.1+.
+    pub fn open_action_ref(&mut self, action: Ref) -> &mut Self {
+        self.pair(Name(b"OpenAction"), action);
+        self
+    }
+
+    /// Start writing the `/OpenAction` attribute to specify a destination that
+    /// shall be displayed when the document is opened. PDF 1.1+.
+    pub fn open_action_destination(&mut self) -> Destination<'_> {
+        self.insert(Name(b"OpenAction")).start()
+    }
+
+    /// Write the `/Threads` attribute pointing to the document's [article
+    /// threads](Thread). PDF 1.1+.
+    pub fn threads(&mut self, threads: impl IntoIterator<Item = Ref>) -> &mut Self {
+        self.insert(Name(b"Threads")).array().items(threads);
+        self
+    }
+
+    /// Start writing the `/Collection` dictionary to turn the document into a
+    /// portable collection. PDF 1.7+.
+    pub fn collection(&mut self) -> Collection<'_> {
+        if let Some(record) = self.dict.recorder() {
+            record.requires("portable collections", (1, 7));
+        }
+        self.insert(Name(b"Collection")).start()
+    }
+
+    /// Start writing the `/Requirements` array to specify which features a
+    /// viewer must support to process the document correctly. PDF 1.7+.
+    pub fn requirements(&mut self) -> TypedArray<'_, Requirement<'_>> {
+        self.insert(Name(b"Requirements")).array().typed()
+    }
+
+    /// Write the `/AcroForm` attribute pointing to the document's [interactive
+    /// form dictionary](Form). PDF 1.2+.
+    pub fn form(&mut self, id: Ref) -> &mut Self {
+        self.pair(Name(b"AcroForm"), id);
+        self
+    }
+
+    /// Start writing the `/Perms` dictionary to reference signatures that
+    /// certify the document or grant additional usage rights. PDF 1.5+.
+    pub fn permissions(&mut self) -> Perms<'_> {
+        self.insert(Name(b"Perms")).start()
+    }
+
     /// Start writing the `/StructTreeRoot` attribute to specify the root of the
     /// document's structure tree. PDF 1.3+.
     pub fn struct_tree_root(&mut self) -> StructTreeRoot<'_> {
@@ -100,18 +160,41 @@ impl<'a> Catalog<'a> {
         self
     }
 
+    /// Write the `/NeedsRendering` attribute to specify whether the document
+    /// contains XFA forms that the viewer must render itself. PDF 1.7+,
+    /// deprecated in PDF 2.0.
+    pub fn needs_rendering(&mut self, needs: bool) -> &mut Self {
+        self.pair(Name(b"NeedsRendering"), needs);
+        self
+    }
+
     /// Start writing the `/Extensions` dictionary to specify which PDF
     /// extensions are in use in the document. PDF 1.5+.
     ///
     /// The dictionary maps a vendor name to an extension dictionary. The Adobe
     /// PDF extensions use the Name prefix `ADBE`.
-    pub fn extensions(&mut self) -> TypedDict<'_, DeveloperExtension> {
+    pub fn extensions(&mut self) -> TypedDict<'_, DeveloperExtension<'_>> {
         self.insert(Name(b"Extensions")).dict().typed()
     }
 
+    /// Start writing the `/PieceInfo` dictionary to store private data of the
+    /// applications that edited the document. PDF 1.4+.
+    pub fn piece_info(&mut self) -> PieceInfo<'_> {
+        self.insert(Name(b"PieceInfo")).start()
+    }
+
+    /// Start writing the `/SpiderInfo` dictionary to record how the content
+    /// of the document was captured from the web. PDF 1.3+.
+    pub fn spider_info(&mut self) -> WebCaptureInfo<'_> {
+        self.insert(Name(b"SpiderInfo")).start()
+    }
+
     /// Start writing the `/SeparationInfo` dictionary to specify which
     /// separation colors are in use on the page and how it relates to other
     /// pages in the document. PDF 1.3+.
+    ///
+    /// The specification only permits this dictionary in page objects. Prefer
+    /// [`Page::separation_info`] instead.
     pub fn separation_info(&mut self) -> SeparationInfo<'_> {
         self.insert(Name(b"SeparationInfo")).start()
     }
@@ -120,8 +203,19 @@ impl<'a> Catalog<'a> {
     /// destinations for the document. PDF 1.4+.
     ///
     /// Each entry in the array is an [output intent
-    /// dictionary.](writers::OutputIntent)
-    pub fn output_intents(&mut self) -> TypedArray<'_, Dict> {
+    /// dictionary.](writers::OutputIntent) Use
+    /// [`typed_output_intents`](Self::typed_output_intents) to write them with
+    /// the dedicated writer.
+    pub fn output_intents(&mut self) -> TypedArray<'_, Dict<'_>> {
+        self.insert(Name(b"OutputIntents")).array().typed()
+    }
+
+    /// Start writing the `/OutputIntents` array to specify the output
+    /// destinations for the document with [output intent
+    /// writers](OutputIntent). PDF 1.4+.
+    ///
+    /// Individual pages can override these with [`Page::output_intents`].
+    pub fn typed_output_intents(&mut self) -> TypedArray<'_, OutputIntent<'_>> {
         self.insert(Name(b"OutputIntents")).array().typed()
     }
 }
@@ -219,6 +313,178 @@ impl<'a> DeveloperExtension<'a> {
 
 deref!('a, DeveloperExtension<'a> => Dict<'a>, dict);
 
+/// Writer for a _requirement dictionary_. PDF 1.7+.
+///
+/// An array of this struct is created by [`Catalog::requirements`].
+pub struct Requirement<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(Requirement: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"Requirement"));
+    Self { dict }
+});
+
+impl<'a> Requirement<'a> {
+    /// Write the `/S` attribute to specify the required feature. Required.
+    pub fn kind(&mut self, kind: RequirementType) -> &mut Self {
+        self.pair(Name(b"S"), kind.to_name());
+        self
+    }
+
+    /// Write the `/V` attribute to specify the minimum version of the feature
+    /// that is required. PDF 2.0+.
+    pub fn version(&mut self, major: u8, minor: u8) -> &mut Self {
+        self.pair(Name(b"V"), Name(format!("{}.{}", major, minor).as_bytes()));
+        self
+    }
+
+    /// Start writing the `/RH` array to specify handlers that check whether
+    /// the requirement is met.
+    pub fn handlers(&mut self) -> TypedArray<'_, RequirementHandler<'_>> {
+        self.insert(Name(b"RH")).array().typed()
+    }
+}
+
+deref!('a, Requirement<'a> => Dict<'a>, dict);
+
+/// A feature a viewer can be required to support.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RequirementType {
+    /// Optional content that users can interact with. PDF 2.0+.
+    OptionalContentInteract,
+    /// Automatic state changes of optional content. PDF 2.0+.
+    OptionalContentAutoStates,
+    /// Interaction with interactive forms. PDF 2.0+.
+    AcroFormInteract,
+    /// Navigation through outlines, destinations and actions. PDF 2.0+.
+    Navigation,
+    /// Markup annotations. PDF 2.0+.
+    Markup,
+    /// Markup annotations on 3D artwork. PDF 2.0+.
+    Markup3D,
+    /// Multimedia content. PDF 2.0+.
+    Multimedia,
+    /// U3D 3D artwork. PDF 2.0+.
+    U3D,
+    /// PRC 3D artwork. PDF 2.0+.
+    PRC,
+    /// Execution of actions. PDF 2.0+.
+    Action,
+    /// Execution of JavaScript.
+    EnableJavaScripts,
+    /// Embedded files. PDF 2.0+.
+    Attachment,
+    /// Editing of embedded files. PDF 2.0+.
+    AttachmentEditing,
+    /// Portable collections. PDF 2.0+.
+    Collection,
+    /// Editing of portable collections. PDF 2.0+.
+    CollectionEditing,
+    /// Validation of digital signatures. PDF 2.0+.
+    DigSigValidation,
+    /// Creation of digital signatures. PDF 2.0+.
+    DigSig,
+    /// Certification signatures with modification detection. PDF 2.0+.
+    DigSigMDP,
+    /// Rich media annotations. PDF 2.0+.
+    RichMedia,
+    /// Two-dimensional geospatial measures. PDF 2.0+.
+    Geospatial2D,
+    /// Three-dimensional geospatial measures. PDF 2.0+.
+    Geospatial3D,
+    /// Interaction with document parts. PDF 2.0+.
+    DPartInteract,
+    /// Simulation of separations. PDF 2.0+.
+    SeparationSimulation,
+    /// Page transitions. PDF 2.0+.
+    Transitions,
+    /// Decryption of the document. PDF 2.0+.
+    Encryption,
+}
+
+impl RequirementType {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::OptionalContentInteract => Name(b"OCInteract"),
+            Self::OptionalContentAutoStates => Name(b"OCAutoStates"),
+            Self::AcroFormInteract => Name(b"AcroFormInteract"),
+            Self::Navigation => Name(b"Navigation"),
+            Self::Markup => Name(b"Markup"),
+            Self::Markup3D => Name(b"3DMarkup"),
+            Self::Multimedia => Name(b"Multimedia"),
+            Self::U3D => Name(b"U3D"),
+            Self::PRC => Name(b"PRC"),
+            Self::Action => Name(b"Action"),
+            Self::EnableJavaScripts => Name(b"EnableJavaScripts"),
+            Self::Attachment => Name(b"Attachment"),
+            Self::AttachmentEditing => Name(b"AttachmentEditing"),
+            Self::Collection => Name(b"Collection"),
+            Self::CollectionEditing => Name(b"CollectionEditing"),
+            Self::DigSigValidation => Name(b"DigSigValidation"),
+            Self::DigSig => Name(b"DigSig"),
+            Self::DigSigMDP => Name(b"DigSigMDP"),
+            Self::RichMedia => Name(b"RichMedia"),
+            Self::Geospatial2D => Name(b"Geospatial2D"),
+            Self::Geospatial3D => Name(b"Geospatial3D"),
+            Self::DPartInteract => Name(b"DPartInteract"),
+            Self::SeparationSimulation => Name(b"SeparationSimulation"),
+            Self::Transitions => Name(b"Transitions"),
+            Self::Encryption => Name(b"Encryption"),
+        }
+    }
+}
+
+/// Writer for a _requirement handler dictionary_. PDF 1.7+.
+///
+/// An array of this struct is created by [`Requirement::handlers`].
+pub struct RequirementHandler<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(RequirementHandler: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"ReqHandler"));
+    Self { dict }
+});
+
+impl<'a> RequirementHandler<'a> {
+    /// Write the `/S` attribute to specify the type of the handler. Required.
+    pub fn kind(&mut self, kind: RequirementHandlerType) -> &mut Self {
+        self.pair(Name(b"S"), kind.to_name());
+        self
+    }
+
+    /// Write the `/Script` attribute to specify the name of a document-level
+    /// JavaScript that checks whether the requirement is met.
+    pub fn script(&mut self, name: TextStr) -> &mut Self {
+        self.pair(Name(b"Script"), name);
+        self
+    }
+}
+
+deref!('a, RequirementHandler<'a> => Dict<'a>, dict);
+
+/// How a [requirement handler](RequirementHandler) checks a requirement.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RequirementHandlerType {
+    /// Run a JavaScript that needs to be set with
+    /// [`RequirementHandler::script`].
+    JavaScript,
+    /// Do nothing.
+    NoOp,
+}
+
+impl RequirementHandlerType {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::JavaScript => Name(b"JS"),
+            Self::NoOp => Name(b"NoOp"),
+        }
+    }
+}
+
 /// Writer for a _viewer preference dictionary_.
 ///
 /// This struct is created by [`Catalog::viewer_preferences`].
@@ -316,10 +582,25 @@ impl<'a> StructTreeRoot<'a> {
     /// Start writing the `/ParentTree` attribute to maps structure elements to
     /// the content items they belong to. Required if any structure elements
     /// contain content items.
+    ///
+    /// The keys are the values of the `/StructParents` attributes of pages and
+    /// XObjects and the `/StructParent` attributes of annotations and
+    /// XObjects. The former map to arrays of structure elements indexed by
+    /// marked-content identifier, see
+    /// [`insert_refs`](NumberTreeEntries::insert_refs), while the latter map
+    /// directly to a single structure element.
     pub fn parent_tree(&mut self) -> NumberTree<'_, Ref> {
         self.dict.insert(Name(b"ParentTree")).start()
     }
 
+    /// Write the `/ParentTree` attribute to reference a
+    /// [number tree](PdfWriter::number_tree) that is written as an indirect
+    /// object. See [`parent_tree`](Self::parent_tree) for its contents.
+    pub fn parent_tree_ref(&mut self, tree: Ref) -> &mut Self {
+        self.dict.pair(Name(b"ParentTree"), tree);
+        self
+    }
+
     /// Write the `/ParentTreeNextKey` attribute to specify the next available key
     /// for the `/ParentTree` dictionary.
     pub fn parent_tree_next_key(&mut self, key: i32) -> &mut Self {
@@ -411,16 +692,34 @@ impl<'a> StructElement<'a> {
 
     /// Start writing the `/A` attribute to specify the attributes of this
     /// structure element.
-    pub fn attributes(&mut self) -> TypedArray<'_, Attributes> {
+    pub fn attributes(&mut self) -> TypedArray<'_, Attributes<'_>> {
         self.dict.insert(Name(b"A")).array().typed()
     }
 
+    /// Start writing the `/A` attribute as a single attribute dictionary.
+    pub fn single_attributes(&mut self) -> Attributes<'_> {
+        self.dict.insert(Name(b"A")).start()
+    }
+
+    /// Start writing the `/A` attribute as an array of attribute objects, each
+    /// optionally followed by its revision number.
+    pub fn attributes_with_revisions(&mut self) -> StructAttributes<'_> {
+        self.dict.insert(Name(b"A")).start()
+    }
+
     /// Start writing the `/C` attribute to associate the structure element with
     /// an attribute class.
-    pub fn attribute_class(&mut self) -> TypedArray<'_, Name> {
+    pub fn attribute_class(&mut self) -> TypedArray<'_, Name<'_>> {
         self.dict.insert(Name(b"C")).array().typed()
     }
 
+    /// Write the `/C` attribute to associate the structure element with a
+    /// single attribute class.
+    pub fn single_attribute_class(&mut self, class: Name) -> &mut Self {
+        self.dict.pair(Name(b"C"), class);
+        self
+    }
+
     /// Write the `/R` attribute to specify the revision number, starting at 0.
     pub fn revision(&mut self, revision: i32) -> &mut Self {
         self.dict.pair(Name(b"R"), revision);
@@ -459,6 +758,20 @@ impl<'a> StructElement<'a> {
         self.dict.pair(Name(b"ActualText"), actual_text);
         self
     }
+
+    /// Write the `/AF` attribute to reference [file specifications](FileSpec)
+    /// of files associated with the structure element, e.g. the source data
+    /// of a figure. PDF 2.0+
+    pub fn associated_files(
+        &mut self,
+        files: impl IntoIterator<Item = Ref>,
+    ) -> &mut Self {
+        if let Some(record) = self.dict.recorder() {
+            record.requires("associated files", (2, 0));
+        }
+        self.dict.insert(Name(b"AF")).array().items(files);
+        self
+    }
 }
 
 deref!('a, StructElement<'a> => Dict<'a>, dict);
@@ -587,6 +900,10 @@ writer!(RoleMap: |obj| Self { dict: obj.dict().typed() });
 
 impl<'a> RoleMap<'a> {
     /// Write an entry mapping a custom name to a pre-defined role.
+    ///
+    /// The target is always one of the standard structure types. Custom names
+    /// must not coincide with the name of a standard type, as viewers would
+    /// not apply the mapping in that case.
     pub fn insert(&mut self, name: Name, role: StructRole) -> &mut Self {
         self.dict.pair(name, role.to_name());
         self
@@ -611,9 +928,15 @@ impl<'a> ClassMap<'a> {
     }
 
     /// Start writing an array of attribute dictionaries for a class name.
-    pub fn multiple(&mut self, name: Name) -> TypedArray<'_, Attributes> {
+    pub fn multiple(&mut self, name: Name) -> TypedArray<'_, Attributes<'_>> {
         self.dict.insert(name).array().typed()
     }
+
+    /// Start writing an array of attribute objects for a class name, each
+    /// optionally followed by its revision number.
+    pub fn multiple_with_revisions(&mut self, name: Name) -> StructAttributes<'_> {
+        self.dict.insert(name).start()
+    }
 }
 
 deref!('a, ClassMap<'a> => Dict<'a>, dict);
@@ -972,6 +1295,85 @@ impl<'a> DocumentInfo<'a> {
 
 deref!('a, DocumentInfo<'a> => Dict<'a>, dict);
 
+/// Writer for additional entries in the _file trailer dictionary_.
+///
+/// The `/Size` attribute as well as the `/Root` and `/Info` attributes for a
+/// [catalog](PdfWriter::catalog) and [document
+/// information](PdfWriter::document_info) written through the [`PdfWriter`]
+/// are always written automatically.
+///
+/// This struct is created by [`PdfWriter::finish_with`] and
+/// [`PdfWriter::into_buf_with`].
+pub struct Trailer<'a> {
+    dict: Dict<'a>,
+    pub(crate) root: Option<Ref>,
+    pub(crate) refs: Vec<Ref>,
+    pub(crate) prev: bool,
+}
+
+impl<'a> Trailer<'a> {
+    pub(crate) fn start_with_dict(dict: Dict<'a>) -> Self {
+        Self { dict, root: None, refs: vec![], prev: false }
+    }
+
+    /// Write the `/Root` attribute to reference the document catalog. Only
+    /// necessary if the catalog was not written with [`PdfWriter::catalog`].
+    pub fn root(&mut self, catalog: Ref) -> &mut Self {
+        self.root = Some(catalog);
+        self.refs.push(catalog);
+        self.pair(Name(b"Root"), catalog);
+        self
+    }
+
+    /// Write the `/Info` attribute to reference the document information
+    /// dictionary. Only necessary if the dictionary was not written with
+    /// [`PdfWriter::document_info`].
+    pub fn info(&mut self, info: Ref) -> &mut Self {
+        self.refs.push(info);
+        self.pair(Name(b"Info"), info);
+        self
+    }
+
+    /// Write the `/ID` attribute to set the file identifier. The first string
+    /// identifies the original file and the second one this version of it.
+    /// PDF 1.1+.
+    pub fn id(&mut self, id: [Str; 2]) -> &mut Self {
+        self.insert(Name(b"ID")).array().items(id);
+        self
+    }
+
+    /// Write the `/Encrypt` attribute to reference the encryption dictionary
+    /// of the document. PDF 1.1+.
+    pub fn encrypt(&mut self, encrypt: Ref) -> &mut Self {
+        self.refs.push(encrypt);
+        self.pair(Name(b"Encrypt"), encrypt);
+        self
+    }
+
+    /// Write the `/Prev` attribute to set the byte offset of the previous
+    /// cross-reference section for an incremental update.
+    pub fn prev(&mut self, offset: i32) -> &mut Self {
+        self.prev = true;
+        self.pair(Name(b"Prev"), offset);
+        self
+    }
+
+    /// Write the `/XRefStm` attribute to set the byte offset of a
+    /// cross-reference stream in a hybrid-reference file. PDF 1.5+.
+    ///
+    /// This is written automatically for a [hybrid-reference
+    /// file](PdfWriter::set_hybrid_xref). Only use this if you write the
+    /// cross-reference stream by hand, for example with
+    /// [`PdfWriter::stream`], and query its offset with
+    /// [`PdfWriter::offset`].
+    pub fn xref_stm(&mut self, offset: i32) -> &mut Self {
+        self.pair(Name(b"XRefStm"), offset);
+        self
+    }
+}
+
+deref!('a, Trailer<'a> => Dict<'a>, dict);
+
 /// Whether a document has been adjusted with traps.
 ///
 /// Those account for colorant misregistration during the printing process.
@@ -1004,6 +1406,9 @@ pub struct Pages<'a> {
 
 writer!(Pages: |obj| {
     let mut dict = obj.dict();
+    if let Some(record) = dict.recorder() {
+        record.node(false);
+    }
     dict.pair(Name(b"Type"), Name(b"Pages"));
     Self { dict }
 });
@@ -1011,6 +1416,9 @@ writer!(Pages: |obj| {
 impl<'a> Pages<'a> {
     /// Write the `/Parent` attribute. Required except in root node.
     pub fn parent(&mut self, parent: Ref) -> &mut Self {
+        if let Some(record) = self.dict.recorder() {
+            record.parent(parent);
+        }
         self.pair(Name(b"Parent"), parent);
         self
     }
@@ -1018,7 +1426,18 @@ impl<'a> Pages<'a> {
     /// Write the `/Kids` attributes, listing the immediate children of this
     /// node in the page tree. Required.
     pub fn kids(&mut self, kids: impl IntoIterator<Item = Ref>) -> &mut Self {
-        self.insert(Name(b"Kids")).array().items(kids);
+        if let Some(record) = self.dict.recorder() {
+            record.kids();
+        }
+
+        let mut array = self.dict.insert(Name(b"Kids")).array();
+        for kid in kids {
+            array.item(kid);
+            if let Some(record) = array.recorder() {
+                record.kid(kid);
+            }
+        }
+        drop(array);
         self
     }
 
@@ -1026,13 +1445,16 @@ impl<'a> Pages<'a> {
     /// in the page tree has. This may be different to the length of `/Kids`
     /// when the tree has multiple layers. Required.
     pub fn count(&mut self, count: i32) -> &mut Self {
+        if let Some(record) = self.dict.recorder() {
+            record.count(count);
+        }
         self.pair(Name(b"Count"), count);
         self
     }
 
     /// Write the `/MediaBox` attribute.
     pub fn media_box(&mut self, rect: Rect) -> &mut Self {
-        self.pair(Name(b"MediaBox"), rect);
+        self.pair(Name(b"MediaBox"), rect.normalized());
         self
     }
 
@@ -1053,13 +1475,19 @@ pub struct Page<'a> {
 
 writer!(Page: |obj| {
     let mut dict = obj.dict();
+    if let Some(record) = dict.recorder() {
+        record.node(true);
+    }
     dict.pair(Name(b"Type"), Name(b"Page"));
     Self { dict }
 });
 
 impl<'a> Page<'a> {
-    /// Write the `/Parent` attribute. Required.
+    /// Write the `/Parent` attribute. Required, except for template pages.
     pub fn parent(&mut self, parent: Ref) -> &mut Self {
+        if let Some(record) = self.dict.recorder() {
+            record.parent(parent);
+        }
         self.pair(Name(b"Parent"), parent);
         self
     }
@@ -1070,17 +1498,26 @@ impl<'a> Page<'a> {
         self
     }
 
+    /// Start writing the `/PieceInfo` dictionary to store private data of the
+    /// applications that edited the page. PDF 1.3+.
+    ///
+    /// The [`last_modified`](Self::last_modified) attribute is required if
+    /// this is present.
+    pub fn piece_info(&mut self) -> PieceInfo<'_> {
+        self.insert(Name(b"PieceInfo")).start()
+    }
+
     /// Write the `/MediaBox` attribute. This is the size of the physical medium
     /// the page gets printed onto.
     pub fn media_box(&mut self, rect: Rect) -> &mut Self {
-        self.pair(Name(b"MediaBox"), rect);
+        self.pair(Name(b"MediaBox"), rect.normalized());
         self
     }
 
     /// Write the `/CropBox` attribute. This is the size of the area within
     /// which content is visible.
     pub fn crop_box(&mut self, rect: Rect) -> &mut Self {
-        self.pair(Name(b"CropBox"), rect);
+        self.pair(Name(b"CropBox"), rect.normalized());
         self
     }
 
@@ -1088,24 +1525,36 @@ impl<'a> Page<'a> {
     /// which content is visible in a print production environment. Most
     /// production-aiding marks should be outside of this box. PDF 1.3+.
     pub fn bleed_box(&mut self, rect: Rect) -> &mut Self {
-        self.pair(Name(b"BleedBox"), rect);
+        self.pair(Name(b"BleedBox"), rect.normalized());
         self
     }
 
     /// Write the `/TrimBox` attribute. This is the size of the produced
     /// document after trimming is applied. PDF 1.3+.
     pub fn trim_box(&mut self, rect: Rect) -> &mut Self {
-        self.pair(Name(b"TrimBox"), rect);
+        self.pair(Name(b"TrimBox"), rect.normalized());
         self
     }
 
     /// Write the `/ArtBox` attribute. This is the area that another program
     /// importing this file should use. PDF 1.3+.
     pub fn art_box(&mut self, rect: Rect) -> &mut Self {
-        self.pair(Name(b"ArtBox"), rect);
+        self.pair(Name(b"ArtBox"), rect.normalized());
         self
     }
 
+    /// Start writing the `/VP` array to set regions of the page with their
+    /// own measurement coordinate systems. PDF 1.6+.
+    pub fn viewports(&mut self) -> TypedArray<'_, Viewport<'_>> {
+        self.insert(Name(b"VP")).array().typed()
+    }
+
+    /// Start writing the `/BoxColorInfo` dictionary to set how viewers display
+    /// guides for the page boundaries. PDF 1.4+.
+    pub fn box_color_info(&mut self) -> BoxColorInfo<'_> {
+        self.insert(Name(b"BoxColorInfo")).start()
+    }
+
     /// Start writing the `/Resources` dictionary.
     pub fn resources(&mut self) -> Resources<'_> {
         self.insert(Name(b"Resources")).start()
@@ -1161,20 +1610,71 @@ impl<'a> Page<'a> {
         self.insert(Name(b"Trans")).start()
     }
 
+    /// Write the `/B` attribute pointing to the [beads](Bead) of article
+    /// threads that appear on this page. PDF 1.1+.
+    ///
+    /// The beads shall be listed in drawing order.
+    pub fn beads(&mut self, beads: impl IntoIterator<Item = Ref>) -> &mut Self {
+        self.insert(Name(b"B")).array().items(beads);
+        self
+    }
+
+    /// Start writing the `/SeparationInfo` dictionary to specify which
+    /// separation this page represents in a preseparated document. PDF 1.3+.
+    pub fn separation_info(&mut self) -> SeparationInfo<'_> {
+        self.insert(Name(b"SeparationInfo")).start()
+    }
+
+    /// Start writing the `/OutputIntents` array to specify the output
+    /// destinations for this page, overriding the ones of the [document
+    /// catalog](Catalog::typed_output_intents). PDF 2.0+.
+    pub fn output_intents(&mut self) -> TypedArray<'_, OutputIntent<'_>> {
+        if let Some(record) = self.dict.recorder() {
+            record.requires("page output intents", (2, 0));
+        }
+        self.insert(Name(b"OutputIntents")).array().typed()
+    }
+
     /// Start writing the `/Annots` (annotations) array.'
-    pub fn annotations(&mut self) -> TypedArray<'_, Annotation> {
+    pub fn annotations(&mut self) -> TypedArray<'_, Annotation<'_>> {
         self.insert(Name(b"Annots")).array().typed()
     }
 
+    /// Write the `/Annots` (annotations) array as references to annotations
+    /// written with [`PdfWriter::annotation`].
+    ///
+    /// Annotations must be indirect objects if they are targeted by actions,
+    /// e.g. a screen annotation that plays a rendition.
+    pub fn annotation_refs(
+        &mut self,
+        annotations: impl IntoIterator<Item = Ref>,
+    ) -> &mut Self {
+        self.insert(Name(b"Annots")).array().items(annotations);
+        self
+    }
+
     /// Write the `/StructParents` attribute to indicate the [structure tree
-    /// elements][StructElement] the contents of this XObject may belong to. PDF 1.3+.
+    /// elements][StructElement] the contents of this page may belong to. The
+    /// key must map to an array of structure elements in the
+    /// [parent tree](StructTreeRoot::parent_tree). PDF 1.3+.
     pub fn struct_parents(&mut self, key: i32) -> &mut Self {
         self.pair(Name(b"StructParents"), key);
         self
     }
 
+    /// Write the `/Lang` attribute to specify the natural language of the
+    /// page's content as a RFC 3066 language tag, overriding the language of
+    /// the document.
+    pub fn lang(&mut self, lang: TextStr) -> &mut Self {
+        self.pair(Name(b"Lang"), lang);
+        self
+    }
+
     /// Write the `/Tabs` attribute. This specifies the order in which the
     /// annotations should be focused by hitting tab. PDF 1.5+.
+    ///
+    /// PDF/UA requires [`TabOrder::StructureOrder`] on all pages with
+    /// annotations.
     pub fn tab_order(&mut self, order: TabOrder) -> &mut Self {
         self.pair(Name(b"Tabs"), order.to_name());
         self
@@ -1199,6 +1699,142 @@ impl<'a> Page<'a> {
 
 deref!('a, Page<'a> => Dict<'a>, dict);
 
+/// Writer for a _template page dictionary_. PDF 1.3+.
+///
+/// Template pages are not part of the page tree, so they cannot inherit
+/// anything from a page tree node. The `/MediaBox` is always written when the
+/// template is started and an empty `/Resources` dictionary is written when
+/// the template is finished if no resources were written. In debug builds,
+/// writing a `/Parent` panics.
+///
+/// This struct is created by [`PdfWriter::template_page`].
+pub struct TemplatePage<'a> {
+    page: Page<'a>,
+}
+
+impl<'a> TemplatePage<'a> {
+    /// Create a new template page writer. The page is not recorded as part of
+    /// the page tree.
+    pub(crate) fn start(obj: Obj<'a>, media_box: Rect) -> Self {
+        let mut dict = obj.dict();
+        dict.watch(&[b"Parent", b"Resources"]);
+        dict.pair(Name(b"Type"), Name(b"Page"));
+        let mut page = Page { dict };
+        page.media_box(media_box);
+        Self { page }
+    }
+}
+
+impl Drop for TemplatePage<'_> {
+    fn drop(&mut self) {
+        debug_assert!(
+            !self.page.written(b"Parent"),
+            "template pages must not have a parent",
+        );
+
+        if !self.page.written(b"Resources") {
+            self.page.resources();
+        }
+    }
+}
+
+deref!('a, TemplatePage<'a> => Page<'a>, page);
+
+/// Writer for a _box color information dictionary_. PDF 1.4+.
+///
+/// This struct is created by [`Page::box_color_info`].
+pub struct BoxColorInfo<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(BoxColorInfo: |obj| Self { dict: obj.dict() });
+
+impl<'a> BoxColorInfo<'a> {
+    /// Start writing the `/CropBox` dictionary to set the guide style for the
+    /// [crop box](Page::crop_box).
+    pub fn crop_box(&mut self) -> BoxStyle<'_> {
+        self.insert(Name(b"CropBox")).start()
+    }
+
+    /// Start writing the `/BleedBox` dictionary to set the guide style for the
+    /// [bleed box](Page::bleed_box).
+    pub fn bleed_box(&mut self) -> BoxStyle<'_> {
+        self.insert(Name(b"BleedBox")).start()
+    }
+
+    /// Start writing the `/TrimBox` dictionary to set the guide style for the
+    /// [trim box](Page::trim_box).
+    pub fn trim_box(&mut self) -> BoxStyle<'_> {
+        self.insert(Name(b"TrimBox")).start()
+    }
+
+    /// Start writing the `/ArtBox` dictionary to set the guide style for the
+    /// [art box](Page::art_box).
+    pub fn art_box(&mut self) -> BoxStyle<'_> {
+        self.insert(Name(b"ArtBox")).start()
+    }
+}
+
+deref!('a, BoxColorInfo<'a> => Dict<'a>, dict);
+
+/// Writer for a _box style dictionary_. PDF 1.4+.
+///
+/// This struct is created by [`BoxColorInfo::crop_box`],
+/// [`BoxColorInfo::bleed_box`], [`BoxColorInfo::trim_box`], and
+/// [`BoxColorInfo::art_box`].
+pub struct BoxStyle<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(BoxStyle: |obj| Self { dict: obj.dict() });
+
+impl<'a> BoxStyle<'a> {
+    /// Write the `/C` attribute to set the color of the guide in the
+    /// `DeviceRGB` color space.
+    pub fn color(&mut self, r: f32, g: f32, b: f32) -> &mut Self {
+        self.insert(Name(b"C")).array().items([r, g, b]);
+        self
+    }
+
+    /// Write the `/W` attribute to set the width of the guide in points.
+    pub fn width(&mut self, width: f32) -> &mut Self {
+        self.pair(Name(b"W"), width);
+        self
+    }
+
+    /// Write the `/S` attribute to set whether the guide is solid or dashed.
+    pub fn style(&mut self, style: GuideStyle) -> &mut Self {
+        self.pair(Name(b"S"), style.to_name());
+        self
+    }
+
+    /// Write the `/D` attribute to set the dash pattern of a dashed guide.
+    pub fn dashes(&mut self, dash_pattern: impl IntoIterator<Item = f32>) -> &mut Self {
+        self.insert(Name(b"D")).array().items(dash_pattern);
+        self
+    }
+}
+
+deref!('a, BoxStyle<'a> => Dict<'a>, dict);
+
+/// The line style of a page boundary guide.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum GuideStyle {
+    /// A solid line.
+    Solid,
+    /// A dashed line.
+    Dashed,
+}
+
+impl GuideStyle {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::Solid => Name(b"S"),
+            Self::Dashed => Name(b"D"),
+        }
+    }
+}
+
 /// Writer for an _outline dictionary_.
 ///
 /// This struct is created by [`PdfWriter::outline`].
@@ -1311,11 +1947,19 @@ impl<'a> OutlineItem<'a> {
         self
     }
 
+    /// Write the `/C` attribute. This sets the color in which the outline
+    /// item's title should be rendered. PDF 1.4+.
+    ///
+    /// Gray and CMYK colors are converted to RGB.
+    pub fn color(&mut self, color: OpaqueColor) -> &mut Self {
+        self.insert(Name(b"C")).array().items(color.to_rgb());
+        self
+    }
+
     /// Write the `/C` attribute using an RGB color. This sets the color in
     /// which the outline item's title should be rendered. PDF 1.4+.
     pub fn color_rgb(&mut self, r: f32, g: f32, b: f32) -> &mut Self {
-        self.insert(Name(b"C")).array().items([r, g, b]);
-        self
+        self.color(OpaqueColor::Rgb(r, g, b))
     }
 
     /// Write the `/F` attribute. PDF 1.4+.
@@ -1327,6 +1971,97 @@ impl<'a> OutlineItem<'a> {
 
 deref!('a, OutlineItem<'a> => Dict<'a>, dict);
 
+/// Writer for an _article thread dictionary_. PDF 1.1+.
+///
+/// An article thread connects a sequence of [beads](Bead), i.e. rectangular
+/// regions on pages, that make up one logical flow of content.
+///
+/// This struct is created by [`PdfWriter::thread`].
+pub struct Thread<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(Thread: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"Thread"));
+    Self { dict }
+});
+
+impl<'a> Thread<'a> {
+    /// Write the `/F` attribute which points to the first [bead](Bead) of the
+    /// thread. Required.
+    pub fn first(&mut self, bead: Ref) -> &mut Self {
+        self.pair(Name(b"F"), bead);
+        self
+    }
+
+    /// Start writing the `/I` dictionary to provide information about the
+    /// thread (like its title and author).
+    pub fn info(&mut self) -> DocumentInfo<'_> {
+        self.insert(Name(b"I")).start()
+    }
+}
+
+deref!('a, Thread<'a> => Dict<'a>, dict);
+
+/// Writer for a _bead dictionary_. PDF 1.1+.
+///
+/// The beads of a [thread](Thread) form a ring: The `/N` attribute of the last
+/// bead points back to the first bead and the `/V` attribute of the first bead
+/// points to the last one. For a thread with a single bead, both point to the
+/// bead itself.
+///
+/// This struct is created by [`PdfWriter::bead`].
+pub struct Bead<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(Bead: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"Bead"));
+    Self { dict }
+});
+
+impl<'a> Bead<'a> {
+    /// Write the `/T` attribute which points to the thread this bead belongs
+    /// to. Required for the first bead of a thread, not permitted for all
+    /// others.
+    pub fn thread(&mut self, thread: Ref) -> &mut Self {
+        self.pair(Name(b"T"), thread);
+        self
+    }
+
+    /// Write the `/N` attribute which points to the next bead in the thread.
+    /// Required. For the last bead, this is the first bead.
+    pub fn next(&mut self, bead: Ref) -> &mut Self {
+        self.pair(Name(b"N"), bead);
+        self
+    }
+
+    /// Write the `/V` attribute which points to the previous bead in the
+    /// thread. Required. For the first bead, this is the last bead.
+    pub fn prev(&mut self, bead: Ref) -> &mut Self {
+        self.pair(Name(b"V"), bead);
+        self
+    }
+
+    /// Write the `/P` attribute which points to the page the bead appears on.
+    /// Required.
+    pub fn page(&mut self, page: Ref) -> &mut Self {
+        self.pair(Name(b"P"), page);
+        self
+    }
+
+    /// Write the `/R` attribute to set the location of the bead on the page in
+    /// default user space units. Required.
+    pub fn rect(&mut self, rect: Rect) -> &mut Self {
+        self.pair(Name(b"R"), rect);
+        self
+    }
+}
+
+deref!('a, Bead<'a> => Dict<'a>, dict);
+
 bitflags::bitflags! {
     /// Bitflags describing the appearance of an outline item.
     pub struct OutlineItemFlags: u32 {
@@ -1350,6 +2085,10 @@ writer!(Names: |obj| Self { dict: obj.dict() });
 impl Names<'_> {
     /// Start writing the `/Dests` attribute to provide associations for
     /// [destinations](Destination).
+    ///
+    /// The values point to destination arrays written with
+    /// [`PdfWriter::destination`] or are destination arrays written with
+    /// [`NameTreeEntries::insert_destination`].
     pub fn destinations(&mut self) -> NameTree<'_, Ref> {
         self.dict.insert(Name(b"Dests")).start()
     }
@@ -1362,17 +2101,26 @@ impl Names<'_> {
 
     /// Start writing the `/JavaScript` attribute to provide associations for
     /// JavaScript actions. PDF 1.3+.
+    ///
+    /// The values point to actions written with [`PdfWriter::javascript`].
+    /// The scripts are run when the document is opened.
     pub fn javascript(&mut self) -> NameTree<'_, Ref> {
         self.dict.insert(Name(b"JavaScript")).start()
     }
 
-    /// Start writing the `/Pages` attribute to name [pages](Page). PDF 1.3+.
+    /// Start writing the `/Pages` attribute to name [pages](Page) in the page
+    /// tree. PDF 1.3+.
+    ///
+    /// Named pages can be referred to by scripts, e.g. to stamp them.
     pub fn pages(&mut self) -> NameTree<'_, Ref> {
         self.dict.insert(Name(b"Pages")).start()
     }
 
-    /// Start writing the `/Template` attribute to name [pages](Pages) outside
+    /// Start writing the `/Templates` attribute to name [pages](Page) outside
     /// of the page tree as templates for interactive forms. PDF 1.3+.
+    ///
+    /// The values point to template pages written with
+    /// [`PdfWriter::template_page`]. Scripts can spawn new pages from them.
     pub fn templates(&mut self) -> NameTree<'_, Ref> {
         self.dict.insert(Name(b"Templates")).start()
     }
@@ -1414,7 +2162,8 @@ deref!('a, Names<'a> => Dict<'a>, dict);
 ///
 /// A dictionary mapping to this struct is created by
 /// [`PdfWriter::destinations`]. This struct is also created by
-/// [`Action::destination`].
+/// [`PdfWriter::destination`], [`Action::destination`], and
+/// [`Action::structure_destination`].
 pub struct Destination<'a> {
     array: Array<'a>,
 }
@@ -1428,6 +2177,17 @@ impl<'a> Destination<'a> {
         self
     }
 
+    /// The target [structure element](StructElement) of a structure
+    /// destination. Required instead of the [page](Self::page) for structure
+    /// destinations. PDF 2.0+.
+    ///
+    /// The coordinates of the command are relative to the bounding box of the
+    /// element's content on the page it starts on.
+    pub fn struct_element(mut self, element: Ref) -> Self {
+        self.item(element);
+        self
+    }
+
     /// Write the `/XYZ` command which skips to the specified coordinated.
     pub fn xyz(mut self, left: f32, top: f32, zoom: Option<f32>) {
         self.item(Name(b"XYZ"));
@@ -1495,6 +2255,12 @@ pub enum TabOrder {
     ColumnOrder,
     /// Navigate the annotations in the order they appear in the structure tree.
     StructureOrder,
+    /// Navigate the annotations in the order of the page's `/Annots` array.
+    /// PDF 2.0+.
+    AnnotationsOrder,
+    /// Navigate the widget annotations in the order of the page's `/Annots`
+    /// array first, followed by all other annotations. PDF 2.0+.
+    WidgetOrder,
 }
 
 impl TabOrder {
@@ -1503,6 +2269,8 @@ impl TabOrder {
             Self::RowOrder => Name(b"R"),
             Self::ColumnOrder => Name(b"C"),
             Self::StructureOrder => Name(b"S"),
+            Self::AnnotationsOrder => Name(b"A"),
+            Self::WidgetOrder => Name(b"W"),
         }
     }
 }
@@ -1524,3 +2292,64 @@ impl<'a> Metadata<'a> {
 }
 
 deref!('a, Metadata<'a> => Stream<'a>, stream);
+
+/// Writer for a _page-piece dictionary_. PDF 1.3+.
+///
+/// The page-piece dictionary holds private data of the applications that
+/// created or edited a page, form XObject, or document. Each entry is keyed by
+/// the name of an application.
+///
+/// This struct is created by [`Catalog::piece_info`], [`Page::piece_info`],
+/// and [`FormXObject::piece_info`].
+pub struct PieceInfo<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(PieceInfo: |obj| Self { dict: obj.dict() });
+
+impl<'a> PieceInfo<'a> {
+    /// Start writing the data dictionary of the application with the given
+    /// name. Second-class names should be used to avoid conflicts between
+    /// applications.
+    ///
+    /// This also writes the required `/LastModified` attribute of the data
+    /// dictionary with the date at which the application last modified its
+    /// data. If this is more recent than the `/LastModified` date of the
+    /// page, form XObject, or document, the private data may be out of sync
+    /// with the content.
+    pub fn application(
+        &mut self,
+        name: Name,
+        last_modified: Date,
+    ) -> ApplicationData<'_> {
+        let mut data: ApplicationData = self.insert(name).start();
+        data.pair(Name(b"LastModified"), last_modified);
+        data
+    }
+}
+
+deref!('a, PieceInfo<'a> => Dict<'a>, dict);
+
+/// Writer for an application's _data dictionary_ in a page-piece dictionary.
+/// PDF 1.3+.
+///
+/// This struct is created by [`PieceInfo::application`].
+pub struct ApplicationData<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(ApplicationData: |obj| Self { dict: obj.dict() });
+
+impl<'a> ApplicationData<'a> {
+    /// Start writing the `/Private` attribute with arbitrary private data of
+    /// the application, e.g. a dictionary or a reference to a stream.
+    ///
+    /// The returned object writer can be used to write any primitive or
+    /// container, so the data round-trips in whatever structure the
+    /// application defines.
+    pub fn private(&mut self) -> Obj<'_> {
+        self.insert(Name(b"Private"))
+    }
+}
+
+deref!('a, ApplicationData<'a> => Dict<'a>, dict);
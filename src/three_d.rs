@@ -0,0 +1,471 @@
+use super::*;
+
+/// Writer for a _3D stream_. PDF 1.6+.
+///
+/// The stream data contains the 3D artwork in the U3D or PRC format. It is
+/// written as-is, so you must make sure that it matches the
+/// [`subtype`](Self::subtype).
+///
+/// This struct is created by [`PdfWriter::stream_3d`].
+pub struct Stream3D<'a> {
+    stream: Stream<'a>,
+}
+
+impl<'a> Stream3D<'a> {
+    /// Create a new 3D stream writer.
+    pub(crate) fn start(mut stream: Stream<'a>) -> Self {
+        stream.pair(Name(b"Type"), Name(b"3D"));
+        Self { stream }
+    }
+
+    /// Write the `/Subtype` attribute to set the format of the 3D artwork.
+    /// Required.
+    pub fn subtype(&mut self, kind: Stream3DType) -> &mut Self {
+        self.pair(Name(b"Subtype"), kind.to_name());
+        self
+    }
+
+    /// Start writing the `/VA` array to set the predefined views of the
+    /// artwork.
+    pub fn views(&mut self) -> TypedArray<'_, View3D<'_>> {
+        self.insert(Name(b"VA")).array().typed()
+    }
+
+    /// Write the `/DV` attribute to set the default view by its index in the
+    /// [`views`](Self::views) array.
+    pub fn default_view_index(&mut self, index: i32) -> &mut Self {
+        self.pair(Name(b"DV"), index);
+        self
+    }
+
+    /// Write the `/DV` attribute to set the default view by its
+    /// [internal name](View3D::internal_name).
+    pub fn default_view_name(&mut self, name: TextStr) -> &mut Self {
+        self.pair(Name(b"DV"), name);
+        self
+    }
+
+    /// Start writing the `/DV` dictionary to set a default view that is not
+    /// part of the [`views`](Self::views) array.
+    pub fn default_view(&mut self) -> View3D<'_> {
+        self.insert(Name(b"DV")).start()
+    }
+
+    /// Start writing the `/AN` dictionary to set how the animations of the
+    /// artwork are driven.
+    pub fn animation(&mut self) -> Animation3D<'_> {
+        self.insert(Name(b"AN")).start()
+    }
+}
+
+deref!('a, Stream3D<'a> => Stream<'a>, stream);
+
+/// The format of the artwork in a [3D stream](Stream3D).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Stream3DType {
+    /// The Universal 3D file format.
+    U3D,
+    /// The Product Representation Compact format. PDF 1.7+.
+    PRC,
+}
+
+impl Stream3DType {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::U3D => Name(b"U3D"),
+            Self::PRC => Name(b"PRC"),
+        }
+    }
+}
+
+/// Writer for a _3D animation style dictionary_. PDF 1.7+.
+///
+/// This struct is created by [`Stream3D::animation`].
+pub struct Animation3D<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(Animation3D: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"3DAnimationStyle"));
+    Self { dict }
+});
+
+impl<'a> Animation3D<'a> {
+    /// Write the `/Subtype` attribute to set how the animation is played.
+    /// Defaults to `None`.
+    pub fn subtype(&mut self, kind: AnimationType3D) -> &mut Self {
+        self.pair(Name(b"Subtype"), kind.to_name());
+        self
+    }
+
+    /// Write the `/PC` attribute to set how often the animation is played.
+    /// Negative values play it indefinitely. Defaults to `-1`.
+    pub fn play_count(&mut self, count: i32) -> &mut Self {
+        self.pair(Name(b"PC"), count);
+        self
+    }
+
+    /// Write the `/TM` attribute to set the factor by which the playback
+    /// speed is multiplied. Defaults to `1.0`.
+    pub fn time_multiplier(&mut self, factor: f32) -> &mut Self {
+        self.pair(Name(b"TM"), factor);
+        self
+    }
+}
+
+deref!('a, Animation3D<'a> => Dict<'a>, dict);
+
+/// How the animation of a [3D stream](Stream3D) is played.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum AnimationType3D {
+    /// The animation is driven by the artwork itself or by scripts.
+    None,
+    /// The animation is played from beginning to end.
+    Linear,
+    /// The animation is played forward and then backward.
+    Oscillating,
+}
+
+impl AnimationType3D {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::None => Name(b"None"),
+            Self::Linear => Name(b"Linear"),
+            Self::Oscillating => Name(b"Oscillating"),
+        }
+    }
+}
+
+/// A view that is selected relative to the current view of a 3D annotation.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RelativeView3D {
+    /// The first view in the 3D stream's [`views`](Stream3D::views) array.
+    First,
+    /// The last view in the 3D stream's [`views`](Stream3D::views) array.
+    Last,
+    /// The view after the current one in the array.
+    Next,
+    /// The view before the current one in the array.
+    Previous,
+    /// The [default view](Stream3D::default_view) of the 3D stream.
+    Default,
+}
+
+impl RelativeView3D {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::First => Name(b"F"),
+            Self::Last => Name(b"L"),
+            Self::Next => Name(b"N"),
+            Self::Previous => Name(b"P"),
+            Self::Default => Name(b"D"),
+        }
+    }
+}
+
+/// Writer for a _3D view dictionary_. PDF 1.6+.
+///
+/// A view specifies the camera position and rendering parameters with which
+/// the 3D artwork is shown.
+///
+/// This struct is created by [`Annotation::view_3d`], [`Stream3D::views`],
+/// [`Stream3D::default_view`], and [`Action::view_3d`].
+pub struct View3D<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(View3D: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"3DView"));
+    Self { dict }
+});
+
+impl<'a> View3D<'a> {
+    /// Write the `/XN` attribute to set the name of the view that is shown in
+    /// the user interface. Required.
+    pub fn external_name(&mut self, name: TextStr) -> &mut Self {
+        self.pair(Name(b"XN"), name);
+        self
+    }
+
+    /// Write the `/IN` attribute to set the internal name of the view.
+    pub fn internal_name(&mut self, name: TextStr) -> &mut Self {
+        self.pair(Name(b"IN"), name);
+        self
+    }
+
+    /// Write the `/C2W` attribute to set the matrix that transforms the camera
+    /// coordinate system to the world coordinate system of the artwork. Also
+    /// writes the `/MS` attribute to specify that this matrix is used.
+    pub fn camera_to_world(&mut self, matrix: [f32; 12]) -> &mut Self {
+        self.pair(Name(b"MS"), Name(b"M"));
+        self.insert(Name(b"C2W")).array().items(matrix);
+        self
+    }
+
+    /// Write the `/CO` attribute to set the distance from the camera to the
+    /// center of orbit in the camera coordinate system.
+    pub fn center_of_orbit(&mut self, distance: f32) -> &mut Self {
+        self.pair(Name(b"CO"), distance);
+        self
+    }
+
+    /// Start writing the `/P` dictionary to set the projection of the view.
+    pub fn projection(&mut self) -> Projection3D<'_> {
+        self.insert(Name(b"P")).start()
+    }
+
+    /// Start writing the `/BG` dictionary to set the background of the view.
+    pub fn background(&mut self) -> Background3D<'_> {
+        self.insert(Name(b"BG")).start()
+    }
+}
+
+deref!('a, View3D<'a> => Dict<'a>, dict);
+
+/// Writer for a _3D projection dictionary_. PDF 1.6+.
+///
+/// This struct is created by [`View3D::projection`].
+pub struct Projection3D<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(Projection3D: |obj| Self { dict: obj.dict() });
+
+impl<'a> Projection3D<'a> {
+    /// Write the `/Subtype` attribute to set the kind of projection. Required.
+    pub fn subtype(&mut self, kind: Projection3DType) -> &mut Self {
+        self.pair(Name(b"Subtype"), kind.to_name());
+        self
+    }
+
+    /// Write the `/CS` attribute to set how the near and far clipping planes
+    /// are determined.
+    pub fn clipping(&mut self, clipping: Clipping3D) -> &mut Self {
+        self.pair(Name(b"CS"), clipping.to_name());
+        self
+    }
+
+    /// Write the `/F` attribute to set the far clipping distance.
+    pub fn far(&mut self, distance: f32) -> &mut Self {
+        self.pair(Name(b"F"), distance);
+        self
+    }
+
+    /// Write the `/N` attribute to set the near clipping distance.
+    pub fn near(&mut self, distance: f32) -> &mut Self {
+        self.pair(Name(b"N"), distance);
+        self
+    }
+
+    /// Write the `/FOV` attribute to set the field of view in degrees. Only
+    /// permissible for perspective projections.
+    pub fn field_of_view(&mut self, degrees: f32) -> &mut Self {
+        self.pair(Name(b"FOV"), degrees);
+        self
+    }
+
+    /// Write the `/OS` attribute to set the scale factor. Only permissible for
+    /// orthographic projections.
+    pub fn ortho_scale(&mut self, scale: f32) -> &mut Self {
+        self.pair(Name(b"OS"), scale);
+        self
+    }
+}
+
+deref!('a, Projection3D<'a> => Dict<'a>, dict);
+
+/// The kind of a [3D projection](Projection3D).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Projection3DType {
+    /// A perspective projection.
+    Perspective,
+    /// An orthographic projection.
+    Orthographic,
+}
+
+impl Projection3DType {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::Perspective => Name(b"P"),
+            Self::Orthographic => Name(b"O"),
+        }
+    }
+}
+
+/// How the clipping planes of a [3D projection](Projection3D) are determined.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Clipping3D {
+    /// Use the explicit near and far distances.
+    Explicit,
+    /// Compute the planes automatically from the artwork.
+    Automatic,
+}
+
+impl Clipping3D {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::Explicit => Name(b"XNF"),
+            Self::Automatic => Name(b"ANF"),
+        }
+    }
+}
+
+/// Writer for a _3D background dictionary_. PDF 1.6+.
+///
+/// This struct is created by [`View3D::background`].
+pub struct Background3D<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(Background3D: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"3DBG"));
+    Self { dict }
+});
+
+impl<'a> Background3D<'a> {
+    /// Write the `/C` attribute to set the background color in the
+    /// `DeviceRGB` color space.
+    pub fn color(&mut self, r: f32, g: f32, b: f32) -> &mut Self {
+        self.insert(Name(b"C")).array().items([r, g, b]);
+        self
+    }
+
+    /// Write the `/EA` attribute to set whether the background covers the
+    /// whole annotation rectangle instead of just the 3D artwork.
+    pub fn entire_annotation(&mut self, entire: bool) -> &mut Self {
+        self.pair(Name(b"EA"), entire);
+        self
+    }
+}
+
+deref!('a, Background3D<'a> => Dict<'a>, dict);
+
+/// Writer for a _3D activation dictionary_. PDF 1.6+.
+///
+/// The activation dictionary controls when the 3D artwork of an annotation is
+/// instantiated and running.
+///
+/// This struct is created by [`Annotation::activation_3d`].
+pub struct Activation3D<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(Activation3D: |obj| Self { dict: obj.dict() });
+
+impl<'a> Activation3D<'a> {
+    /// Write the `/A` attribute to set when the annotation is activated.
+    pub fn activation(&mut self, trigger: ActivationTrigger3D) -> &mut Self {
+        self.pair(Name(b"A"), trigger.to_name());
+        self
+    }
+
+    /// Write the `/AIS` attribute to set the state of the artwork after
+    /// activation.
+    ///
+    /// Panics if the state is `Uninstantiated`.
+    pub fn activation_state(&mut self, state: ArtworkState3D) -> &mut Self {
+        assert_ne!(
+            state,
+            ArtworkState3D::Uninstantiated,
+            "artwork must be instantiated after activation"
+        );
+        self.pair(Name(b"AIS"), state.to_name());
+        self
+    }
+
+    /// Write the `/D` attribute to set when the annotation is deactivated.
+    pub fn deactivation(&mut self, trigger: DeactivationTrigger3D) -> &mut Self {
+        self.pair(Name(b"D"), trigger.to_name());
+        self
+    }
+
+    /// Write the `/DIS` attribute to set the state of the artwork after
+    /// deactivation.
+    pub fn deactivation_state(&mut self, state: ArtworkState3D) -> &mut Self {
+        self.pair(Name(b"DIS"), state.to_name());
+        self
+    }
+
+    /// Write the `/TB` attribute to set whether the viewer shows a toolbar
+    /// when the annotation is activated.
+    pub fn toolbar(&mut self, show: bool) -> &mut Self {
+        self.pair(Name(b"TB"), show);
+        self
+    }
+
+    /// Write the `/NP` attribute to set whether the viewer shows a navigation
+    /// panel listing the objects of the artwork when the annotation is
+    /// activated.
+    pub fn navigation_panel(&mut self, show: bool) -> &mut Self {
+        self.pair(Name(b"NP"), show);
+        self
+    }
+}
+
+deref!('a, Activation3D<'a> => Dict<'a>, dict);
+
+/// When a 3D annotation is activated.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ActivationTrigger3D {
+    /// When the page containing the annotation is opened.
+    PageOpen,
+    /// When the page containing the annotation becomes visible.
+    PageVisible,
+    /// When the user explicitly activates the annotation.
+    Explicit,
+}
+
+impl ActivationTrigger3D {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::PageOpen => Name(b"PO"),
+            Self::PageVisible => Name(b"PV"),
+            Self::Explicit => Name(b"XA"),
+        }
+    }
+}
+
+/// When a 3D annotation is deactivated.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum DeactivationTrigger3D {
+    /// When the page containing the annotation is closed.
+    PageClose,
+    /// When the page containing the annotation becomes invisible.
+    PageInvisible,
+    /// When the user explicitly deactivates the annotation.
+    Explicit,
+}
+
+impl DeactivationTrigger3D {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::PageClose => Name(b"PC"),
+            Self::PageInvisible => Name(b"PI"),
+            Self::Explicit => Name(b"XD"),
+        }
+    }
+}
+
+/// The state of the 3D artwork of an annotation.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ArtworkState3D {
+    /// The artwork is not loaded.
+    Uninstantiated,
+    /// The artwork is loaded but not animated.
+    Instantiated,
+    /// The artwork is loaded and running.
+    Live,
+}
+
+impl ArtworkState3D {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::Uninstantiated => Name(b"U"),
+            Self::Instantiated => Name(b"I"),
+            Self::Live => Name(b"L"),
+        }
+    }
+}
@@ -2,21 +2,52 @@ use super::*;
 
 /// Writer for a _transition dictionary_.
 ///
-/// This struct is created by [`Page::transition`].
+/// In debug builds, the methods assert that the attributes they write have an
+/// effect for the style set with [`Transition::style`], no matter in which
+/// order they are called, since viewers silently ignore them otherwise.
+///
+/// This struct is created by [`Page::transition`] and [`Action::transition`].
+///
+/// # Example
+/// A page of a full screen presentation that is shown for five seconds before
+/// the next page flies in from the left.
+/// ```
+/// use pdf_writer::types::{PageMode, TransitionAngle, TransitionStyle};
+/// use pdf_writer::{PdfWriter, Rect, Ref};
+///
+/// let mut writer = PdfWriter::new();
+/// writer.catalog(Ref::new(1)).pages(Ref::new(2)).page_mode(PageMode::FullScreen);
+/// writer.pages(Ref::new(2)).kids([Ref::new(3)]).count(1);
+///
+/// let mut page = writer.page(Ref::new(3));
+/// page.parent(Ref::new(2));
+/// page.media_box(Rect::new(0.0, 0.0, 640.0, 480.0));
+/// page.duration(5.0);
+/// page.transition()
+///     .style(TransitionStyle::Fly)
+///     .duration(0.5)
+///     .angle(TransitionAngle::LeftToRight);
+/// ```
 pub struct Transition<'a> {
     dict: Dict<'a>,
+    style: Option<TransitionStyle>,
+    used: Vec<(&'static str, &'static [TransitionStyle])>,
 }
 
 writer!(Transition: |obj| {
     let mut dict = obj.dict();
     dict.pair(Name(b"Type"), Name(b"Trans"));
-    Self { dict }
+    Self { dict, style: None, used: vec![] }
 });
 
 impl<'a> Transition<'a> {
     /// Write the `/S` attribute to set the transition style.
     pub fn style(&mut self, kind: TransitionStyle) -> &mut Self {
         self.pair(Name(b"S"), kind.to_name());
+        self.style = Some(kind);
+        for &(attr, allowed) in &self.used {
+            debug_assert_style(kind, attr, allowed);
+        }
         self
     }
 
@@ -28,7 +59,13 @@ impl<'a> Transition<'a> {
 
     /// Write the `/Dm` attribute to set the transition direction. Will be
     /// horizontal if the argument is `false`.
+    ///
+    /// Only has an effect for the `Split` and `Blinds` styles.
     pub fn dimension(&mut self, vertical: bool) -> &mut Self {
+        self.debug_assert_style(
+            "dimension",
+            &[TransitionStyle::Split, TransitionStyle::Blinds],
+        );
         let name = if vertical { Name(b"V") } else { Name(b"H") };
         self.pair(Name(b"Dm"), name);
         self
@@ -36,14 +73,38 @@ impl<'a> Transition<'a> {
 
     /// Write the `/M` attribute to set the transition direction. Will be
     /// inwards if the argument is `false`.
+    ///
+    /// Only has an effect for the `Split`, `Box` and `Fly` styles.
     pub fn direction(&mut self, outward: bool) -> &mut Self {
+        self.debug_assert_style(
+            "direction",
+            &[TransitionStyle::Split, TransitionStyle::Box, TransitionStyle::Fly],
+        );
         let name = if outward { Name(b"O") } else { Name(b"I") };
         self.pair(Name(b"M"), name);
         self
     }
 
     /// Write the `/Di` attribute to set the transition angle.
+    ///
+    /// Only has an effect for the `Wipe`, `Glitter`, `Fly`, `Cover`, `Uncover`
+    /// and `Push` styles. [`TransitionAngle::None`] is only allowed for `Fly`.
     pub fn angle(&mut self, angle: TransitionAngle) -> &mut Self {
+        if angle == TransitionAngle::None {
+            self.debug_assert_style("angle `None`", &[TransitionStyle::Fly]);
+        } else {
+            self.debug_assert_style(
+                "angle",
+                &[
+                    TransitionStyle::Wipe,
+                    TransitionStyle::Glitter,
+                    TransitionStyle::Fly,
+                    TransitionStyle::Cover,
+                    TransitionStyle::Uncover,
+                    TransitionStyle::Push,
+                ],
+            );
+        }
         angle.write_to_obj(self.insert(Name(b"Di")));
         self
     }
@@ -51,15 +112,49 @@ impl<'a> Transition<'a> {
     /// Write the `/SS` attribute to set the scale for the `Fly` transition.
     /// PDF 1.5+.
     pub fn scale(&mut self, scale: f32) -> &mut Self {
+        self.debug_assert_style("scale", &[TransitionStyle::Fly]);
         self.pair(Name(b"SS"), scale);
         self
     }
 
-    /// Write the `/B` attribute for the `Fly` transition. PDF 1.5+.
+    /// Write the `/B` attribute to set whether the area that is flown in by
+    /// the `Fly` transition is rectangular and opaque. PDF 1.5+.
+    pub fn opaque_area(&mut self, opaque: bool) -> &mut Self {
+        self.debug_assert_style("opaque area", &[TransitionStyle::Fly]);
+        self.pair(Name(b"B"), opaque);
+        self
+    }
+
+    /// Write the `/F` attribute for the `Fly` transition. PDF 1.5+.
+    #[deprecated = "`/F` is not a transition attribute, use `opaque_area` to write `/B`"]
     pub fn opaque(&mut self, opaque: f32) -> &mut Self {
         self.pair(Name(b"F"), opaque);
         self
     }
+
+    /// Assert in debug builds that the style is one of `allowed`. If the style
+    /// is not known yet, the check happens once it is set.
+    fn debug_assert_style(
+        &mut self,
+        attr: &'static str,
+        allowed: &'static [TransitionStyle],
+    ) {
+        match self.style {
+            Some(style) => debug_assert_style(style, attr, allowed),
+            None if cfg!(debug_assertions) => self.used.push((attr, allowed)),
+            None => {}
+        }
+    }
+}
+
+/// Assert in debug builds that `style` is one of `allowed`.
+fn debug_assert_style(style: TransitionStyle, attr: &str, allowed: &[TransitionStyle]) {
+    debug_assert!(
+        allowed.contains(&style),
+        "{} has no effect for the {:?} transition style",
+        attr,
+        style,
+    );
 }
 
 deref!('a, Transition<'a> => Dict<'a>, dict);
@@ -0,0 +1,366 @@
+use std::collections::{HashMap, HashSet};
+
+use super::*;
+
+/// Structural checks.
+impl PdfWriter {
+    /// Cross-check the objects written so far for common structural mistakes.
+    ///
+    /// This is not a full validator. It only knows what the typed writers
+    /// recorded while writing, e.g. the kids passed to [`Pages::kids`], no
+    /// matter whether they were started through the writer, through
+    /// [`indirect`](Self::indirect) or in a [`Chunk`]. Apart from references
+    /// in an `/Annots` array, entries written through the generic [`Dict`]
+    /// methods or with [`Obj::raw`] are not seen. The following problems are
+    /// reported:
+    ///
+    /// - The document has no [catalog](Catalog).
+    /// - An indirect reference id was used more than once.
+    /// - The `/Count` of a page tree node does not match the number of pages
+    ///   below it.
+    /// - The `/Parent` of a page or page tree node is not a written page tree
+    ///   node.
+    /// - An annotation is not referenced from the `/Annots` of any page.
+    /// - A feature requires a newer PDF version than the
+    ///   [profile](Self::set_profile), e.g. an annotation subtype, a portable
+    ///   collection, page output intents or associated files.
+    pub fn validate(&self) -> Vec<Warning> {
+        let structure = &self.structure;
+        let mut warnings = vec![];
+        if !structure.catalog {
+            warnings.push(Warning::MissingCatalog);
+        }
+
+        let mut sorted = self.offsets.clone();
+        sorted.sort_by_key(|&(_, offset)| offset);
+
+        let mut seen = HashSet::new();
+        for &(id, _) in &sorted {
+            if !seen.insert(id) {
+                warnings.push(Warning::DuplicateRef(id));
+            }
+        }
+
+        let mut ids: Vec<Ref> = structure.nodes.keys().copied().collect();
+        ids.sort();
+
+        for &id in &ids {
+            let node = &structure.nodes[&id];
+            if let Some(parent) = node.parent {
+                if !structure.nodes.get(&parent).map_or(false, |node| !node.page) {
+                    warnings.push(Warning::MissingParent { child: id, parent });
+                }
+            }
+
+            if node.page {
+                continue;
+            }
+
+            if let (Some(count), Some(leaves)) = (node.count, structure.leaves(id, 0)) {
+                if count != leaves as i32 {
+                    warnings.push(Warning::CountMismatch { node: id, count, leaves });
+                }
+            }
+        }
+
+        let mut annotations = structure.annotations.clone();
+        annotations.sort();
+        annotations.dedup();
+        for id in annotations {
+            if !structure.annotated.contains(&id) {
+                warnings.push(Warning::UnreferencedAnnotation(id));
+            }
+        }
+
+        let target = self.profile.version();
+        for &(object, feature, version) in &structure.versions {
+            if version > target {
+                warnings.push(Warning::RequiresVersion { object, feature, version });
+            }
+        }
+
+        warnings
+    }
+}
+
+/// A structural problem found by [`PdfWriter::validate`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Warning {
+    /// No document catalog was written.
+    MissingCatalog,
+    /// An indirect reference id was used for more than one object.
+    DuplicateRef(Ref),
+    /// A page tree node's `/Count` differs from the number of pages below it.
+    CountMismatch {
+        /// The page tree node.
+        node: Ref,
+        /// The written count.
+        count: i32,
+        /// The number of pages below the node.
+        leaves: usize,
+    },
+    /// The `/Parent` of a page or page tree node is not a written page tree
+    /// node.
+    MissingParent {
+        /// The page or page tree node.
+        child: Ref,
+        /// The parent that was not written as a page tree node.
+        parent: Ref,
+    },
+    /// An annotation is not referenced from any page.
+    UnreferencedAnnotation(Ref),
+    /// A feature requires a newer PDF version than the
+    /// [profile](crate::PdfWriter::set_profile) targets.
+    RequiresVersion {
+        /// The object that uses the feature.
+        object: Ref,
+        /// The feature, e.g. `screen annotations`.
+        feature: &'static str,
+        /// The PDF version the feature requires.
+        version: (u8, u8),
+    },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingCatalog => f.write_str("document catalog is missing"),
+            Self::DuplicateRef(id) => {
+                write!(f, "duplicate indirect reference id: {}", id.get())
+            }
+            Self::CountMismatch { node, count, leaves } => write!(
+                f,
+                "page tree node {} has count {}, but {} pages below it",
+                node.get(),
+                count,
+                leaves,
+            ),
+            Self::MissingParent { child, parent } => write!(
+                f,
+                "object {} has parent {}, which is not a written page tree node",
+                child.get(),
+                parent.get(),
+            ),
+            Self::UnreferencedAnnotation(id) => {
+                write!(f, "annotation {} is not referenced from any page", id.get())
+            }
+            Self::RequiresVersion { object, feature, version: (major, minor) } => write!(
+                f,
+                "object {} uses {}, which require PDF {}.{}",
+                object.get(),
+                feature,
+                major,
+                minor,
+            ),
+        }
+    }
+}
+
+/// The parts of the document structure that are checked by
+/// [`PdfWriter::validate`], as recorded by the typed writers.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Structure {
+    catalog: bool,
+    nodes: HashMap<Ref, Node>,
+    annotations: Vec<Ref>,
+    annotated: HashSet<Ref>,
+    refs: Vec<Ref>,
+    versions: Vec<(Ref, &'static str, (u8, u8))>,
+}
+
+/// A recorded page or page tree node.
+#[derive(Debug, Default, Clone)]
+struct Node {
+    page: bool,
+    parent: Option<Ref>,
+    kids: Option<Vec<Ref>>,
+    count: Option<i32>,
+}
+
+impl Structure {
+    /// Add everything recorded in another structure, e.g. of a chunk.
+    pub(crate) fn extend(&mut self, other: &Self) {
+        self.catalog |= other.catalog;
+        self.nodes
+            .extend(other.nodes.iter().map(|(&id, node)| (id, node.clone())));
+        self.annotations.extend(&other.annotations);
+        self.annotated.extend(&other.annotated);
+        self.refs.extend(&other.refs);
+        self.versions.extend(&other.versions);
+    }
+
+    /// Replace all recorded ids according to `mapping`.
+    pub(crate) fn renumber(&mut self, mut mapping: impl FnMut(Ref) -> Ref) {
+        self.nodes = std::mem::take(&mut self.nodes)
+            .into_iter()
+            .map(|(id, mut node)| {
+                node.parent = node.parent.map(&mut mapping);
+                if let Some(kids) = &mut node.kids {
+                    kids.iter_mut().for_each(|kid| *kid = mapping(*kid));
+                }
+                (mapping(id), node)
+            })
+            .collect();
+        self.annotations.iter_mut().for_each(|id| *id = mapping(*id));
+        self.annotated = self.annotated.drain().map(&mut mapping).collect();
+        self.refs.iter_mut().for_each(|id| *id = mapping(*id));
+        self.versions.iter_mut().for_each(|(id, _, _)| *id = mapping(*id));
+    }
+
+    /// Whether any page was written or any page tree node has pages below
+    /// it.
+    pub(crate) fn has_pages(&self) -> bool {
+        self.nodes.values().any(|node| {
+            node.page
+                || node.count.map_or(false, |count| count > 0)
+                || node.kids.as_ref().map_or(false, |kids| !kids.is_empty())
+        })
+    }
+
+    /// All indirect references written by the typed writers and through
+    /// [`Obj::primitive`], in the order they were written.
+    pub(crate) fn refs(&self) -> &[Ref] {
+        &self.refs
+    }
+
+    /// The number of pages below a page tree node or `None` if it is unknown
+    /// because a kid is missing or the tree is too deep.
+    fn leaves(&self, id: Ref, depth: usize) -> Option<usize> {
+        let node = self.nodes.get(&id)?;
+        if node.page {
+            Some(1)
+        } else if depth < 64 {
+            node.kids
+                .as_ref()?
+                .iter()
+                .map(|&kid| self.leaves(kid, depth + 1))
+                .sum()
+        } else {
+            None
+        }
+    }
+}
+
+/// Records what a typed writer for an indirect object writes into a
+/// [`Structure`].
+///
+/// Direct objects nested in an indirect object get a reborrowed recorder. It
+/// records the references and annotations they write, but not what kind of
+/// object they are, since they are not the indirect object itself.
+#[derive(Debug)]
+pub(crate) struct Recorder<'a> {
+    structure: &'a mut Structure,
+    id: Ref,
+    nested: bool,
+    annots: bool,
+}
+
+impl<'a> Recorder<'a> {
+    /// Create a recorder for the indirect object with the given id.
+    pub(crate) fn new(structure: &'a mut Structure, id: Ref) -> Self {
+        Self { structure, id, nested: false, annots: false }
+    }
+
+    /// Borrow the recorder for a direct object nested in the indirect one.
+    pub(crate) fn reborrow(&mut self) -> Recorder<'_> {
+        Recorder {
+            structure: self.structure,
+            id: self.id,
+            nested: true,
+            annots: self.annots,
+        }
+    }
+
+    /// Borrow the recorder for the value of a dictionary pair with the given
+    /// key.
+    ///
+    /// References in the value of an `/Annots` pair, no matter how it is
+    /// written, are recorded as annotations that are referenced from a page.
+    pub(crate) fn reborrow_pair(&mut self, key: Name) -> Recorder<'_> {
+        let mut record = self.reborrow();
+        record.annots = key == Name(b"Annots");
+        record
+    }
+
+    /// Record that the object is the document catalog.
+    pub(crate) fn catalog(&mut self) {
+        if !self.nested {
+            self.structure.catalog = true;
+        }
+    }
+
+    /// Record that the object is an annotation.
+    pub(crate) fn annotation(&mut self) {
+        if !self.nested {
+            self.structure.annotations.push(self.id);
+        }
+    }
+
+    /// Record that the object is a page or a page tree node.
+    pub(crate) fn node(&mut self, page: bool) {
+        if !self.nested {
+            self.structure.nodes.insert(self.id, Node { page, ..Node::default() });
+        }
+    }
+
+    /// Record an indirect reference written somewhere in the object.
+    pub(crate) fn reference(&mut self, id: Ref) {
+        self.structure.refs.push(id);
+        if self.annots {
+            self.structure.annotated.insert(id);
+        }
+    }
+
+    /// Record that the object uses a feature that requires the given PDF
+    /// version. Features below PDF 1.4 are not recorded since every profile
+    /// supports them.
+    pub(crate) fn requires(&mut self, feature: &'static str, version: (u8, u8)) {
+        if version > (1, 4) {
+            self.structure.versions.push((self.id, feature, version));
+        }
+    }
+
+    /// Record the `/Parent` of a page or page tree node.
+    pub(crate) fn parent(&mut self, parent: Ref) {
+        if let Some(node) = self.node_mut() {
+            node.parent = Some(parent);
+        }
+    }
+
+    /// Record that the `/Kids` of a page tree node are written. The kids
+    /// themselves are recorded with [`kid`](Self::kid).
+    pub(crate) fn kids(&mut self) {
+        if let Some(node) = self.node_mut() {
+            node.kids = Some(vec![]);
+        }
+    }
+
+    /// Record a kid of a page tree node while its `/Kids` are written.
+    ///
+    /// This also works with the recorder of the `/Kids` array, which is
+    /// nested in the node.
+    pub(crate) fn kid(&mut self, kid: Ref) {
+        let node = self.structure.nodes.get_mut(&self.id);
+        if let Some(kids) = node.and_then(|node| node.kids.as_mut()) {
+            kids.push(kid);
+        }
+    }
+
+    /// Record the `/Count` of a page tree node.
+    pub(crate) fn count(&mut self, count: i32) {
+        if let Some(node) = self.node_mut() {
+            node.count = Some(count);
+        }
+    }
+
+    /// The recorded page or page tree node, unless this recorder belongs to
+    /// a nested object.
+    fn node_mut(&mut self) -> Option<&mut Node> {
+        if self.nested {
+            None
+        } else {
+            self.structure.nodes.get_mut(&self.id)
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use super::*;
+
+/// Writer for a _web capture information dictionary_. PDF 1.3+.
+///
+/// This struct is created by [`Catalog::spider_info`].
+pub struct WebCaptureInfo<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(WebCaptureInfo: |obj| Self { dict: obj.dict() });
+
+impl<'a> WebCaptureInfo<'a> {
+    /// Write the `/V` attribute to set the version of the web capture
+    /// extension the document conforms to. Should be `1.0`. Required.
+    pub fn version(&mut self, version: f32) -> &mut Self {
+        self.pair(Name(b"V"), version);
+        self
+    }
+
+    /// Start writing the `/C` array with the commands that were used to
+    /// capture the content of the document.
+    pub fn commands(&mut self) -> TypedArray<'_, WebCaptureCommand<'_>> {
+        self.insert(Name(b"C")).array().typed()
+    }
+
+    /// Write the `/C` array as references to command dictionaries.
+    pub fn command_refs(&mut self, commands: impl IntoIterator<Item = Ref>) -> &mut Self {
+        self.insert(Name(b"C")).array().items(commands);
+        self
+    }
+}
+
+deref!('a, WebCaptureInfo<'a> => Dict<'a>, dict);
+
+/// Writer for a _web capture command dictionary_. PDF 1.3+.
+///
+/// This struct is created by [`PdfWriter::web_capture_command`]. An array of
+/// this struct is created by [`WebCaptureInfo::commands`].
+pub struct WebCaptureCommand<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(WebCaptureCommand: |obj| Self { dict: obj.dict() });
+
+impl<'a> WebCaptureCommand<'a> {
+    /// Write the `/URL` attribute to set the URL of the captured content.
+    /// Required.
+    pub fn url(&mut self, url: Str) -> &mut Self {
+        self.pair(Name(b"URL"), url);
+        self
+    }
+
+    /// Write the `/L` attribute to set how many levels of links were followed
+    /// from the URL. Defaults to `1`.
+    pub fn levels(&mut self, levels: i32) -> &mut Self {
+        self.pair(Name(b"L"), levels);
+        self
+    }
+
+    /// Write the `/F` attribute to set flags that restrict which links were
+    /// followed.
+    pub fn flags(&mut self, flags: WebCaptureFlags) -> &mut Self {
+        self.pair(Name(b"F"), flags.bits() as i32);
+        self
+    }
+
+    /// Write the `/P` attribute to set the data that was posted to the URL.
+    pub fn post_data(&mut self, data: Str) -> &mut Self {
+        self.pair(Name(b"P"), data);
+        self
+    }
+
+    /// Write the `/CT` attribute to set the content type of the post data.
+    pub fn content_type(&mut self, mime: Str) -> &mut Self {
+        self.pair(Name(b"CT"), mime);
+        self
+    }
+
+    /// Write the `/H` attribute to set additional HTTP request headers,
+    /// separated by CRLF.
+    pub fn headers(&mut self, headers: Str) -> &mut Self {
+        self.pair(Name(b"H"), headers);
+        self
+    }
+}
+
+deref!('a, WebCaptureCommand<'a> => Dict<'a>, dict);
+
+bitflags::bitflags! {
+    /// Restrictions on the links that were followed by a [web capture
+    /// command](WebCaptureCommand).
+    pub struct WebCaptureFlags: u32 {
+        /// Only follow links to the same host as the URL.
+        const SAME_SITE = 1 << 1;
+        /// Only follow links to paths below the path of the URL.
+        const SAME_PATH = 1 << 2;
+        /// Submit the post data. Should be set if
+        /// [`post_data`](WebCaptureCommand::post_data) is written.
+        const SUBMIT = 1 << 3;
+    }
+}
+
+/// Writer for a _web capture content set dictionary_. PDF 1.3+.
+///
+/// A content set groups the pages or images that were produced from one
+/// captured URL. Content sets are referenced from the
+/// [`capture_urls`](Names::capture_urls) and
+/// [`capture_ids`](Names::capture_ids) name trees.
+///
+/// This struct is created by [`PdfWriter::web_capture_content_set`].
+pub struct WebCaptureContentSet<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(WebCaptureContentSet: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"SpiderContentSet"));
+    Self { dict }
+});
+
+impl<'a> WebCaptureContentSet<'a> {
+    /// Write the `/S` attribute to set whether the content set consists of
+    /// pages or images. Required.
+    pub fn subtype(&mut self, kind: WebCaptureContentType) -> &mut Self {
+        self.pair(Name(b"S"), kind.to_name());
+        self
+    }
+
+    /// Write the `/ID` attribute to set the digital identifier of the content
+    /// set, typically an MD5 digest of the captured data. Required.
+    pub fn id(&mut self, id: Str) -> &mut Self {
+        self.pair(Name(b"ID"), id);
+        self
+    }
+
+    /// Write the `/O` attribute to reference the [pages](Page) or [image
+    /// XObjects](ImageXObject) in the content set. Required.
+    pub fn objects(&mut self, objects: impl IntoIterator<Item = Ref>) -> &mut Self {
+        self.insert(Name(b"O")).array().items(objects);
+        self
+    }
+
+    /// Start writing the `/SI` dictionary to describe where the content was
+    /// captured from. Required.
+    pub fn source_info(&mut self) -> WebCaptureSource<'_> {
+        self.insert(Name(b"SI")).start()
+    }
+
+    /// Write the `/CT` attribute to set the content type of the captured
+    /// data, e.g. `text/html`.
+    pub fn content_type(&mut self, mime: Str) -> &mut Self {
+        self.pair(Name(b"CT"), mime);
+        self
+    }
+
+    /// Write the `/TS` attribute to set when the content was captured.
+    pub fn timestamp(&mut self, date: Date) -> &mut Self {
+        self.pair(Name(b"TS"), date);
+        self
+    }
+}
+
+deref!('a, WebCaptureContentSet<'a> => Dict<'a>, dict);
+
+/// The kind of objects in a [web capture content set](WebCaptureContentSet).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum WebCaptureContentType {
+    /// The content set consists of pages.
+    Pages,
+    /// The content set consists of image XObjects.
+    Images,
+}
+
+impl WebCaptureContentType {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::Pages => Name(b"SPS"),
+            Self::Images => Name(b"SIS"),
+        }
+    }
+}
+
+/// Writer for a _web capture source information dictionary_. PDF 1.3+.
+///
+/// This struct is created by [`WebCaptureContentSet::source_info`].
+pub struct WebCaptureSource<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(WebCaptureSource: |obj| Self { dict: obj.dict() });
+
+impl<'a> WebCaptureSource<'a> {
+    /// Write the `/AU` attribute to set the URL the content was retrieved
+    /// from. Required.
+    pub fn url(&mut self, url: Str) -> &mut Self {
+        self.pair(Name(b"AU"), url);
+        self
+    }
+
+    /// Write the `/TS` attribute to set when the content was last modified.
+    pub fn timestamp(&mut self, date: Date) -> &mut Self {
+        self.pair(Name(b"TS"), date);
+        self
+    }
+
+    /// Write the `/E` attribute to set when the content expires.
+    pub fn expiration(&mut self, date: Date) -> &mut Self {
+        self.pair(Name(b"E"), date);
+        self
+    }
+
+    /// Write the `/C` attribute to reference the [command](WebCaptureCommand)
+    /// that retrieved the content. The command must be an indirect object
+    /// that is also listed in [`WebCaptureInfo::command_refs`].
+    pub fn command(&mut self, command: Ref) -> &mut Self {
+        self.pair(Name(b"C"), command);
+        self
+    }
+}
+
+deref!('a, WebCaptureSource<'a> => Dict<'a>, dict);
@@ -55,6 +55,42 @@ impl<'a> ImageXObject<'a> {
         self
     }
 
+    /// Check that the length of the sample data matches the given dimensions,
+    /// number of color components and bits per component. Each row of samples
+    /// must start at a byte boundary.
+    ///
+    /// The check only runs in debug builds and is skipped if a
+    /// [filter](Stream::filter) was set before, as the data is encoded then.
+    ///
+    /// Panics if the length does not match or if any of the numbers is not
+    /// positive.
+    pub fn check_samples(
+        &mut self,
+        width: i32,
+        height: i32,
+        components: i32,
+        bits: i32,
+    ) -> &mut Self {
+        if cfg!(debug_assertions) && !self.stream.is_filtered() {
+            assert!(
+                width > 0 && height > 0 && components > 0 && bits > 0,
+                "image dimensions, components and bits must be positive",
+            );
+
+            let row_bits = width as usize * components as usize * bits as usize;
+            let row = (row_bits + 7) / 8;
+            let expected = row * height as usize;
+            let len = self.stream.data().len();
+            assert_eq!(
+                len, expected,
+                "image data length (is `{}`) must be `{}` for {}x{} pixels with {} \
+                 components of {} bits",
+                len, expected, width, height, components, bits,
+            );
+        }
+        self
+    }
+
     /// Write the `/Intent` attribute. PDF 1.1+.
     pub fn intent(&mut self, intent: RenderingIntent) -> &mut Self {
         self.pair(Name(b"Intent"), intent.to_name());
@@ -92,6 +128,27 @@ impl<'a> ImageXObject<'a> {
         self
     }
 
+    /// Write the `/Decode` attribute for an image in an `Indexed` color space
+    /// with the given number of bits per component, i.e. `[0 2^bits-1]`.
+    ///
+    /// The samples of indexed images are palette indices and must not be
+    /// scaled to the unit range like the samples of other color spaces. This
+    /// range is also the default, but some viewers misindex the palette if it
+    /// is missing. Use [`decode`](Self::decode) instead if the samples must
+    /// be remapped, e.g. with `[2^bits-1 0]` to reverse the palette.
+    ///
+    /// Panics if `bits` is not 1, 2, 4, 8 or 16.
+    pub fn indexed_decode(&mut self, bits: i32) -> &mut Self {
+        assert!(
+            matches!(bits, 1 | 2 | 4 | 8 | 16),
+            "bits per component of an indexed image must be 1, 2, 4, 8 or 16 (is `{}`)",
+            bits,
+        );
+        let max = (1 << bits) - 1;
+        self.insert(Name(b"Decode")).array().items([0, max]);
+        self
+    }
+
     /// Write the `/Interpolate` attribute.
     pub fn interpolate(&mut self, interpolate: bool) -> &mut Self {
         self.pair(Name(b"Interpolate"), interpolate);
@@ -100,12 +157,24 @@ impl<'a> ImageXObject<'a> {
 
     /// Write the `/Alternates` attribute. PDF 1.3+.
     ///
-    /// Images that may replace this image. The order is not relevant.
+    /// Images that may replace this image. The order is not relevant. The
+    /// references must point to alternate image dictionaries, which can be
+    /// written inline with [`alternate_images`](Self::alternate_images)
+    /// instead.
     pub fn alternates(&mut self, alternates: impl IntoIterator<Item = Ref>) -> &mut Self {
         self.insert(Name(b"Alternates")).array().items(alternates);
         self
     }
 
+    /// Start writing the `/Alternates` array with inline alternate image
+    /// dictionaries. PDF 1.3+.
+    ///
+    /// This can be used to show a low-resolution proxy on screen while a
+    /// high-resolution version is used for printing.
+    pub fn alternate_images(&mut self) -> TypedArray<'_, AlternateImage<'_>> {
+        self.insert(Name(b"Alternates")).array().typed()
+    }
+
     /// Start writing the `/SMask` attribute. PDF 1.4+.
     ///
     /// Must not be used if this image already is an image soft mask.
@@ -147,6 +216,25 @@ impl<'a> ImageXObject<'a> {
         self.pair(Name(b"Metadata"), id);
         self
     }
+
+    /// Start writing the `/Measure` dictionary to map positions in the XObject
+    /// to real-world coordinates. PDF 2.0+.
+    pub fn measure(&mut self) -> Measure<'_> {
+        self.insert(Name(b"Measure")).start()
+    }
+
+    /// Start writing the `/PtData` dictionary to attach extended geospatial
+    /// point data. PDF 2.0+.
+    pub fn point_data(&mut self) -> PointData<'_> {
+        self.insert(Name(b"PtData")).start()
+    }
+
+    /// Start writing the `/OPI` dictionary to reference a high-resolution
+    /// version of the image for prepress systems. PDF 1.2+. Deprecated in PDF
+    /// 2.0.
+    pub fn opi(&mut self) -> Opi<'_> {
+        self.insert(Name(b"OPI")).start()
+    }
 }
 
 deref!('a, ImageXObject<'a> => Stream<'a>, stream);
@@ -172,6 +260,42 @@ impl SMaskInData {
     }
 }
 
+/// Writer for an _alternate image dictionary_. PDF 1.3+.
+///
+/// An array of this struct is created by [`ImageXObject::alternate_images`].
+pub struct AlternateImage<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(AlternateImage: |obj| Self { dict: obj.dict() });
+
+impl<'a> AlternateImage<'a> {
+    /// Write the `/Image` attribute to reference the [image
+    /// XObject](ImageXObject) that is the alternate. Required.
+    pub fn image(&mut self, image: Ref) -> &mut Self {
+        self.pair(Name(b"Image"), image);
+        self
+    }
+
+    /// Write the `/DefaultForPrinting` attribute to set whether this image is
+    /// used when printing. At most one alternate of an image may set this to
+    /// `true`.
+    pub fn default_for_printing(&mut self, default: bool) -> &mut Self {
+        self.pair(Name(b"DefaultForPrinting"), default);
+        self
+    }
+
+    /// Write the `/OC` attribute to reference an optional content group or
+    /// membership dictionary that determines whether this alternate is used.
+    /// PDF 1.5+.
+    pub fn optional_content(&mut self, oc: Ref) -> &mut Self {
+        self.pair(Name(b"OC"), oc);
+        self
+    }
+}
+
+deref!('a, AlternateImage<'a> => Dict<'a>, dict);
+
 /// Writer for an _form XObject stream_. PDF 1.1+.
 ///
 /// This struct is created by [`PdfWriter::form_xobject`].
@@ -194,7 +318,7 @@ impl<'a> FormXObject<'a> {
     ///
     /// This clips the form xobject to coordinates in its coordinate system.
     pub fn bbox(&mut self, bbox: Rect) -> &mut Self {
-        self.pair(Name(b"BBox"), bbox);
+        self.pair(Name(b"BBox"), bbox.normalized());
         self
     }
 
@@ -235,6 +359,11 @@ impl<'a> FormXObject<'a> {
 
     /// Start writing the `/Ref` dictionary to identify the page from an
     /// external document that the XObject is a reference to. PDF 1.4+.
+    ///
+    /// The stream data and [`bbox`](Self::bbox) of the XObject are still
+    /// required. They serve as proxy content that is shown if the viewer
+    /// cannot or chooses not to import the external page, so they should
+    /// contain a low-resolution rendition or placeholder of the page.
     pub fn reference(&mut self) -> Reference<'_> {
         self.insert(Name(b"Ref")).start()
     }
@@ -253,6 +382,34 @@ impl<'a> FormXObject<'a> {
         self.pair(Name(b"LastModified"), last_modified);
         self
     }
+
+    /// Start writing the `/PieceInfo` dictionary to store private data of the
+    /// applications that edited the XObject. PDF 1.3+.
+    ///
+    /// The [`last_modified`](Self::last_modified) attribute is required if
+    /// this is present.
+    pub fn piece_info(&mut self) -> PieceInfo<'_> {
+        self.insert(Name(b"PieceInfo")).start()
+    }
+
+    /// Start writing the `/Measure` dictionary to map positions in the XObject
+    /// to real-world coordinates. PDF 2.0+.
+    pub fn measure(&mut self) -> Measure<'_> {
+        self.insert(Name(b"Measure")).start()
+    }
+
+    /// Start writing the `/PtData` dictionary to attach extended geospatial
+    /// point data. PDF 2.0+.
+    pub fn point_data(&mut self) -> PointData<'_> {
+        self.insert(Name(b"PtData")).start()
+    }
+
+    /// Start writing the `/OPI` dictionary to reference a high-resolution
+    /// version of the XObject for prepress systems. PDF 1.2+. Deprecated in PDF
+    /// 2.0.
+    pub fn opi(&mut self) -> Opi<'_> {
+        self.insert(Name(b"OPI")).start()
+    }
 }
 
 deref!('a, FormXObject<'a> => Stream<'a>, stream);
@@ -260,6 +417,17 @@ deref!('a, FormXObject<'a> => Stream<'a>, stream);
 /// Writer for a _group XObject dictionary_. PDF 1.4+.
 ///
 /// This struct is created by [`FormXObject::group`] and [`Page::group`].
+///
+/// # Example
+/// Make a page an isolated transparency group that is blended in RGB, even if
+/// its contents use other color spaces.
+/// ```
+/// use pdf_writer::{PdfWriter, Ref};
+///
+/// let mut writer = PdfWriter::new();
+/// let mut page = writer.page(Ref::new(1));
+/// page.group().transparency().isolated(true).color_space().device_rgb();
+/// ```
 pub struct Group<'a> {
     dict: Dict<'a>,
 }
@@ -286,6 +454,13 @@ impl<'a> Group<'a> {
         self.insert(Name(b"CS")).start()
     }
 
+    /// Write the `/CS` attribute as a reference to a color space, e.g. an
+    /// ICCBased color space that is shared with other groups.
+    pub fn color_space_ref(&mut self, id: Ref) -> &mut Self {
+        self.pair(Name(b"CS"), id);
+        self
+    }
+
     /// Set the `/I` attribute to indicate whether the group is isolated.
     ///
     /// If it is true, the group will initially be composited against a clear
@@ -320,11 +495,18 @@ writer!(Reference: |obj| Self { dict: obj.dict() });
 
 impl<'a> Reference<'a> {
     /// Start writing the `/F` attribute to set a file specification dictionary.
-    /// Required.
+    /// Either this or [`file_spec_ref`](Self::file_spec_ref) is required.
     pub fn file_spec(&mut self) -> FileSpec<'_> {
         self.insert(Name(b"F")).start()
     }
 
+    /// Write the `/F` attribute to reference a [file
+    /// specification](FileSpec) that is written as an indirect object.
+    pub fn file_spec_ref(&mut self, file_spec: Ref) -> &mut Self {
+        self.pair(Name(b"F"), file_spec);
+        self
+    }
+
     /// Write the `/Page` attribute to set the page number. Setting the
     /// attribute through either this function or [`Self::page_label`] is
     /// required. Page indices start at 0.
@@ -348,3 +530,290 @@ impl<'a> Reference<'a> {
 }
 
 deref!('a, Reference<'a> => Dict<'a>, dict);
+
+/// Writer for an _OPI dictionary_. PDF 1.2+. Deprecated in PDF 2.0.
+///
+/// Open Prepress Interface (OPI) dictionaries reference a high-resolution
+/// version of a proxy image or form XObject that is substituted by the
+/// prepress system. Exactly one of the [1.3](Self::version_13) or
+/// [2.0](Self::version_20) dialects must be written.
+///
+/// This struct is created by [`ImageXObject::opi`] and [`FormXObject::opi`].
+pub struct Opi<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(Opi: |obj| Self { dict: obj.dict() });
+
+impl<'a> Opi<'a> {
+    /// Start writing the `/1.3` dictionary for the OPI 1.3 dialect.
+    pub fn version_13(&mut self) -> Opi13<'_> {
+        self.insert(Name(b"1.3")).start()
+    }
+
+    /// Start writing the `/2.0` dictionary for the OPI 2.0 dialect.
+    pub fn version_20(&mut self) -> Opi20<'_> {
+        self.insert(Name(b"2.0")).start()
+    }
+}
+
+deref!('a, Opi<'a> => Dict<'a>, dict);
+
+/// Writer for an _OPI version 1.3 dictionary_. PDF 1.2+.
+///
+/// This struct is created by [`Opi::version_13`].
+pub struct Opi13<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(Opi13: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"OPI"));
+    dict.pair(Name(b"Version"), 1.3);
+    Self { dict }
+});
+
+impl<'a> Opi13<'a> {
+    /// Start writing the `/F` dictionary to set the external file with the
+    /// high-resolution image. Required.
+    pub fn file_spec(&mut self) -> FileSpec<'_> {
+        self.insert(Name(b"F")).start()
+    }
+
+    /// Write the `/ID` attribute to identify the high-resolution image.
+    pub fn id(&mut self, id: Str) -> &mut Self {
+        self.pair(Name(b"ID"), id);
+        self
+    }
+
+    /// Write the `/Comments` attribute.
+    pub fn comments(&mut self, comments: TextStr) -> &mut Self {
+        self.pair(Name(b"Comments"), comments);
+        self
+    }
+
+    /// Write the `/Size` attribute to set the dimensions of the
+    /// high-resolution image in pixels. Required.
+    pub fn size(&mut self, width: i32, height: i32) -> &mut Self {
+        self.insert(Name(b"Size")).array().items([width, height]);
+        self
+    }
+
+    /// Write the `/CropRect` attribute to set the region of the
+    /// high-resolution image that is used in pixels. Required.
+    pub fn crop_rect(
+        &mut self,
+        left: i32,
+        top: i32,
+        right: i32,
+        bottom: i32,
+    ) -> &mut Self {
+        self.insert(Name(b"CropRect"))
+            .array()
+            .items([left, top, right, bottom]);
+        self
+    }
+
+    /// Write the `/CropFixed` attribute to set the [crop
+    /// rectangle](Self::crop_rect) with fractional coordinates.
+    pub fn crop_fixed(
+        &mut self,
+        left: f32,
+        top: f32,
+        right: f32,
+        bottom: f32,
+    ) -> &mut Self {
+        self.insert(Name(b"CropFixed"))
+            .array()
+            .items([left, top, right, bottom]);
+        self
+    }
+
+    /// Write the `/Position` attribute to set the upper-left, upper-right,
+    /// lower-right, and lower-left corners of the cropped image in the unit
+    /// square of the XObject. Required.
+    pub fn position(&mut self, corners: [f32; 8]) -> &mut Self {
+        self.insert(Name(b"Position")).array().items(corners);
+        self
+    }
+
+    /// Write the `/Resolution` attribute to set the horizontal and vertical
+    /// resolution of the high-resolution image in pixels per inch.
+    pub fn resolution(&mut self, horizontal: f32, vertical: f32) -> &mut Self {
+        self.insert(Name(b"Resolution")).array().items([horizontal, vertical]);
+        self
+    }
+
+    /// Write the `/ColorType` attribute to set whether the image is printed
+    /// with process colors, a spot color, or as a separation.
+    pub fn color_type(&mut self, kind: OpiColorType) -> &mut Self {
+        self.pair(Name(b"ColorType"), kind.to_name());
+        self
+    }
+
+    /// Write the `/Color` attribute to set the CMYK approximation and the name
+    /// of a spot color.
+    pub fn color(&mut self, c: f32, m: f32, y: f32, k: f32, name: Str) -> &mut Self {
+        let mut array = self.insert(Name(b"Color")).array();
+        array.items([c, m, y, k]);
+        array.item(name);
+        array.finish();
+        self
+    }
+
+    /// Write the `/Tint` attribute to set the tint of a spot color between
+    /// `0.0` and `1.0`.
+    pub fn tint(&mut self, tint: f32) -> &mut Self {
+        self.pair(Name(b"Tint"), tint);
+        self
+    }
+
+    /// Write the `/Overprint` attribute to set whether the image overprints.
+    pub fn overprint(&mut self, overprint: bool) -> &mut Self {
+        self.pair(Name(b"Overprint"), overprint);
+        self
+    }
+
+    /// Write the `/ImageType` attribute to set the number of samples per
+    /// pixel and bits per sample of the high-resolution image.
+    pub fn image_type(&mut self, samples: i32, bits: i32) -> &mut Self {
+        self.insert(Name(b"ImageType")).array().items([samples, bits]);
+        self
+    }
+
+    /// Write the `/Transparency` attribute to set whether white pixels of a
+    /// monochrome image are transparent.
+    pub fn transparency(&mut self, transparent: bool) -> &mut Self {
+        self.pair(Name(b"Transparency"), transparent);
+        self
+    }
+}
+
+deref!('a, Opi13<'a> => Dict<'a>, dict);
+
+/// With which colors an image referenced by an [OPI 1.3](Opi13) dictionary is
+/// printed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum OpiColorType {
+    /// With the process colors.
+    Process,
+    /// With a spot color.
+    Spot,
+    /// As a separation prepared for a spot color.
+    Separation,
+}
+
+impl OpiColorType {
+    pub(crate) fn to_name(self) -> Name<'static> {
+        match self {
+            Self::Process => Name(b"Process"),
+            Self::Spot => Name(b"Spot"),
+            Self::Separation => Name(b"Separation"),
+        }
+    }
+}
+
+/// Writer for an _OPI version 2.0 dictionary_. PDF 1.2+.
+///
+/// This struct is created by [`Opi::version_20`].
+pub struct Opi20<'a> {
+    dict: Dict<'a>,
+}
+
+writer!(Opi20: |obj| {
+    let mut dict = obj.dict();
+    dict.pair(Name(b"Type"), Name(b"OPI"));
+    dict.pair(Name(b"Version"), 2.0);
+    Self { dict }
+});
+
+impl<'a> Opi20<'a> {
+    /// Start writing the `/F` dictionary to set the external file with the
+    /// high-resolution image. Required.
+    pub fn file_spec(&mut self) -> FileSpec<'_> {
+        self.insert(Name(b"F")).start()
+    }
+
+    /// Write the `/MainImage` attribute to set the path of the main image file
+    /// if the [file](Self::file_spec) is a low-resolution image.
+    pub fn main_image(&mut self, path: Str) -> &mut Self {
+        self.pair(Name(b"MainImage"), path);
+        self
+    }
+
+    /// Write the `/Size` attribute to set the dimensions of the
+    /// high-resolution image in pixels.
+    pub fn size(&mut self, width: f32, height: f32) -> &mut Self {
+        self.insert(Name(b"Size")).array().items([width, height]);
+        self
+    }
+
+    /// Write the `/CropRect` attribute to set the region of the
+    /// high-resolution image that is used in pixels.
+    pub fn crop_rect(
+        &mut self,
+        left: f32,
+        top: f32,
+        right: f32,
+        bottom: f32,
+    ) -> &mut Self {
+        self.insert(Name(b"CropRect"))
+            .array()
+            .items([left, top, right, bottom]);
+        self
+    }
+
+    /// Write the `/Overprint` attribute to set whether the image overprints.
+    pub fn overprint(&mut self, overprint: bool) -> &mut Self {
+        self.pair(Name(b"Overprint"), overprint);
+        self
+    }
+
+    /// Write the `/Inks` attribute to set that the image uses all process
+    /// colors.
+    pub fn inks_full_color(&mut self) -> &mut Self {
+        self.pair(Name(b"Inks"), Name(b"full_color"));
+        self
+    }
+
+    /// Write the `/Inks` attribute to set that the image is printed on all
+    /// separations.
+    pub fn inks_registration(&mut self) -> &mut Self {
+        self.pair(Name(b"Inks"), Name(b"registration"));
+        self
+    }
+
+    /// Write the `/Inks` attribute to set the names and tints of the inks of
+    /// a monochrome image.
+    pub fn inks_monochrome<'i>(
+        &mut self,
+        inks: impl IntoIterator<Item = (Str<'i>, f32)>,
+    ) -> &mut Self {
+        let mut array = self.insert(Name(b"Inks")).array();
+        array.item(Name(b"monochrome"));
+        for (name, tint) in inks {
+            array.item(name);
+            array.item(tint);
+        }
+        array.finish();
+        self
+    }
+
+    /// Write the `/IncludedImageDimensions` attribute to set the dimensions of
+    /// the image that is included in the XObject in pixels.
+    pub fn included_image_dimensions(&mut self, width: i32, height: i32) -> &mut Self {
+        self.insert(Name(b"IncludedImageDimensions"))
+            .array()
+            .items([width, height]);
+        self
+    }
+
+    /// Write the `/IncludedImageQuality` attribute to set the quality of the
+    /// included image from `1.0` (low) to `3.0` (high).
+    pub fn included_image_quality(&mut self, quality: f32) -> &mut Self {
+        self.pair(Name(b"IncludedImageQuality"), quality);
+        self
+    }
+}
+
+deref!('a, Opi20<'a> => Dict<'a>, dict);
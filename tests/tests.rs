@@ -1,5 +1,25 @@
+use pdf_writer::legacy::{MoviePlayMode, SoundEncoding};
+use pdf_writer::types::{
+    ActionType, ActivationTrigger3D, AnimationType3D, AnnotationType, ArtworkState3D,
+    CidCmap, CidSet, CidToGidMap, CollectionFieldType, Color, CoordinateSystemType,
+    FieldFlags, FieldMdpAction, FieldType, FractionFormat, FreeTextIntent, GuideStyle,
+    IconScaleWhen, ListNumbering, MdpPermissions, MeasureType, MediaFit, OpaqueColor,
+    OpiColorType, OutputIntentSubtype, OverprintMode, Placement, ProcSet,
+    Projection3DType, Quadding, RelativeView3D, RenditionOperation, RenditionType,
+    RequirementHandlerType, RequirementType, RichMediaActivation, RichMediaDeactivation,
+    RichMediaType, SpotFunction, Stream3DType, StructRole, SubmitFlags, SubsetTag,
+    SystemInfo, TabOrder, TableHeaderScope, TempFilePermission, TextPosition,
+    TransformMethod, TransitionAngle, TransitionStyle, WebCaptureContentType,
+    WebCaptureFlags, WidthsBuilder, WritingMode,
+};
+use pdf_writer::writers::{
+    Annotation, Catalog, CidFont, CollectionSort, ColorSpace, Encoding, Measure,
+    NameTree, Page, PropertyList, Resources,
+};
 use pdf_writer::{
-    Date, Filter, Finish, Name, Null, Obj, PdfWriter, Rect, Ref, Str, TextStr,
+    name, Chunk, Content, Date, DefaultAppearance, Filter, Finish, Name, Null, Obj,
+    PageBuilder, PdfError, PdfWriter, Primitive, Profile, Rect, Ref, RefAllocator, Str,
+    TextStr, Warning,
 };
 
 /// Test that `buf` is the same as the result of concatenating the strings.
@@ -42,7 +62,7 @@ where
     let start = w.len();
     f(&mut w);
     let end = w.len();
-    let buf = w.finish();
+    let buf = w.into_buf();
     buf[start..end].to_vec()
 }
 
@@ -59,7 +79,7 @@ where
 fn test_minimal() {
     let w = PdfWriter::new();
     test!(
-        w.finish(),
+        w.into_buf(),
         b"%PDF-1.7\n%\x80\x80\x80\x80\n\n",
         b"xref\n0 1\n0000000000 65535 f\r\n",
         b"trailer\n<<\n  /Size 1\n>>\n",
@@ -67,13 +87,397 @@ fn test_minimal() {
     );
 }
 
+#[test]
+fn test_trailer() {
+    let mut w = PdfWriter::new();
+    w.catalog(Ref::new(1));
+    test!(
+        w.into_buf_with(|trailer| {
+            trailer.id([Str(b"A"), Str(b"B")]).encrypt(Ref::new(2)).prev(1024);
+        }),
+        b"%PDF-1.7\n%\x80\x80\x80\x80\n\n",
+        b"1 0 obj\n<<\n  /Type /Catalog\n>>\nendobj\n\n",
+        b"xref\n0 2\n0000000000 65535 f\r\n0000000016 00000 n\r\n",
+        b"trailer\n",
+        b"<<\n",
+        b"  /Size 2\n",
+        b"  /Root 1 0 R\n",
+        b"  /ID [(A) (B)]\n",
+        b"  /Encrypt 2 0 R\n",
+        b"  /Prev 1024\n",
+        b">>\n",
+        b"startxref\n55\n%%EOF",
+    );
+}
+
+#[test]
+fn test_hybrid_reference_trailer() {
+    // The previous revision of the file, whose cross-reference table the
+    // `/Prev` entry points to.
+    let base = PdfWriter::new().into_buf();
+    let startxref = |buf: &[u8]| {
+        let text = String::from_utf8_lossy(buf).into_owned();
+        let start = text.rfind("startxref\n").unwrap() + 10;
+        text[start..text.rfind("\n%%EOF").unwrap()].parse::<usize>().unwrap()
+    };
+    let prev = startxref(&base);
+    assert!(base[prev..].starts_with(b"xref\n"));
+
+    let mut w = PdfWriter::new();
+    w.set_hybrid_xref(Ref::new(3));
+    w.catalog(Ref::new(1)).pages(Ref::new(6));
+    w.page(Ref::new(4)).parent(Ref::new(6));
+
+    // An object stream with the page tree and a media box, which come after
+    // the last object that is written directly.
+    w.stream(
+        Ref::new(2),
+        b"6 0 7 39 <</Type /Pages /Kids [4 0 R] /Count 1>>[0 0 595 842]",
+    )
+    .pair(Name(b"Type"), Name(b"ObjStm"))
+    .pair(Name(b"N"), 2)
+    .pair(Name(b"First"), 9);
+    w.compressed_object(Ref::new(6), Ref::new(2), 0);
+    w.compressed_object(Ref::new(7), Ref::new(2), 1);
+    let buf = w
+        .finish_with(|trailer| {
+            trailer.prev(prev as i32);
+        })
+        .unwrap();
+
+    let text = String::from_utf8_lossy(&buf);
+    let trailer = &text[text.rfind("trailer\n").unwrap()..];
+    assert!(trailer.contains(&format!("  /Prev {prev}\n")));
+    let start = trailer.find("/XRefStm ").unwrap() + 9;
+    let xref_stm: usize = trailer[start..start + trailer[start..].find('\n').unwrap()]
+        .parse()
+        .unwrap();
+
+    // The cross-reference stream comes right before the table.
+    test!(
+        buf[xref_stm..startxref(&buf)].to_vec(),
+        b"3 0 obj\n<<\n",
+        b"  /Length 14\n",
+        b"  /Type /XRef\n",
+        b"  /Size 8\n",
+        b"  /Index [6 2]\n",
+        b"  /W [1 4 2]\n",
+        b">>\nstream\n",
+        b"\x02\x00\x00\x00\x02\x00\x00",
+        b"\x02\x00\x00\x00\x02\x00\x01",
+        b"\nendstream\nendobj\n\n",
+    );
+
+    // The compressed objects are free in the table.
+    let table = &text[text.rfind("xref\n0 ").unwrap()..text.rfind("trailer\n").unwrap()];
+    assert!(table.starts_with("xref\n0 8\n0000000005 65535 f\r\n"));
+    assert!(table
+        .ends_with("0000000006 00000 f\r\n0000000007 00000 f\r\n0000000000 00000 f\r\n"));
+}
+
+#[test]
+fn test_finish_checks() {
+    let mut w = PdfWriter::new();
+    w.catalog(Ref::new(1)).pages(Ref::new(2));
+    w.pages(Ref::new(2)).kids([Ref::new(3)]).count(1);
+    w.page(Ref::new(3)).parent(Ref::new(2));
+    assert!(w.finish().is_ok());
+
+    let mut w = PdfWriter::new();
+    w.page(Ref::new(3));
+    assert_eq!(
+        w.finish_with(|trailer| {
+            trailer.root(Ref::new(1));
+        }),
+        Err(PdfError::MissingObject(Ref::new(1))),
+    );
+
+    let mut w = PdfWriter::new();
+    w.page(Ref::new(3));
+    assert_eq!(w.finish(), Err(PdfError::MissingCatalog));
+
+    let mut w = PdfWriter::new();
+    w.catalog(Ref::new(1)).pages(Ref::new(2));
+    w.pages(Ref::new(2)).kids([]).count(0);
+    assert_eq!(w.finish(), Err(PdfError::NoPages));
+
+    let mut w = PdfWriter::new();
+    w.catalog(Ref::new(1)).pages(Ref::new(2));
+    w.pages(Ref::new(2)).kids([Ref::new(3), Ref::new(4)]).count(2);
+    w.page(Ref::new(3)).parent(Ref::new(2));
+    assert_eq!(w.finish(), Err(PdfError::MissingObject(Ref::new(4))));
+
+    let mut w = PdfWriter::new();
+    w.catalog(Ref::new(1)).pages(Ref::new(2));
+    w.pages(Ref::new(2)).kids([Ref::new(3)]).count(1);
+    w.page(Ref::new(3)).parent(Ref::new(2));
+    w.indirect(Ref::new(4)).dict().pair(Name(b"Target"), Ref::new(5));
+    assert_eq!(w.finish(), Err(PdfError::MissingObject(Ref::new(5))));
+
+    let mut w = PdfWriter::new();
+    w.catalog(Ref::new(1)).pages(Ref::new(2));
+    w.pages(Ref::new(2)).kids([Ref::new(3)]).count(1);
+    w.page(Ref::new(3)).parent(Ref::new(2));
+    w.indirect(Ref::new(4)).dict().pair(Name(b"Target"), Ref::new(5));
+    assert!(w
+        .finish_with(|trailer| {
+            trailer.prev(9);
+        })
+        .is_ok());
+
+    let mut w = PdfWriter::new();
+    w.catalog(Ref::new(1));
+    w.page(Ref::new(1));
+    assert_eq!(w.finish(), Err(PdfError::DuplicateRef(Ref::new(1))));
+
+    let mut w = PdfWriter::new();
+    w.catalog(Ref::new(1));
+    std::mem::forget(w.page(Ref::new(2)));
+    assert_eq!(w.finish(), Err(PdfError::UnfinishedObject(Ref::new(2))));
+}
+
+#[test]
+fn test_template_pages() {
+    test!(
+        slice(|w| {
+            let mut catalog = w.catalog(Ref::new(1));
+            catalog.pages(Ref::new(2));
+            let mut names = catalog.names();
+            names.pages().names().insert(Str(b"cover"), Ref::new(3));
+            names.templates().names().insert(Str(b"stamp"), Ref::new(4));
+            names.finish();
+            catalog.finish();
+
+            w.pages(Ref::new(2)).kids([Ref::new(3)]).count(1);
+            w.page(Ref::new(3)).parent(Ref::new(2));
+            w.template_page(Ref::new(4), Rect::new(0.0, 0.0, 100.0, 50.0));
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Catalog\n",
+        b"  /Pages 2 0 R\n",
+        b"  /Names <<\n",
+        b"    /Pages <<\n",
+        b"      /Names [(cover) 3 0 R]\n",
+        b"    >>\n",
+        b"    /Templates <<\n",
+        b"      /Names [(stamp) 4 0 R]\n",
+        b"    >>\n",
+        b"  >>\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n<<\n",
+        b"  /Type /Pages\n",
+        b"  /Kids [3 0 R]\n",
+        b"  /Count 1\n",
+        b">>\nendobj\n\n",
+        b"3 0 obj\n<<\n",
+        b"  /Type /Page\n",
+        b"  /Parent 2 0 R\n",
+        b">>\nendobj\n\n",
+        b"4 0 obj\n<<\n",
+        b"  /Type /Page\n",
+        b"  /MediaBox [0 0 100 50]\n",
+        b"  /Resources <<>>\n",
+        b">>\nendobj\n\n",
+    );
+
+    let mut w = PdfWriter::new();
+    w.catalog(Ref::new(1));
+    w.template_page(Ref::new(2), Rect::new(0.0, 0.0, 100.0, 50.0))
+        .resources()
+        .fonts()
+        .pair(Name(b"F1"), Ref::new(3));
+    w.indirect(Ref::new(3)).dict().pair(Name(b"Type"), Name(b"Font"));
+    assert_eq!(w.finish(), Err(PdfError::NoPages));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "template pages must not have a parent")]
+fn test_template_page_parent() {
+    let mut w = PdfWriter::new();
+    w.template_page(Ref::new(1), Rect::new(0.0, 0.0, 100.0, 50.0))
+        .parent(Ref::new(2));
+}
+
+#[test]
+fn test_pretty_printing() {
+    let write = |w: &mut PdfWriter| {
+        w.pages(Ref::new(1)).kids((2..12).map(Ref::new)).count(10);
+        w.page(Ref::new(2)).resources();
+        w.stream(Ref::new(3), b"")
+            .insert(Name(b"Decode"))
+            .array()
+            .items([0, 1]);
+    };
+
+    test!(
+        slice(write),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Pages\n",
+        b"  /Kids [2 0 R 3 0 R 4 0 R 5 0 R 6 0 R 7 0 R 8 0 R 9 0 R 10 0 R 11 0 R]\n",
+        b"  /Count 10\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n<<\n  /Type /Page\n  /Resources <<>>\n>>\nendobj\n\n",
+        b"3 0 obj\n<<\n  /Length 0\n  /Decode [0 1]\n>>\nstream\n\nendstream\nendobj\n\n",
+    );
+
+    test!(
+        slice(|w| {
+            w.set_pretty(true);
+            w.set_array_wrap_threshold(4);
+            write(w);
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Pages\n",
+        b"  /Kids [2 0 R\n",
+        b"    3 0 R\n    4 0 R\n    5 0 R\n    6 0 R\n    7 0 R\n",
+        b"    8 0 R\n    9 0 R\n    10 0 R\n    11 0 R]\n",
+        b"  /Count 10\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n<<\n  /Type /Page\n  /Resources << >>\n>>\nendobj\n\n",
+        b"3 0 obj\n<<\n  /Length 0\n  /Decode [0 1]\n>>\nstream\n\nendstream\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_deterministic_output() {
+    let write = || {
+        let mut w = PdfWriter::new();
+        assert!(w.deterministic());
+        w.set_deterministic(true);
+        let mut alloc = RefAllocator::new();
+        let catalog_id = alloc.bump();
+        let page_tree_id = alloc.bump();
+        w.catalog(catalog_id).pages(page_tree_id);
+
+        let mut page = PageBuilder::new(Rect::new(0.0, 0.0, 100.0, 100.0));
+        let fonts: Vec<_> = (0..20).map(|_| page.font(alloc.bump())).collect();
+        for font in &fonts {
+            page.content().set_font(Name(font), 12.0);
+        }
+        let page_id = page.finish(&mut w, &mut alloc, page_tree_id);
+        w.pages(page_tree_id).kids([page_id]).count(1);
+
+        let widths =
+            WidthsBuilder::from_iter((0..500).map(|cid| (cid, (cid % 7) as f32)));
+        w.cid_font(alloc.bump()).widths_from(&widths);
+        w.into_buf()
+    };
+
+    let buf = write();
+    assert_eq!(buf, write());
+    assert!(!buf.windows(4).any(|w| w == b"/ID "));
+}
+
+#[test]
+fn test_non_deterministic_file_id() {
+    let write = |id: Option<[Str; 2]>| {
+        let mut w = PdfWriter::new();
+        w.set_deterministic(false);
+        w.into_buf_with(|trailer| {
+            if let Some(id) = id {
+                trailer.id(id);
+            }
+        })
+    };
+
+    // Without an explicit identifier, one is derived from the time.
+    let (first, second) = (write(None), write(None));
+    assert_ne!(first, second);
+    let trailer = |buf: &[u8]| {
+        let start = buf.windows(8).position(|w| w == b"trailer\n").unwrap();
+        buf[start..].to_vec()
+    };
+    assert_eq!(trailer(&first).windows(5).filter(|w| w == b"/ID [").count(), 1);
+
+    // An explicit identifier is kept.
+    let id = [Str(b"a"), Str(b"b")];
+    let buf = write(Some(id));
+    assert_eq!(buf, write(Some(id)));
+    assert!(trailer(&buf).ends_with(b"  /ID [(a) (b)]\n>>\nstartxref\n16\n%%EOF"));
+}
+
+#[test]
+fn test_validate() {
+    let mut w = PdfWriter::new();
+    w.catalog(Ref::new(1)).pages(Ref::new(2));
+    w.pages(Ref::new(2)).kids([Ref::new(3), Ref::new(4)]).count(3);
+    w.pages(Ref::new(3))
+        .parent(Ref::new(2))
+        .kids([Ref::new(5), Ref::new(6)])
+        .count(2);
+    w.page(Ref::new(4)).parent(Ref::new(2)).annotations().push();
+    w.page(Ref::new(5)).parent(Ref::new(3)).annotation_refs([Ref::new(7)]);
+    w.page(Ref::new(6)).parent(Ref::new(3));
+    w.annotation(Ref::new(7)).rect(Rect::new(0.0, 0.0, 1.0, 1.0));
+    assert_eq!(w.validate(), vec![]);
+
+    let mut w = PdfWriter::new();
+    w.pages(Ref::new(2)).kids([Ref::new(3), Ref::new(4)]).count(1);
+    w.page(Ref::new(3)).parent(Ref::new(2));
+    w.page(Ref::new(4)).parent(Ref::new(9));
+    w.annotation(Ref::new(5));
+    w.stream(Ref::new(4), b"");
+    assert_eq!(
+        w.validate(),
+        vec![
+            Warning::MissingCatalog,
+            Warning::DuplicateRef(Ref::new(4)),
+            Warning::CountMismatch { node: Ref::new(2), count: 1, leaves: 2 },
+            Warning::MissingParent { child: Ref::new(4), parent: Ref::new(9) },
+            Warning::UnreferencedAnnotation(Ref::new(5)),
+        ],
+    );
+
+    // Strings that look like entries are not mistaken for them and the
+    // structure written through generic objects and chunks is known.
+    let mut w = PdfWriter::new();
+    w.indirect(Ref::new(1)).start::<Catalog>().pages(Ref::new(2));
+    w.pages(Ref::new(2)).kids([Ref::new(3)]).count(1);
+    w.document_info(Ref::new(4)).title(TextStr("\n  /Type /Annot"));
+    let mut chunk = Chunk::new();
+    chunk
+        .indirect(Ref::new(1))
+        .start::<Page>()
+        .parent(Ref::new(2))
+        .annotation_refs([Ref::new(9)]);
+    chunk.indirect(Ref::new(9)).start::<Annotation>();
+    chunk.renumber(|id| match id.get() {
+        1 => Ref::new(3),
+        9 => Ref::new(5),
+        _ => id,
+    });
+    w.extend(&chunk);
+    assert_eq!(w.validate(), vec![]);
+
+    // Annotations are also referenced through `/Annots` arrays written with
+    // the generic methods, but not through other entries.
+    let mut w = PdfWriter::new();
+    w.catalog(Ref::new(1)).pages(Ref::new(2));
+    w.pages(Ref::new(2)).kids([Ref::new(3), Ref::new(4)]).count(2);
+    let mut page = w.page(Ref::new(3));
+    page.parent(Ref::new(2));
+    page.insert(Name(b"Annots")).array().item(Ref::new(5));
+    page.finish();
+    let mut page = w.indirect(Ref::new(4)).dict();
+    page.pair(Name(b"Type"), Name(b"Page"))
+        .pair(Name(b"Parent"), Ref::new(2));
+    page.insert(Name(b"Annots")).array().items([Ref::new(6)]);
+    page.insert(Name(b"Other")).array().item(Ref::new(7));
+    page.finish();
+    for id in 5..=7 {
+        w.annotation(Ref::new(id));
+    }
+    assert_eq!(w.validate(), vec![Warning::UnreferencedAnnotation(Ref::new(7))]);
+}
+
 #[test]
 fn test_xref_free_list_short() {
     let mut w = PdfWriter::new();
     w.indirect(Ref::new(1)).primitive(1);
     w.indirect(Ref::new(2)).primitive(2);
     test!(
-        w.finish(),
+        w.into_buf(),
         b"%PDF-1.7\n%\x80\x80\x80\x80\n\n",
         b"1 0 obj\n1\nendobj\n\n",
         b"2 0 obj\n2\nendobj\n\n",
@@ -96,7 +500,7 @@ fn test_xref_free_list_long() {
     w.indirect(Ref::new(2)).primitive(2);
     w.indirect(Ref::new(5)).primitive(5);
     test!(
-        w.finish(),
+        w.into_buf(),
         b"%PDF-1.4\n%\x80\x80\x80\x80\n\n",
         b"1 0 obj\n1\nendobj\n\n",
         b"2 0 obj\n2\nendobj\n\n",
@@ -124,7 +528,16 @@ fn test_xref_free_list_duplicate() {
     w.indirect(Ref::new(13)).primitive(1);
     w.indirect(Ref::new(3)).primitive(1);
     w.indirect(Ref::new(6)).primitive(2);
-    w.finish();
+    w.into_buf();
+}
+
+#[test]
+#[should_panic(expected = "object 1 started but not finished")]
+fn test_unfinished_object() {
+    let mut w = PdfWriter::new();
+    std::mem::forget(w.indirect(Ref::new(1)).dict());
+    w.indirect(Ref::new(2)).primitive(2);
+    w.into_buf();
 }
 
 #[test]
@@ -144,6 +557,8 @@ fn test_primitive_objects() {
     test_primitive!(Str(b"()"), b"<2829>");
     test_primitive!(Str(br"\"), b"<5C>");
     test_primitive!(Str(br"\n"), b"<5C6E>");
+    test_primitive!(Str::from("Hi"), b"(Hi)");
+    test_primitive!(Str::from(&b"()"[..]), b"<2829>");
 
     // Test text strings.
     test_primitive!(TextStr("Hallo"), b"(Hallo)");
@@ -155,6 +570,406 @@ fn test_primitive_objects() {
     test_primitive!(Name(b"~+c"), br"/#7E#2Bc");
     test_primitive!(Name(b"#"), br"/#23");
     test_primitive!(Name(b"\n"), br"/#0A");
+    test_primitive!(Name::from("Type"), b"/Type");
+    test_primitive!(Name::from(&b"A B"[..]), br"/A#20B");
+    test_primitive!(name!("Type"), b"/Type");
+
+    let name: Name = "Type".into();
+    assert_eq!(name, Name(b"Type"));
+}
+
+#[test]
+fn test_indirect_primitives() {
+    test!(
+        slice(|w| {
+            w.indirect(Ref::new(5)).primitive(12345);
+            w.indirect_primitive(Ref::new(6), 12345);
+            w.indirect_primitive(Ref::new(7), Ref::new(5));
+        }),
+        b"5 0 obj\n12345\nendobj\n\n",
+        b"6 0 obj\n12345\nendobj\n\n",
+        b"7 0 obj\n5 0 R\nendobj\n\n",
+    );
+
+    let mut chunk = Chunk::new();
+    chunk.indirect_primitive(Ref::new(1), 42);
+    test!(slice(|w| w.extend(&chunk)), b"1 0 obj\n42\nendobj\n\n");
+}
+
+#[test]
+fn test_raw_objects() {
+    test_obj!(|obj| obj.raw(b"<< /X 1 >>"), b"<< /X 1 >>");
+    test_obj!(
+        |obj| obj.array().item(1).item_raw(b"/Vendor#20Name").item(2),
+        b"[1 /Vendor#20Name 2]",
+    );
+    test_obj!(
+        |obj| obj
+            .dict()
+            .pair(Name(b"A"), 1)
+            .pair_raw(Name(b"XYZ_Private"), b"[true 1.5]")
+            .pair(Name(b"B"), 2),
+        b"<<\n  /A 1\n  /XYZ#5FPrivate [true 1.5]\n  /B 2\n>>",
+    );
+    test!(slice(|w| w.indirect(Ref::new(1)).raw(b"42")), b"1 0 obj\n42\nendobj\n\n",);
+}
+
+#[test]
+fn test_wrapped_strings() {
+    test_primitive!(Str(b"abcdefg").wrapped(3), b"(abc\\\ndef\\\ng)");
+    test_primitive!(Str(b"()(").wrapped(3), b"<28\n29\n28>");
+    test_primitive!(Str(b"ab").wrapped(2), b"(ab)");
+
+    // Parse the wrapped string back by dropping the continuations.
+    let bytes: Vec<u8> = (0..200).map(|i| b'a' + (i % 26) as u8).collect();
+    let buf = slice_obj(|obj| obj.primitive(Str(&bytes).wrapped(80)));
+    let lines: Vec<&[u8]> = buf.split(|&b| b == b'\n').collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines.iter().all(|line| line.len() <= 82));
+
+    let inner = &buf[1..buf.len() - 1];
+    let mut parsed = vec![];
+    let mut i = 0;
+    while i < inner.len() {
+        if inner[i..].starts_with(b"\\\n") {
+            i += 2;
+        } else {
+            parsed.push(inner[i]);
+            i += 1;
+        }
+    }
+    assert_eq!(parsed, bytes);
+}
+
+#[test]
+fn test_cid_cmap() {
+    let info = SystemInfo {
+        registry: Str(b"Adobe"),
+        ordering: Str(b"Japan1"),
+        supplement: 6,
+    };
+
+    // A horizontal map and a vertical one that only overrides a few codes.
+    let mut cmap = CidCmap::new(Name(b"Custom-H"), info);
+    cmap.codespace_range(&[0x00], &[0x80]);
+    cmap.codespace_range(&[0x81, 0x40], &[0x9F, 0xFC]);
+    cmap.cid_range(&[0x20], &[0x7E], 231);
+    cmap.cid_char(&[0x81, 0x40], 633);
+    cmap.cid_char(&[0x81, 0x41], 634);
+    let horizontal = String::from_utf8(cmap.finish()).unwrap();
+    assert!(horizontal.contains("/CMapType 1 def\n2 begincodespacerange\n"));
+    assert!(horizontal.contains("<00> <80>\n<8140> <9FFC>\nendcodespacerange\n"));
+    assert!(horizontal.contains("1 begincidrange\n<20> <7E> 231\nendcidrange\n"));
+    assert!(horizontal.contains("2 begincidchar\n<8140> 633\n<8141> 634\nendcidchar\n"));
+    assert!(!horizontal.contains("WMode"));
+
+    let mut cmap = CidCmap::new(Name(b"Custom-V"), info);
+    cmap.vertical(true);
+    cmap.use_cmap(Name(b"Custom-H"));
+    cmap.cid_char(&[0x81, 0x41], 7887);
+    let vertical = String::from_utf8(cmap.finish()).unwrap();
+    assert!(vertical.ends_with(concat!(
+        "/CMapName /Custom-V def\n",
+        "/CMapVersion 1 def\n",
+        "/CMapType 1 def\n",
+        "/WMode 1 def\n",
+        "/Custom-H usecmap\n",
+        "1 begincidchar\n",
+        "<8141> 7887\n",
+        "endcidchar\n",
+        "endcmap\n",
+        "CMapName currentdict /CMap defineresource pop\n",
+        "end\n",
+        "end\n",
+        "%%EndResource\n",
+        "%%EOF",
+    )));
+
+    // Sections are split after 100 mappings.
+    let mut cmap = CidCmap::new(Name(b"Custom-H"), info);
+    for i in 0..101 {
+        cmap.cid_char(&[i], u16::from(i));
+    }
+    let split = String::from_utf8(cmap.finish()).unwrap();
+    assert!(split.contains("<63> 99\nendcidchar\n1 begincidchar\n<64> 100\n"));
+
+    test!(
+        slice(|w| {
+            w.type0_font(Ref::new(1)).encoding_cmap(Ref::new(2));
+            w.cmap(Ref::new(2), b"")
+                .name(Name(b"Custom-V"))
+                .system_info(info)
+                .vertical(true)
+                .use_cmap(Ref::new(3));
+            w.cmap(Ref::new(4), b"").use_cmap_predefined(Name(b"UniJIS-UTF16-H"));
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Font\n",
+        b"  /Subtype /Type0\n",
+        b"  /Encoding 2 0 R\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n<<\n",
+        b"  /Length 0\n",
+        b"  /Type /CMap\n",
+        b"  /CMapName /Custom#2DV\n",
+        b"  /CIDSystemInfo <<\n",
+        b"    /Registry (Adobe)\n",
+        b"    /Ordering (Japan1)\n",
+        b"    /Supplement 6\n",
+        b"  >>\n",
+        b"  /WMode 1\n",
+        b"  /UseCMap 3 0 R\n",
+        b">>\nstream\n\nendstream\nendobj\n\n",
+        b"4 0 obj\n<<\n",
+        b"  /Length 0\n",
+        b"  /Type /CMap\n",
+        b"  /UseCMap /UniJIS#2DUTF16#2DH\n",
+        b">>\nstream\n\nendstream\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_cid_set() {
+    assert_eq!(CidSet::new().finish(), b"");
+    assert_eq!(CidSet::from_iter([0]).finish(), [0b1000_0000]);
+    assert_eq!(CidSet::from_iter([0, 7]).finish(), [0b1000_0001]);
+    assert_eq!(CidSet::from_iter([8]).finish(), [0b0000_0000, 0b1000_0000]);
+    assert_eq!(
+        CidSet::from_iter([0, 1, 2, 3, 21, 3]).finish(),
+        [0b1111_0000, 0b0000_0000, 0b0000_0100],
+    );
+
+    let mut set = CidSet::new();
+    set.insert(0).insert(65535);
+    let data = set.finish();
+    assert_eq!(data.len(), 8192);
+    assert_eq!((data[0], data[8191]), (0x80, 0x01));
+
+    test!(
+        slice(|w| {
+            w.font_descriptor(Ref::new(1))
+                .char_set(Str(b"/a/b"))
+                .cid_set(Ref::new(2));
+            w.stream(Ref::new(2), &CidSet::from_iter([0, 3, 4]).finish());
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /FontDescriptor\n",
+        b"  /CharSet (/a/b)\n",
+        b"  /CIDSet 2 0 R\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n<<\n  /Length 1\n>>\nstream\n\x98\nendstream\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_vertical_widths() {
+    test_obj!(
+        |obj| {
+            let mut font = obj.start::<CidFont>();
+            font.default_vertical_metrics(880.0, -1000.0);
+            font.vertical_widths()
+                .consecutive(120, [(-500.0, 250.0, 880.0), (-1000.0, 500.0, 880.0)])
+                .same(630, 640, -1000.0, 500.0, 880.0);
+        },
+        b"<<\n",
+        b"  /Type /Font\n",
+        b"  /DW2 [880 -1000]\n",
+        b"  /W2 [120 [-500 250 880 -1000 500 880] 630 640 -1000 500 880]\n",
+        b">>",
+    );
+}
+
+#[test]
+fn test_widths_builder() {
+    let widths = WidthsBuilder::from_iter([
+        (5, 600.0),
+        (1, 500.0),
+        (2, 500.0),
+        (3, 250.0),
+        (4, 250.0),
+        (6, 600.0),
+        (7, 600.0),
+        (8, 600.0),
+        (20, 500.0),
+        (21, 100.0),
+        (30, 500.0),
+        (31, 500.0),
+    ]);
+    assert_eq!(widths.default_width(), 500.0);
+    assert_eq!(WidthsBuilder::new().default_width(), 1000.0);
+    assert_eq!(WidthsBuilder::from_iter([(3, 1.0), (1, 2.0)]).default_width(), 2.0);
+
+    test_obj!(
+        |obj| obj.start::<CidFont>().widths_from(&widths),
+        b"<<\n",
+        b"  /Type /Font\n",
+        b"  /DW 500\n",
+        b"  /W [3 4 250 5 8 600 21 [100]]\n",
+        b">>",
+    );
+
+    // A default width between two other widths is cheaper to include.
+    let widths = WidthsBuilder::from_iter([
+        (1, 250.0),
+        (2, 500.0),
+        (3, 250.0),
+        (10, 500.0),
+        (11, 500.0),
+    ]);
+    test_obj!(
+        |obj| obj.start::<CidFont>().widths_from(&widths),
+        b"<<\n",
+        b"  /Type /Font\n",
+        b"  /DW 500\n",
+        b"  /W [1 [250 500 250]]\n",
+        b">>",
+    );
+
+    // Check that random widths are described losslessly.
+    let mut state = 7u32;
+    let mut random = |max: u32| {
+        state = state.wrapping_mul(1103515245).wrapping_add(12345);
+        (state >> 16) % max
+    };
+
+    for _ in 0..50 {
+        let mut builder = WidthsBuilder::new();
+        let mut expected = std::collections::BTreeMap::new();
+        for _ in 0..random(200) {
+            let cid = random(300) as u16;
+            let width = [250.0, 500.0, 600.0, 1000.0][random(4) as usize];
+            builder.insert(cid, width);
+            expected.insert(cid, width);
+        }
+
+        let buf = slice_obj(|obj| {
+            obj.start::<CidFont>().widths_from(&builder);
+        });
+        let text = String::from_utf8(buf).unwrap();
+        let default: f32 = text
+            .lines()
+            .find_map(|line| line.strip_prefix("  /DW "))
+            .unwrap()
+            .parse()
+            .unwrap();
+
+        let mut described = std::collections::BTreeMap::new();
+        if let Some(array) = text.lines().find_map(|line| line.strip_prefix("  /W ")) {
+            let array = array.replace('[', " [ ").replace(']', " ] ");
+            let tokens: Vec<&str> = array.split_whitespace().collect();
+            let mut i = 1;
+            while tokens[i] != "]" {
+                let first: u16 = tokens[i].parse().unwrap();
+                if tokens[i + 1] == "[" {
+                    i += 2;
+                    let mut cid = first;
+                    while tokens[i] != "]" {
+                        described.insert(cid, tokens[i].parse::<f32>().unwrap());
+                        cid += 1;
+                        i += 1;
+                    }
+                    i += 1;
+                } else {
+                    let last: u16 = tokens[i + 1].parse().unwrap();
+                    let width: f32 = tokens[i + 2].parse().unwrap();
+                    for cid in first..=last {
+                        described.insert(cid, width);
+                    }
+                    i += 3;
+                }
+            }
+        }
+
+        for (cid, width) in &expected {
+            assert_eq!(described.get(cid).copied().unwrap_or(default), *width);
+        }
+        assert!(described.keys().all(|cid| expected.contains_key(cid)));
+    }
+}
+
+#[test]
+fn test_subset_tag() {
+    let tag = SubsetTag::new(b"ABCDEF").unwrap();
+    assert_eq!(tag.as_bytes(), b"ABCDEF");
+    assert_eq!(SubsetTag::new(b"ABCDE"), None);
+    assert_eq!(SubsetTag::new(b"ABCDEFG"), None);
+    assert_eq!(SubsetTag::new(b"abcdef"), None);
+    assert_eq!(SubsetTag::new(b"ABC1EF"), None);
+
+    for seed in [0, 1, 2, u64::MAX] {
+        let derived = SubsetTag::derive(seed);
+        assert_eq!(SubsetTag::new(derived.as_bytes()), Some(derived));
+        assert_eq!(SubsetTag::derive(seed), derived);
+    }
+    assert_ne!(SubsetTag::derive(1), SubsetTag::derive(2));
+    assert_eq!(SubsetTag::from_data(b"font"), SubsetTag::from_data(b"font"));
+
+    test!(
+        slice(|w| {
+            w.type0_font(Ref::new(1)).base_font_subset(tag, Name(b"Noto-Sans"));
+            w.font_descriptor(Ref::new(2)).name_subset(tag, Name(b"Noto-Sans"));
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Font\n",
+        b"  /Subtype /Type0\n",
+        b"  /BaseFont /ABCDEF#2BNoto#2DSans\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n<<\n",
+        b"  /Type /FontDescriptor\n",
+        b"  /FontName /ABCDEF#2BNoto#2DSans\n",
+        b">>\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_custom_primitive() {
+    struct Point(f32, f32);
+
+    impl Primitive for Point {
+        fn write(self, buf: &mut Vec<u8>) {
+            Rect::new(self.0, self.1, self.0, self.1).write(buf);
+        }
+    }
+
+    test_obj!(
+        |obj| obj.dict().pair(Name(b"At"), Point(1.0, 2.0)).pair(Name(b"N"), 3),
+        b"<<\n  /At [1 2 1 2]\n  /N 3\n>>",
+    );
+    test_obj!(|obj| obj.array().item(Point(0.0, 0.5)), b"[[0 0.5 0 0.5]]");
+}
+
+#[test]
+fn test_rects() {
+    let rect = Rect::from_xywh(10.0, 20.0, 30.0, 40.0);
+    assert_eq!(rect, Rect::new(10.0, 20.0, 40.0, 60.0));
+    assert_eq!((rect.width(), rect.height()), (30.0, 40.0));
+
+    let swapped = Rect::new(40.0, 60.0, 10.0, 20.0);
+    assert_eq!((swapped.width(), swapped.height()), (30.0, 40.0));
+    assert_eq!(swapped.normalized(), rect);
+    assert!(swapped.contains(10.0, 60.0));
+    assert!(!swapped.contains(9.0, 30.0));
+
+    let other = Rect::new(30.0, 0.0, 50.0, 30.0);
+    assert_eq!(rect.intersect(other), Some(Rect::new(30.0, 20.0, 40.0, 30.0)));
+    assert_eq!(swapped.union(other), Rect::new(10.0, 0.0, 50.0, 60.0));
+    assert_eq!(rect.intersect(Rect::new(50.0, 0.0, 60.0, 10.0)), None);
+
+    // Degenerate rectangles have no area, but still intersect and contain
+    // points on their boundary.
+    let line = Rect::new(40.0, 30.0, 40.0, 80.0);
+    assert_eq!((line.width(), line.height()), (0.0, 50.0));
+    assert_eq!(rect.intersect(line), Some(Rect::new(40.0, 30.0, 40.0, 60.0)));
+    assert!(line.contains(40.0, 50.0));
+    let point = Rect::new(5.0, 5.0, 5.0, 5.0);
+    assert_eq!(point.normalized(), point);
+    assert_eq!(point.intersect(point), Some(point));
+    assert_eq!(point.union(rect), Rect::new(5.0, 5.0, 40.0, 60.0));
+
+    test_obj!(
+        |obj| obj.start::<Page>().media_box(swapped),
+        b"<<\n  /Type /Page\n  /MediaBox [10 20 40 60]\n>>",
+    );
 }
 
 #[test]
@@ -200,6 +1015,40 @@ fn test_dicts() {
         },
         b"<<\n  /A 1\n  /B 2\n>>",
     );
+    test_obj!(
+        |obj| obj.dict().pair(Name(b"A"), 1).pair(Name(b"B"), true),
+        b"<<\n  /A 1\n  /B true\n>>",
+    );
+    test_obj!(
+        |obj| obj.start::<Page>().pair_null(Name(b"Rotate")),
+        b"<<\n  /Type /Page\n  /Rotate null\n>>",
+    );
+}
+
+#[test]
+fn test_deep_indentation() {
+    fn nest(obj: Obj, depth: usize) {
+        let mut dict = obj.dict();
+        dict.pair(Name(b"A"), 1);
+        if depth > 0 {
+            nest(dict.insert(Name(b"B")), depth - 1);
+        }
+    }
+
+    let depth = 100;
+    let mut expected = b"<<".to_vec();
+    for level in 1..=depth + 1 {
+        let indent = " ".repeat(2 * level);
+        expected.extend(format!("\n{indent}/A 1").bytes());
+        if level <= depth {
+            expected.extend(format!("\n{indent}/B <<").bytes());
+        }
+    }
+    for level in (0..=depth).rev() {
+        expected.extend(format!("\n{}>>", " ".repeat(2 * level)).bytes());
+    }
+
+    assert_eq!(slice_obj(|obj| nest(obj, depth)), expected);
 }
 
 #[test]
@@ -221,7 +1070,7 @@ fn test_annotations() {
         b"    /Rect [0 0 1 1]\n",
         b"  >> <<\n",
         b"    /Type /Annot\n",
-        b"    /Rect [1 1 0 0]\n",
+        b"    /Rect [0 0 1 1]\n",
         b"  >>]\n",
         b"  /BleedBox [-100 -100 100 100]\n",
         b">>\n",
@@ -230,20 +1079,1522 @@ fn test_annotations() {
 }
 
 #[test]
-fn test_streams() {
-    let mut w = PdfWriter::new();
-    w.stream(Ref::new(1), &b"Hi there!"[..]).filter(Filter::Crypt);
+fn test_color() {
     test!(
-        w.finish(),
-        b"%PDF-1.7\n%\x80\x80\x80\x80\n\n",
+        slice(|w| {
+            let mut annot = w.annotation(Ref::new(1));
+            annot
+                .color(Color::Cmyk(0.0, 0.5, 1.0, 0.0))
+                .interior_color(Color::Gray(0.5));
+            annot
+                .appearance_characteristics()
+                .border_color(Color::Transparent)
+                .background_color(Color::Rgb(1.0, 1.0, 0.0));
+            annot.finish();
+            w.outline_item(Ref::new(2)).color(OpaqueColor::Gray(0.25));
+            let mut elem = w.struct_element(Ref::new(3));
+            let mut attrs = elem.attributes();
+            attrs
+                .push()
+                .layout()
+                .color(OpaqueColor::Cmyk(0.0, 0.0, 0.0, 0.5))
+                .border_color([0.0, 0.0, 1.0]);
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Annot\n",
+        b"  /C [0 0.5 1 0]\n",
+        b"  /IC [0.5]\n",
+        b"  /MK <<\n    /BC []\n    /BG [1 1 0]\n  >>\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n<<\n  /C [0.25 0.25 0.25]\n>>\nendobj\n\n",
+        b"3 0 obj\n<<\n",
+        b"  /Type /StructElem\n",
+        b"  /A [<<\n",
+        b"    /O /Layout\n",
+        b"    /Color [0.5 0.5 0.5]\n",
+        b"    /BorderColor [0 0 1]\n",
+        b"  >>]\n",
+        b">>\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_annotation_colors() {
+    test!(
+        slice(|w| {
+            let mut page = w.page(Ref::new(1));
+            let mut annots = page.annotations();
+            annots.push().color_transparent().interior_color_transparent();
+            annots.push().color_gray(0.5).interior_color_gray(1.0);
+            annots
+                .push()
+                .color_rgb(1.0, 0.0, 0.0)
+                .interior_color_rgb(0.0, 1.0, 0.0);
+            annots
+                .push()
+                .color_cmyk(0.0, 0.0, 0.0, 1.0)
+                .interior_color_cmyk(1.0, 0.0, 0.0, 0.0);
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Page\n",
+        b"  /Annots [<<\n",
+        b"    /Type /Annot\n    /C []\n    /IC []\n",
+        b"  >> <<\n",
+        b"    /Type /Annot\n    /C [0.5]\n    /IC [1]\n",
+        b"  >> <<\n",
+        b"    /Type /Annot\n    /C [1 0 0]\n    /IC [0 1 0]\n",
+        b"  >> <<\n",
+        b"    /Type /Annot\n    /C [0 0 0 1]\n    /IC [1 0 0 0]\n",
+        b"  >>]\n",
+        b">>\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_annotation_common_fields() {
+    test!(
+        slice(|w| {
+            w.annotation(Ref::new(1))
+                .subtype(AnnotationType::Link)
+                .rect(Rect::new(0.0, 0.0, 10.0, 10.0))
+                .name(TextStr("link-1"))
+                .modified(Date::new(2022).month(4).day(1))
+                .appearance_state(Name(b"Off"))
+                .struct_parent(3);
+        }),
         b"1 0 obj\n",
-        b"<<\n  /Length 9\n  /Filter /Crypt\n>>\n",
-        b"stream\n",
-        b"Hi there!\n",
-        b"endstream\n",
+        b"<<\n",
+        b"  /Type /Annot\n",
+        b"  /Subtype /Link\n",
+        b"  /Rect [0 0 10 10]\n",
+        b"  /NM (link-1)\n",
+        b"  /M (D:20220401)\n",
+        b"  /AS /Off\n",
+        b"  /StructParent 3\n",
+        b">>\n",
         b"endobj\n\n",
-        b"xref\n",
-        b"0 2\n",
+    );
+}
+
+#[test]
+fn test_3d_annotation() {
+    test!(
+        slice(|w| {
+            let mut annot = w.annotation(Ref::new(1));
+            annot.subtype(AnnotationType::ThreeD).stream_3d(Ref::new(2));
+            let mut view = annot.view_3d();
+            view.external_name(TextStr("Front"))
+                .camera_to_world([
+                    1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, -5.0,
+                ])
+                .center_of_orbit(5.0);
+            view.projection()
+                .subtype(Projection3DType::Perspective)
+                .field_of_view(30.0);
+            view.background().color(1.0, 1.0, 1.0);
+            view.finish();
+            annot
+                .activation_3d()
+                .activation(ActivationTrigger3D::PageOpen)
+                .activation_state(ArtworkState3D::Live)
+                .toolbar(false);
+            annot.finish();
+            w.stream_3d(Ref::new(2), b"PRC\0").subtype(Stream3DType::PRC);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Annot\n",
+        b"  /Subtype /3D\n",
+        b"  /3DD 2 0 R\n",
+        b"  /3DV <<\n",
+        b"    /Type /3DView\n",
+        b"    /XN (Front)\n",
+        b"    /MS /M\n",
+        b"    /C2W [1 0 0 0 1 0 0 0 1 0 0 -5]\n",
+        b"    /CO 5\n",
+        b"    /P <<\n",
+        b"      /Subtype /P\n",
+        b"      /FOV 30\n",
+        b"    >>\n",
+        b"    /BG <<\n",
+        b"      /Type /3DBG\n",
+        b"      /C [1 1 1]\n",
+        b"    >>\n",
+        b"  >>\n",
+        b"  /3DA <<\n",
+        b"    /A /PO\n",
+        b"    /AIS /L\n",
+        b"    /TB false\n",
+        b"  >>\n",
+        b">>\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"<<\n",
+        b"  /Length 4\n",
+        b"  /Type /3D\n",
+        b"  /Subtype /PRC\n",
+        b">>\n",
+        b"stream\n",
+        b"PRC\0\n",
+        b"endstream\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_3d_stream_default_view() {
+    test!(
+        slice(|w| {
+            w.annotation(Ref::new(1))
+                .subtype(AnnotationType::ThreeD)
+                .stream_3d(Ref::new(2))
+                .view_3d_name(TextStr("top"));
+            let mut stream = w.stream_3d(Ref::new(2), b"U3D");
+            stream.subtype(Stream3DType::U3D);
+            let mut views = stream.views();
+            views
+                .push()
+                .external_name(TextStr("Front"))
+                .internal_name(TextStr("front"));
+            views
+                .push()
+                .external_name(TextStr("Top"))
+                .internal_name(TextStr("top"));
+            views.finish();
+            stream.default_view_index(1);
+            stream.animation().subtype(AnimationType3D::Oscillating).play_count(2);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Annot\n",
+        b"  /Subtype /3D\n",
+        b"  /3DD 2 0 R\n",
+        b"  /3DV (top)\n",
+        b">>\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"<<\n",
+        b"  /Length 3\n",
+        b"  /Type /3D\n",
+        b"  /Subtype /U3D\n",
+        b"  /VA [<<\n",
+        b"    /Type /3DView\n",
+        b"    /XN (Front)\n",
+        b"    /IN (front)\n",
+        b"  >> <<\n",
+        b"    /Type /3DView\n",
+        b"    /XN (Top)\n",
+        b"    /IN (top)\n",
+        b"  >>]\n",
+        b"  /DV 1\n",
+        b"  /AN <<\n",
+        b"    /Type /3DAnimationStyle\n",
+        b"    /Subtype /Oscillating\n",
+        b"    /PC 2\n",
+        b"  >>\n",
+        b">>\n",
+        b"stream\n",
+        b"U3D\n",
+        b"endstream\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_media_annotations() {
+    test!(
+        slice(|w| {
+            let mut annot = w.annotation(Ref::new(1));
+            annot.subtype(AnnotationType::Screen).page(Ref::new(5));
+            let mut action = annot.action();
+            action
+                .action_type(ActionType::Rendition)
+                .operation(RenditionOperation::Play)
+                .screen_annotation(Ref::new(1));
+            action
+                .rendition()
+                .subtype(RenditionType::Media)
+                .media_clip()
+                .data_ref(Ref::new(3))
+                .content_type(Str(b"video/mp4"));
+            action.finish();
+            annot.finish();
+
+            let mut annot = w.annotation(Ref::new(2));
+            annot.subtype(AnnotationType::RichMedia);
+            let mut content = annot.rich_media_content();
+            content.assets().names().insert(Str(b"clip.mp4"), Ref::new(3));
+            content
+                .configurations()
+                .push()
+                .subtype(RichMediaType::Video)
+                .instances()
+                .push()
+                .subtype(RichMediaType::Video)
+                .asset(Ref::new(3));
+            content.finish();
+            annot
+                .rich_media_settings()
+                .activation(RichMediaActivation::PageVisible)
+                .deactivation(RichMediaDeactivation::PageInvisible);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Annot\n",
+        b"  /Subtype /Screen\n",
+        b"  /P 5 0 R\n",
+        b"  /A <<\n",
+        b"    /Type /Action\n",
+        b"    /S /Rendition\n",
+        b"    /OP 0\n",
+        b"    /AN 1 0 R\n",
+        b"    /R <<\n",
+        b"      /Type /Rendition\n",
+        b"      /S /MR\n",
+        b"      /C <<\n",
+        b"        /Type /MediaClip\n",
+        b"        /S /MCD\n",
+        b"        /D 3 0 R\n",
+        b"        /CT (video/mp4)\n",
+        b"      >>\n",
+        b"    >>\n",
+        b"  >>\n",
+        b">>\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"<<\n",
+        b"  /Type /Annot\n",
+        b"  /Subtype /RichMedia\n",
+        b"  /RichMediaContent <<\n",
+        b"    /Type /RichMediaContent\n",
+        b"    /Assets <<\n",
+        b"      /Names [(clip.mp4) 3 0 R]\n",
+        b"    >>\n",
+        b"    /Configurations [<<\n",
+        b"      /Type /RichMediaConfiguration\n",
+        b"      /Subtype /Video\n",
+        b"      /Instances [<<\n",
+        b"        /Type /RichMediaInstance\n",
+        b"        /Subtype /Video\n",
+        b"        /Asset 3 0 R\n",
+        b"      >>]\n",
+        b"    >>]\n",
+        b"  >>\n",
+        b"  /RichMediaSettings <<\n",
+        b"    /Type /RichMediaSettings\n",
+        b"    /Activation <<\n",
+        b"      /Type /RichMediaActivation\n",
+        b"      /Condition /PV\n",
+        b"    >>\n",
+        b"    /Deactivation <<\n",
+        b"      /Type /RichMediaDeactivation\n",
+        b"      /Condition /PI\n",
+        b"    >>\n",
+        b"  >>\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_page_separation_info() {
+    test!(
+        slice(|w| {
+            w.page(Ref::new(1))
+                .separation_info()
+                .pages([Ref::new(1), Ref::new(2)])
+                .device_colorant(Name(b"Cyan"))
+                .color_space_ref(Ref::new(3));
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Page\n",
+        b"  /SeparationInfo <<\n",
+        b"    /Pages [1 0 R 2 0 R]\n",
+        b"    /DeviceColorant /Cyan\n",
+        b"    /ColorSpace 3 0 R\n",
+        b"  >>\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_box_color_info() {
+    test!(
+        slice(|w| {
+            let mut page = w.page(Ref::new(1));
+            page.trim_box(Rect::new(10.0, 10.0, 200.0, 300.0));
+            page.box_color_info()
+                .trim_box()
+                .color(1.0, 0.0, 0.0)
+                .width(0.5)
+                .style(GuideStyle::Dashed)
+                .dashes([3.0, 2.0]);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Page\n",
+        b"  /TrimBox [10 10 200 300]\n",
+        b"  /BoxColorInfo <<\n",
+        b"    /TrimBox <<\n",
+        b"      /C [1 0 0]\n",
+        b"      /W 0.5\n",
+        b"      /S /D\n",
+        b"      /D [3 2]\n",
+        b"    >>\n",
+        b"  >>\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_redact_annotation() {
+    test!(
+        slice(|w| {
+            w.annotation(Ref::new(1))
+                .subtype(AnnotationType::Redact)
+                .quad_points_rects([Rect::new(10.0, 20.0, 110.0, 32.0)])
+                .interior_color_rgb(0.0, 0.0, 0.0)
+                .overlay_text(TextStr("Redacted"))
+                .repeat(false)
+                .default_appearance(Str(b"/Helv 10 Tf 1 g"))
+                .quadding(Quadding::Center);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Annot\n",
+        b"  /Subtype /Redact\n",
+        b"  /QuadPoints [10 20 110 20 110 32 10 32]\n",
+        b"  /IC [0 0 0]\n",
+        b"  /OverlayText (Redacted)\n",
+        b"  /Repeat false\n",
+        b"  /DA (/Helv 10 Tf 1 g)\n",
+        b"  /Q 1\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_watermark_annotation() {
+    test!(
+        slice(|w| {
+            let mut annot = w.annotation(Ref::new(1));
+            annot.subtype(AnnotationType::Watermark);
+            annot.appearance().normal(Ref::new(2));
+            annot
+                .fixed_print()
+                .matrix([1.0, 0.0, 0.0, 1.0, 0.0, 0.0])
+                .horizontal(0.5)
+                .vertical(0.25);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Annot\n",
+        b"  /Subtype /Watermark\n",
+        b"  /AP <<\n",
+        b"    /N 2 0 R\n",
+        b"  >>\n",
+        b"  /FixedPrint <<\n",
+        b"    /Type /FixedPrint\n",
+        b"    /Matrix [1 0 0 1 0 0]\n",
+        b"    /H 0.5\n",
+        b"    /V 0.25\n",
+        b"  >>\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_large_stream() {
+    let data: Vec<u8> = (0..1024 * 1024).map(|i| (i % 251) as u8).collect();
+    let mut w = PdfWriter::new();
+    w.stream(Ref::new(1), &data);
+    let buf = w.into_buf();
+    let start = buf.windows(7).position(|w| w == b"stream\n").unwrap() + 7;
+    assert_eq!(&buf[start..start + data.len()], data.as_slice());
+    assert!(buf[start + data.len()..].starts_with(b"\nendstream\n"));
+}
+
+#[test]
+fn test_capacity() {
+    let mut w = PdfWriter::with_capacity(1024);
+    assert!(w.capacity() >= 1024);
+    w.reserve(4096);
+    assert!(w.capacity() >= w.len() + 4096);
+}
+
+#[test]
+fn test_caret_annotation() {
+    test!(
+        slice(|w| {
+            w.annotation(Ref::new(1))
+                .subtype(AnnotationType::Caret)
+                .rect(Rect::new(100.0, 100.0, 110.0, 115.0))
+                .paragraph_symbol(true)
+                .rect_differences(1.0, 1.0, 1.0, 1.0);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Annot\n",
+        b"  /Subtype /Caret\n",
+        b"  /Rect [100 100 110 115]\n",
+        b"  /Sy /P\n",
+        b"  /RD [1 1 1 1]\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_free_text_annotation() {
+    let mut da = DefaultAppearance::new();
+    da.font(Name(b"Helv"), 12.0).rgb(1.0, 0.0, 0.0);
+    let da = da.finish();
+    test!(
+        slice(|w| {
+            w.annotation(Ref::new(1))
+                .subtype(AnnotationType::FreeText)
+                .contents(TextStr("Check this"))
+                .free_text_intent(FreeTextIntent::Callout)
+                .callout_line((50.0, 50.0), Some((80.0, 80.0)), (100.0, 80.0))
+                .default_appearance(Str(&da))
+                .quadding(Quadding::Right)
+                .cloudy_border(1.0);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Annot\n",
+        b"  /Subtype /FreeText\n",
+        b"  /Contents (Check this)\n",
+        b"  /IT /FreeTextCallout\n",
+        b"  /CL [50 50 80 80 100 80]\n",
+        b"  /DA (/Helv 12 Tf\n1 0 0 rg)\n",
+        b"  /Q 2\n",
+        b"  /BE <<\n",
+        b"    /S /C\n",
+        b"    /I 1\n",
+        b"  >>\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_legacy_multimedia() {
+    test!(
+        slice(|w| {
+            w.sound(Ref::new(1), &[0x80, 0x7f])
+                .rate(8000.0)
+                .channels(1)
+                .bits_per_sample(8)
+                .encoding(SoundEncoding::Raw);
+            let mut annot = w.annotation(Ref::new(2));
+            annot.subtype(AnnotationType::Movie);
+            annot.movie().file_spec().path(Str(b"clip.mov"));
+            annot
+                .movie_activation()
+                .show_controls(true)
+                .mode(MoviePlayMode::Repeat);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Length 2\n",
+        b"  /Type /Sound\n",
+        b"  /R 8000\n",
+        b"  /C 1\n",
+        b"  /B 8\n",
+        b"  /E /Raw\n",
+        b">>\n",
+        b"stream\n",
+        b"\x80\x7f\n",
+        b"endstream\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"<<\n",
+        b"  /Type /Annot\n",
+        b"  /Subtype /Movie\n",
+        b"  /Movie <<\n",
+        b"    /F <<\n",
+        b"      /Type /Filespec\n",
+        b"      /F (clip.mov)\n",
+        b"    >>\n",
+        b"  >>\n",
+        b"  /A <<\n",
+        b"    /ShowControls true\n",
+        b"    /Mode /Repeat\n",
+        b"  >>\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_piece_info() {
+    test!(
+        slice(|w| {
+            let date = Date::new(2023).month(4).day(1);
+            let mut page = w.page(Ref::new(1));
+            page.last_modified(date);
+            let mut piece_info = page.piece_info();
+            piece_info
+                .application(Name(b"ACME:Layout"), date)
+                .private()
+                .dict()
+                .pair(Name(b"Grid"), 12);
+            piece_info
+                .application(Name(b"ACME:Notes"), date)
+                .private()
+                .primitive(Ref::new(2));
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Page\n",
+        b"  /LastModified (D:20230401)\n",
+        b"  /PieceInfo <<\n",
+        b"    /ACME#3ALayout <<\n",
+        b"      /LastModified (D:20230401)\n",
+        b"      /Private <<\n",
+        b"        /Grid 12\n",
+        b"      >>\n",
+        b"    >>\n",
+        b"    /ACME#3ANotes <<\n",
+        b"      /LastModified (D:20230401)\n",
+        b"      /Private 2 0 R\n",
+        b"    >>\n",
+        b"  >>\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_prepress_trapping_and_opi() {
+    test!(
+        slice(|w| {
+            w.annotation(Ref::new(1))
+                .subtype(AnnotationType::TrapNet)
+                .version([Ref::new(3)])
+                .annotation_states([Some(Name(b"On")), None])
+                .font_fauxing([Ref::new(4)]);
+            let mut image = w.image_xobject(Ref::new(2), &[]);
+            let mut opi = image.opi();
+            let mut v13 = opi.version_13();
+            v13.file_spec().path(Str(b"photo.tif"));
+            v13.size(2000, 1000)
+                .crop_rect(0, 0, 2000, 1000)
+                .position([0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0])
+                .color_type(OpiColorType::Process);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Annot\n",
+        b"  /Subtype /TrapNet\n",
+        b"  /Version [3 0 R]\n",
+        b"  /AnnotStates [/On null]\n",
+        b"  /FontFauxing [4 0 R]\n",
+        b">>\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"<<\n",
+        b"  /Length 0\n",
+        b"  /Type /XObject\n",
+        b"  /Subtype /Image\n",
+        b"  /OPI <<\n",
+        b"    /1#2E3 <<\n",
+        b"      /Type /OPI\n",
+        b"      /Version 1.3\n",
+        b"      /F <<\n",
+        b"        /Type /Filespec\n",
+        b"        /F (photo.tif)\n",
+        b"      >>\n",
+        b"      /Size [2000 1000]\n",
+        b"      /CropRect [0 0 2000 1000]\n",
+        b"      /Position [0 1 1 1 1 0 0 0]\n",
+        b"      /ColorType /Process\n",
+        b"    >>\n",
+        b"  >>\n",
+        b">>\n",
+        b"stream\n",
+        b"\n",
+        b"endstream\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_push_button_appearance_characteristics() {
+    test!(
+        slice(|w| {
+            let mut annot = w.annotation(Ref::new(1));
+            annot.subtype(AnnotationType::Widget);
+            let mut mk = annot.appearance_characteristics();
+            mk.border_color_gray(0.0)
+                .background_color_rgb(1.0, 1.0, 0.0)
+                .normal_caption(TextStr("Submit"))
+                .normal_icon(Ref::new(2))
+                .text_position(TextPosition::Below);
+            mk.icon_fit()
+                .scale_when(IconScaleWhen::Bigger)
+                .proportional(false)
+                .alignment(0.0, 0.5)
+                .fit_bounds(true);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Annot\n",
+        b"  /Subtype /Widget\n",
+        b"  /MK <<\n",
+        b"    /BC [0]\n",
+        b"    /BG [1 1 0]\n",
+        b"    /CA (Submit)\n",
+        b"    /I 2 0 R\n",
+        b"    /TP 2\n",
+        b"    /IF <<\n",
+        b"      /SW /B\n",
+        b"      /S /A\n",
+        b"      /A [0 0.5]\n",
+        b"      /FB true\n",
+        b"    >>\n",
+        b"  >>\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_form_actions() {
+    test!(
+        slice(|w| {
+            w.action(Ref::new(1))
+                .action_type(ActionType::SubmitForm)
+                .submit_url(Str(b"https://example.com/submit"))
+                .fields([Ref::new(3)])
+                .submit_flags(SubmitFlags::INCLUDE_NO_VALUE_FIELDS);
+            w.action(Ref::new(2))
+                .action_type(ActionType::ResetForm)
+                .fields([Ref::new(3), Ref::new(4)])
+                .reset_exclude(false);
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Action\n",
+        b"  /S /SubmitForm\n",
+        b"  /F <<\n",
+        b"    /Type /Filespec\n",
+        b"    /FS /URL\n",
+        b"    /F (https://example.com/submit)\n",
+        b"  >>\n",
+        b"  /Fields [3 0 R]\n",
+        b"  /Flags 2\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n<<\n",
+        b"  /Type /Action\n",
+        b"  /S /ResetForm\n",
+        b"  /Fields [3 0 R 4 0 R]\n",
+        b"  /Flags 0\n",
+        b">>\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_hide_and_ocg_state_actions() {
+    test!(
+        slice(|w| {
+            w.action(Ref::new(1))
+                .action_type(ActionType::Hide)
+                .hide_target_field(TextStr("details.address"))
+                .hide(false);
+            let mut action = w.action(Ref::new(2));
+            action.action_type(ActionType::SetOcgState);
+            action.ocg_state().on([Ref::new(3)]).off([Ref::new(4)]);
+            action.preserve_radio_buttons(false);
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Action\n",
+        b"  /S /Hide\n",
+        b"  /T (details.address)\n",
+        b"  /H false\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n<<\n",
+        b"  /Type /Action\n",
+        b"  /S /SetOCGState\n",
+        b"  /State [/ON 3 0 R /OFF 4 0 R]\n",
+        b"  /PreserveRB false\n",
+        b">>\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_transition_action() {
+    test!(
+        slice(|w| {
+            w.action(Ref::new(1))
+                .action_type(ActionType::Trans)
+                .transition()
+                .style(TransitionStyle::Dissolve)
+                .duration(0.5);
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Action\n",
+        b"  /S /Trans\n",
+        b"  /Trans <<\n",
+        b"    /Type /Trans\n",
+        b"    /S /Dissolve\n",
+        b"    /D 0.5\n",
+        b"  >>\n",
+        b">>\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_certification_signature() {
+    test!(
+        slice(|w| {
+            w.catalog(Ref::new(1)).permissions().doc_mdp(Ref::new(3));
+            w.form_field(Ref::new(2))
+                .field_type(FieldType::Signature)
+                .partial_name(TextStr("Certification"))
+                .signature_value(Ref::new(3));
+
+            let mut signature = w.signature(Ref::new(3));
+            signature
+                .filter(Name(b"Adobe.PPKLite"))
+                .sub_filter(Name(b"adbe.pkcs7.detached"))
+                .byte_range([(0, 100), (200, 50)])
+                .contents(Str(&[0; 4]));
+            let mut reference = signature.reference();
+            reference
+                .push()
+                .transform_method(TransformMethod::DocMdp)
+                .transform_params()
+                .permissions(MdpPermissions::NoChanges)
+                .version(1, 2);
+            let mut lock = reference.push();
+            lock.transform_method(TransformMethod::FieldMdp).data(Ref::new(1));
+            lock.transform_params()
+                .field_action(FieldMdpAction::Include)
+                .fields([TextStr("total")]);
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Catalog\n",
+        b"  /Perms <<\n    /DocMDP 3 0 R\n  >>\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n<<\n",
+        b"  /FT /Sig\n",
+        b"  /T (Certification)\n",
+        b"  /V 3 0 R\n",
+        b">>\nendobj\n\n",
+        b"3 0 obj\n<<\n",
+        b"  /Type /Sig\n",
+        b"  /Filter /Adobe#2EPPKLite\n",
+        b"  /SubFilter /adbe#2Epkcs7#2Edetached\n",
+        b"  /ByteRange [0 100 200 50]\n",
+        b"  /Contents (\0\0\0\0)\n",
+        b"  /Reference [<<\n",
+        b"    /Type /SigRef\n",
+        b"    /TransformMethod /DocMDP\n",
+        b"    /TransformParams <<\n",
+        b"      /Type /TransformParams\n",
+        b"      /P 1\n",
+        b"      /V /1#2E2\n",
+        b"    >>\n",
+        b"  >> <<\n",
+        b"    /Type /SigRef\n",
+        b"    /TransformMethod /FieldMDP\n",
+        b"    /Data 1 0 R\n",
+        b"    /TransformParams <<\n",
+        b"      /Type /TransformParams\n",
+        b"      /Action /Include\n",
+        b"      /Fields [(total)]\n",
+        b"    >>\n",
+        b"  >>]\n",
+        b">>\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_structure_destination() {
+    test!(
+        slice(|w| {
+            let mut action = w.action(Ref::new(1));
+            action.action_type(ActionType::GoTo);
+            action.destination().page(Ref::new(2)).fit_horizontal(700.0);
+            action
+                .structure_destination()
+                .struct_element(Ref::new(3))
+                .xyz(0.0, 0.0, None);
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Action\n",
+        b"  /S /GoTo\n",
+        b"  /D [2 0 R /FitH 700]\n",
+        b"  /SD [3 0 R /XYZ 0 0 0]\n",
+        b">>\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_document_javascript() {
+    test!(
+        slice(|w| {
+            w.catalog(Ref::new(1))
+                .names()
+                .javascript()
+                .names()
+                .insert(Str(b"init"), Ref::new(2));
+            w.javascript(Ref::new(2)).js_stream(Ref::new(3));
+            w.stream(Ref::new(3), b"app.alert('Hi');");
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Catalog\n",
+        b"  /Names <<\n",
+        b"    /JavaScript <<\n",
+        b"      /Names [(init) 2 0 R]\n",
+        b"    >>\n",
+        b"  >>\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n<<\n",
+        b"  /Type /Action\n",
+        b"  /S /JavaScript\n",
+        b"  /JS 3 0 R\n",
+        b">>\nendobj\n\n",
+        b"3 0 obj\n<<\n  /Length 16\n>>\nstream\n",
+        b"app.alert('Hi');\nendstream\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_screen_annotation() {
+    test!(
+        slice(|w| {
+            let mut annot = w.annotation(Ref::new(1));
+            annot.subtype(AnnotationType::Screen).page(Ref::new(3));
+            annot
+                .appearance_characteristics()
+                .border_color_rgb(0.0, 0.0, 1.0)
+                .normal_caption(TextStr("Play"));
+            annot.action_ref(Ref::new(2));
+            annot.finish();
+            w.action(Ref::new(2))
+                .action_type(ActionType::Rendition)
+                .operation(RenditionOperation::Play)
+                .screen_annotation(Ref::new(1))
+                .rendition()
+                .subtype(RenditionType::Media)
+                .media_clip()
+                .data_ref(Ref::new(4));
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Annot\n",
+        b"  /Subtype /Screen\n",
+        b"  /P 3 0 R\n",
+        b"  /MK <<\n",
+        b"    /BC [0 0 1]\n",
+        b"    /CA (Play)\n",
+        b"  >>\n",
+        b"  /A 2 0 R\n",
+        b">>\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"<<\n",
+        b"  /Type /Action\n",
+        b"  /S /Rendition\n",
+        b"  /OP 0\n",
+        b"  /AN 1 0 R\n",
+        b"  /R <<\n",
+        b"    /Type /Rendition\n",
+        b"    /S /MR\n",
+        b"    /C <<\n",
+        b"      /Type /MediaClip\n",
+        b"      /S /MCD\n",
+        b"      /D 4 0 R\n",
+        b"    >>\n",
+        b"  >>\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_go_to_3d_view_action() {
+    test!(
+        slice(|w| {
+            w.action(Ref::new(1))
+                .action_type(ActionType::GoTo3DView)
+                .target_annotation(Ref::new(3))
+                .view_3d_relative(RelativeView3D::Next);
+            w.action(Ref::new(2))
+                .action_type(ActionType::GoTo3DView)
+                .target_annotation(Ref::new(3))
+                .view_3d()
+                .external_name(TextStr("Top"));
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Action\n",
+        b"  /S /GoTo3DView\n",
+        b"  /TA 3 0 R\n",
+        b"  /V /N\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n<<\n",
+        b"  /Type /Action\n",
+        b"  /S /GoTo3DView\n",
+        b"  /TA 3 0 R\n",
+        b"  /V <<\n",
+        b"    /Type /3DView\n",
+        b"    /XN (Top)\n",
+        b"  >>\n",
+        b">>\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_media_rendition() {
+    test!(
+        slice(|w| {
+            let mut action = w.action(Ref::new(1));
+            action.action_type(ActionType::Rendition);
+            let mut rendition = action.rendition();
+            rendition.subtype(RenditionType::Media).name(TextStr("Intro"));
+            let mut clip = rendition.media_clip();
+            clip.data().path(Str(b"intro.mp4")).embedded_file(Ref::new(2));
+            clip.content_type(Str(b"video/mp4"))
+                .temp_file(TempFilePermission::Access);
+            clip.finish();
+            let mut params = rendition.media_play_parameters();
+            params.must_honor().repeat_count(0.0);
+            params
+                .best_effort()
+                .volume(80)
+                .show_controls(true)
+                .fit(MediaFit::Meet)
+                .duration(12.5);
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Action\n",
+        b"  /S /Rendition\n",
+        b"  /R <<\n",
+        b"    /Type /Rendition\n",
+        b"    /S /MR\n",
+        b"    /N (Intro)\n",
+        b"    /C <<\n",
+        b"      /Type /MediaClip\n",
+        b"      /S /MCD\n",
+        b"      /D <<\n",
+        b"        /Type /Filespec\n",
+        b"        /F (intro.mp4)\n",
+        b"        /EF <<\n          /F 2 0 R\n        >>\n",
+        b"      >>\n",
+        b"      /CT (video/mp4)\n",
+        b"      /P <<\n        /Type /MediaPermissions\n        /TF (TEMPACCESS)\n      >>\n",
+        b"    >>\n",
+        b"    /P <<\n",
+        b"      /Type /MediaPlayParams\n",
+        b"      /MH <<\n        /RC 0\n      >>\n",
+        b"      /BE <<\n",
+        b"        /V 80\n",
+        b"        /C true\n",
+        b"        /F 0\n",
+        b"        /D <<\n",
+        b"          /Type /MediaDuration\n",
+        b"          /S /T\n",
+        b"          /T <<\n",
+        b"            /Type /Timespan\n",
+        b"            /S /S\n",
+        b"            /V 12.5\n",
+        b"          >>\n",
+        b"        >>\n",
+        b"      >>\n",
+        b"    >>\n",
+        b"  >>\n",
+        b">>\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_default_proc_set() {
+    test_obj!(
+        |obj| obj.start::<Resources>().default_proc_set(),
+        b"<<\n  /ProcSet [/PDF /Text /ImageB /ImageC /ImageI]\n>>",
+    );
+
+    // Empty resources are still written as a dictionary.
+    test_obj!(|obj| obj.start::<Resources>(), b"<<>>");
+}
+
+#[test]
+fn test_named_marked_content_properties() {
+    let mut content = Content::new();
+    content
+        .begin_marked_content_with_properties(Name(b"OC"))
+        .properties_named(Name(b"oc1"));
+    content.rect(0.0, 0.0, 10.0, 10.0).fill_nonzero();
+    content.end_marked_content();
+    let data = content.finish();
+
+    test!(
+        slice(|w| {
+            let mut page = w.page(Ref::new(1));
+            let mut resources = page.resources();
+            let mut properties = resources.properties_dict();
+            properties.pair(Name(b"oc1"), Ref::new(3));
+            properties.finish();
+            resources.finish();
+            page.contents(Ref::new(2));
+            page.finish();
+            w.stream(Ref::new(2), &data);
+            w.indirect(Ref::new(3))
+                .dict()
+                .pair(Name(b"Type"), Name(b"OCG"))
+                .pair(Name(b"Name"), TextStr("Layer"));
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Page\n",
+        b"  /Resources <<\n",
+        b"    /Properties <<\n",
+        b"      /oc1 3 0 R\n",
+        b"    >>\n",
+        b"  >>\n",
+        b"  /Contents 2 0 R\n",
+        b">>\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"<<\n",
+        b"  /Length 31\n",
+        b">>\n",
+        b"stream\n",
+        b"/OC /oc1 BDC\n0 0 10 10 re\nf\nEMC\n",
+        b"endstream\n",
+        b"endobj\n\n",
+        b"3 0 obj\n",
+        b"<<\n",
+        b"  /Type /OCG\n",
+        b"  /Name (Layer)\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_embedded_file_checksum() {
+    test!(
+        slice(|w| {
+            w.embedded_file(Ref::new(1), b"").params().checksum_from_bytes(b"");
+            w.embedded_file(Ref::new(2), b"abc")
+                .params()
+                .checksum_from_bytes(b"abc");
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Length 0\n",
+        b"  /Type /EmbeddedFile\n",
+        b"  /Params <<\n",
+        b"    /CheckSum (\xd4\x1d\x8c\xd9\x8f\x00\xb2\x04\xe9\x80\t\x98\xec\xf8B~)\n",
+        b"  >>\n",
+        b">>\n",
+        b"stream\n",
+        b"\n",
+        b"endstream\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"<<\n",
+        b"  /Length 3\n",
+        b"  /Type /EmbeddedFile\n",
+        b"  /Params <<\n",
+        b"    /CheckSum <900150983CD24FB0D6963F7D28E17F72>\n",
+        b"  >>\n",
+        b">>\n",
+        b"stream\n",
+        b"abc\n",
+        b"endstream\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_table_attributes() {
+    test!(
+        slice(|w| {
+            let mut cell = w.struct_element(Ref::new(1));
+            cell.kind(StructRole::TH).parent(Ref::new(2));
+            let mut attrs = cell.attributes_with_revisions();
+            attrs
+                .push()
+                .layout()
+                .placement(Placement::Block)
+                .writing_mode(WritingMode::LtrTtb)
+                .width(120.0)
+                .height(20.0)
+                .background_color([0.9, 0.9, 0.9]);
+            let mut table = attrs.push().table();
+            table.row_span(2).col_span(1).scope(TableHeaderScope::Column);
+            table.headers().item(Str(b"h1"));
+            table.finish();
+            attrs.revision(1);
+            attrs.indirect(Ref::new(3));
+            attrs.finish();
+            cell.finish();
+
+            w.struct_element(Ref::new(4))
+                .kind(StructRole::L)
+                .single_attributes()
+                .list()
+                .list_numbering(ListNumbering::Decimal);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /StructElem\n",
+        b"  /S /TH\n",
+        b"  /P 2 0 R\n",
+        b"  /A [<<\n",
+        b"    /O /Layout\n",
+        b"    /Placement /Block\n",
+        b"    /WritingMode /LrTb\n",
+        b"    /Width 120\n",
+        b"    /Height 20\n",
+        b"    /BackgroundColor [0.9 0.9 0.9]\n",
+        b"  >> <<\n",
+        b"    /O /Table\n",
+        b"    /RowSpan 2\n",
+        b"    /ColSpan 1\n",
+        b"    /Scope /Column\n",
+        b"    /Headers [(h1)]\n",
+        b"  >> 1 3 0 R]\n",
+        b">>\n",
+        b"endobj\n\n",
+        b"4 0 obj\n",
+        b"<<\n",
+        b"  /Type /StructElem\n",
+        b"  /S /L\n",
+        b"  /A <<\n",
+        b"    /O /List\n",
+        b"    /ListNumbering /Decimal\n",
+        b"  >>\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_next_struct_parent() {
+    let mut w = PdfWriter::new();
+    let keys: Vec<i32> = (0..5).map(|_| w.next_struct_parent()).collect();
+    assert_eq!(keys, [0, 1, 2, 3, 4]);
+
+    let key = w.next_struct_parent();
+    w.page(Ref::new(1)).struct_parents(key);
+    assert_eq!(w.next_struct_parent(), 6);
+    assert_eq!(PdfWriter::new().next_struct_parent(), 0);
+}
+
+#[test]
+fn test_accessible_figure() {
+    test!(
+        slice(|w| {
+            w.struct_element(Ref::new(1))
+                .kind(StructRole::Figure)
+                .parent(Ref::new(2))
+                .alt(TextStr("Ein Diagramm"))
+                .lang(TextStr("de-DE"))
+                .associated_files([Ref::new(3)])
+                .single_attribute_class(Name(b"Wide"));
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /StructElem\n",
+        b"  /S /Figure\n",
+        b"  /P 2 0 R\n",
+        b"  /Alt (Ein Diagramm)\n",
+        b"  /Lang (de-DE)\n",
+        b"  /AF [3 0 R]\n",
+        b"  /C /Wide\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+
+    test_obj!(
+        |obj| {
+            obj.start::<PropertyList>()
+                .identify(0)
+                .lang(TextStr("fr"))
+                .associated_files([Ref::new(3)]);
+        },
+        b"<<\n  /MCID 0\n  /Lang (fr)\n  /AF [3 0 R]\n>>",
+    );
+}
+
+#[test]
+fn test_role_and_class_map() {
+    test!(
+        slice(|w| {
+            let mut catalog = w.catalog(Ref::new(1));
+            let mut root = catalog.struct_tree_root();
+            root.child(Ref::new(2));
+            root.role_map().insert(Name(b"Heading"), StructRole::H1);
+            let mut class_map = root.class_map();
+            class_map.single(Name(b"Cell")).table().scope(TableHeaderScope::Row);
+            let mut attrs = class_map.multiple_with_revisions(Name(b"Wide"));
+            attrs.push().layout().width(300.0);
+            attrs.revision(2);
+            attrs.finish();
+            class_map.finish();
+            root.finish();
+            catalog.finish();
+
+            w.struct_element(Ref::new(2))
+                .custom_kind(Name(b"Heading"))
+                .single_attribute_class(Name(b"Wide"));
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Catalog\n",
+        b"  /StructTreeRoot <<\n",
+        b"    /Type /StructTreeRoot\n",
+        b"    /K 2 0 R\n",
+        b"    /RoleMap <<\n",
+        b"      /Heading /H1\n",
+        b"    >>\n",
+        b"    /ClassMap <<\n",
+        b"      /Cell <<\n",
+        b"        /O /Table\n",
+        b"        /Scope /Row\n",
+        b"      >>\n",
+        b"      /Wide [<<\n",
+        b"        /O /Layout\n",
+        b"        /Width 300\n",
+        b"      >> 2]\n",
+        b"    >>\n",
+        b"  >>\n",
+        b">>\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"<<\n",
+        b"  /Type /StructElem\n",
+        b"  /S /Heading\n",
+        b"  /C /Wide\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_parent_tree() {
+    test!(
+        slice(|w| {
+            w.catalog(Ref::new(1)).struct_tree_root().parent_tree_ref(Ref::new(3));
+            let mut page = w.page(Ref::new(2));
+            page.struct_parents(0);
+            page.annotations().push().struct_parent(1);
+            page.finish();
+            w.number_tree(Ref::new(3))
+                .nums()
+                .insert_refs(0, [Ref::new(4), Ref::new(5)])
+                .insert(1, Ref::new(6));
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Catalog\n",
+        b"  /StructTreeRoot <<\n",
+        b"    /Type /StructTreeRoot\n",
+        b"    /ParentTree 3 0 R\n",
+        b"  >>\n",
+        b">>\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"<<\n",
+        b"  /Type /Page\n",
+        b"  /StructParents 0\n",
+        b"  /Annots [<<\n",
+        b"    /Type /Annot\n",
+        b"    /StructParent 1\n",
+        b"  >>]\n",
+        b">>\n",
+        b"endobj\n\n",
+        b"3 0 obj\n",
+        b"<<\n",
+        b"  /Nums [0 [4 0 R 5 0 R] 1 6 0 R]\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_rich_text_annotation() {
+    test!(
+        slice(|w| {
+            w.annotation(Ref::new(1))
+                .subtype(AnnotationType::FreeText)
+                .contents(TextStr("Bold"))
+                .rich_text_ref(Ref::new(2))
+                .default_style(TextStr("font: 12pt Helvetica"))
+                .quadding(Quadding::Right);
+            w.stream(Ref::new(2), b"<body><b>Bold</b></body>");
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Annot\n",
+        b"  /Subtype /FreeText\n",
+        b"  /Contents (Bold)\n",
+        b"  /RC 2 0 R\n",
+        b"  /DS (font: 12pt Helvetica)\n",
+        b"  /Q 2\n",
+        b">>\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"<<\n",
+        b"  /Length 24\n",
+        b">>\n",
+        b"stream\n",
+        b"<body><b>Bold</b></body>\n",
+        b"endstream\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_web_capture() {
+    test!(
+        slice(|w| {
+            let mut catalog = w.catalog(Ref::new(1));
+            catalog.spider_info().version(1.0).command_refs([Ref::new(2)]);
+            catalog
+                .names()
+                .capture_urls()
+                .names()
+                .insert(Str(b"https://example.com/"), Ref::new(3));
+            catalog.finish();
+            w.web_capture_command(Ref::new(2))
+                .url(Str(b"https://example.com/"))
+                .levels(2)
+                .flags(WebCaptureFlags::SAME_SITE);
+            let mut set = w.web_capture_content_set(Ref::new(3));
+            set.subtype(WebCaptureContentType::Pages)
+                .id(Str(b"0123456789abcdef"))
+                .objects([Ref::new(4)]);
+            set.source_info()
+                .url(Str(b"https://example.com/"))
+                .command(Ref::new(2));
+            set.content_type(Str(b"text/html"));
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Catalog\n",
+        b"  /SpiderInfo <<\n",
+        b"    /V 1\n",
+        b"    /C [2 0 R]\n",
+        b"  >>\n",
+        b"  /Names <<\n",
+        b"    /URLS <<\n",
+        b"      /Names [(https://example.com/) 3 0 R]\n",
+        b"    >>\n",
+        b"  >>\n",
+        b">>\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"<<\n",
+        b"  /URL (https://example.com/)\n",
+        b"  /L 2\n",
+        b"  /F 2\n",
+        b">>\n",
+        b"endobj\n\n",
+        b"3 0 obj\n",
+        b"<<\n",
+        b"  /Type /SpiderContentSet\n",
+        b"  /S /SPS\n",
+        b"  /ID (0123456789abcdef)\n",
+        b"  /O [4 0 R]\n",
+        b"  /SI <<\n",
+        b"    /AU (https://example.com/)\n",
+        b"    /C 2 0 R\n",
+        b"  >>\n",
+        b"  /CT (text/html)\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_alternate_images() {
+    test!(
+        slice(|w| {
+            let mut image = w.image_xobject(Ref::new(1), b"");
+            image.width(1).height(1);
+            image
+                .alternate_images()
+                .push()
+                .image(Ref::new(2))
+                .default_for_printing(true);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Length 0\n",
+        b"  /Type /XObject\n",
+        b"  /Subtype /Image\n",
+        b"  /Width 1\n",
+        b"  /Height 1\n",
+        b"  /Alternates [<<\n",
+        b"    /Image 2 0 R\n",
+        b"    /DefaultForPrinting true\n",
+        b"  >>]\n",
+        b">>\n",
+        b"stream\n",
+        b"\n",
+        b"endstream\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_reference_xobject() {
+    test!(
+        slice(|w| {
+            let mut form = w.form_xobject(Ref::new(1), b"0 0 100 100 re\nS");
+            form.bbox(Rect::new(0.0, 0.0, 100.0, 100.0));
+            let mut reference = form.reference();
+            reference.file_spec().path(Str(b"other.pdf"));
+            reference.page_label(TextStr("iv")).id([Str(b"a"), Str(b"b")]);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Length 16\n",
+        b"  /Type /XObject\n",
+        b"  /Subtype /Form\n",
+        b"  /BBox [0 0 100 100]\n",
+        b"  /Ref <<\n",
+        b"    /F <<\n",
+        b"      /Type /Filespec\n",
+        b"      /F (other.pdf)\n",
+        b"    >>\n",
+        b"    /Page (iv)\n",
+        b"    /ID [(a) (b)]\n",
+        b"  >>\n",
+        b">>\n",
+        b"stream\n",
+        b"0 0 100 100 re\nS\n",
+        b"endstream\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_streams() {
+    let mut w = PdfWriter::new();
+    w.stream(Ref::new(1), &b"Hi there!"[..]).filter(Filter::Crypt);
+    test!(
+        w.into_buf(),
+        b"%PDF-1.7\n%\x80\x80\x80\x80\n\n",
+        b"1 0 obj\n",
+        b"<<\n  /Length 9\n  /Filter /Crypt\n>>\n",
+        b"stream\n",
+        b"Hi there!\n",
+        b"endstream\n",
+        b"endobj\n\n",
+        b"xref\n",
+        b"0 2\n",
         b"0000000000 65535 f\r\n",
         b"0000000016 00000 n\r\n",
         b"trailer\n",
@@ -251,3 +2602,1283 @@ fn test_streams() {
         b"startxref\n94\n%%EOF",
     )
 }
+
+#[test]
+fn test_catalog_interactive() {
+    test!(
+        slice(|w| {
+            let mut catalog = w.catalog(Ref::new(1));
+            catalog.form(Ref::new(2));
+            catalog
+                .open_action()
+                .action_type(ActionType::JavaScript)
+                .js_string(TextStr("this.print"));
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Catalog\n",
+        b"  /AcroForm 2 0 R\n",
+        b"  /OpenAction <<\n",
+        b"    /Type /Action\n",
+        b"    /S /JavaScript\n",
+        b"    /JS (this.print)\n",
+        b"  >>\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+
+    test!(
+        slice(|w| {
+            w.catalog(Ref::new(1)).form(Ref::new(2)).open_action_ref(Ref::new(3));
+            w.javascript(Ref::new(3)).js_string(TextStr("this.print"));
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Catalog\n",
+        b"  /AcroForm 2 0 R\n",
+        b"  /OpenAction 3 0 R\n",
+        b">>\nendobj\n\n",
+        b"3 0 obj\n<<\n",
+        b"  /Type /Action\n",
+        b"  /S /JavaScript\n",
+        b"  /JS (this.print)\n",
+        b">>\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_image_check_samples() {
+    let mut w = PdfWriter::new();
+    w.image_xobject(Ref::new(1), &[0; 12]).check_samples(2, 2, 3, 8);
+    w.image_xobject(Ref::new(2), &[0; 4]).check_samples(3, 4, 1, 1);
+
+    let mut image = w.image_xobject(Ref::new(3), &[0; 5]);
+    image.filter(Filter::FlateDecode);
+    image.check_samples(2, 2, 3, 8);
+}
+
+#[test]
+fn test_indexed_image_decode() {
+    test!(
+        slice(|w| {
+            w.image_xobject(Ref::new(1), &[0x01, 0x23])
+                .width(2)
+                .height(2)
+                .bits_per_component(4)
+                .indexed_decode(4);
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Length 2\n",
+        b"  /Type /XObject\n",
+        b"  /Subtype /Image\n",
+        b"  /Width 2\n",
+        b"  /Height 2\n",
+        b"  /BitsPerComponent 4\n",
+        b"  /Decode [0 15]\n",
+        b">>\nstream\n\x01\x23\nendstream\nendobj\n\n",
+    );
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(
+    expected = "image data length (is `11`) must be `12` for 2x2 pixels with 3 components of 8 bits"
+)]
+fn test_image_check_samples_mismatch() {
+    let mut w = PdfWriter::new();
+    w.image_xobject(Ref::new(1), &[0; 11]).check_samples(2, 2, 3, 8);
+}
+
+#[test]
+#[should_panic(
+    expected = "bits per component of an indexed image must be 1, 2, 4, 8 or 16 (is `31`)"
+)]
+fn test_image_indexed_decode_invalid_bits() {
+    let mut w = PdfWriter::new();
+    w.image_xobject(Ref::new(1), &[0; 4]).indexed_decode(31);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "image dimensions, components and bits must be positive")]
+fn test_image_check_samples_negative() {
+    let mut w = PdfWriter::new();
+    w.image_xobject(Ref::new(1), &[0; 12]).check_samples(-2, -2, 3, 8);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "angle has no effect for the Dissolve transition style")]
+fn test_transition_invalid_angle() {
+    let mut w = PdfWriter::new();
+    w.page(Ref::new(1))
+        .transition()
+        .style(TransitionStyle::Dissolve)
+        .angle(TransitionAngle::TopToBottom);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "opaque area has no effect for the Push transition style")]
+fn test_transition_invalid_attribute_before_style() {
+    let mut w = PdfWriter::new();
+    w.page(Ref::new(1))
+        .transition()
+        .opaque_area(true)
+        .style(TransitionStyle::Push);
+}
+
+#[test]
+fn test_thread_beads() {
+    test!(
+        slice(|w| {
+            w.thread(Ref::new(1))
+                .first(Ref::new(2))
+                .info()
+                .title(TextStr("Story"));
+            w.bead(Ref::new(2))
+                .thread(Ref::new(1))
+                .next(Ref::new(3))
+                .prev(Ref::new(3))
+                .page(Ref::new(4))
+                .rect(Rect::new(0.0, 0.0, 100.0, 200.0));
+            w.bead(Ref::new(3))
+                .next(Ref::new(2))
+                .prev(Ref::new(2))
+                .page(Ref::new(5))
+                .rect(Rect::new(0.0, 0.0, 100.0, 200.0));
+        }),
+        b"1 0 obj\n",
+        b"<<\n  /Type /Thread\n  /F 2 0 R\n  /I <<\n    /Title (Story)\n  >>\n>>\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"<<\n  /Type /Bead\n  /T 1 0 R\n  /N 3 0 R\n  /V 3 0 R\n",
+        b"  /P 4 0 R\n  /R [0 0 100 200]\n>>\n",
+        b"endobj\n\n",
+        b"3 0 obj\n",
+        b"<<\n  /Type /Bead\n  /N 2 0 R\n  /V 2 0 R\n",
+        b"  /P 5 0 R\n  /R [0 0 100 200]\n>>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_conformance_entries() {
+    test!(
+        slice(|w| {
+            w.catalog(Ref::new(1)).lang(TextStr("de-CH")).version(2, 0).needs_rendering(false);
+            w.page(Ref::new(2)).lang(TextStr("fr")).tab_order(TabOrder::StructureOrder);
+            w.page(Ref::new(3)).tab_order(TabOrder::WidgetOrder);
+        }),
+        b"1 0 obj\n",
+        b"<<\n  /Type /Catalog\n  /Lang (de-CH)\n  /Version /2#2E0\n  /NeedsRendering false\n>>\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"<<\n  /Type /Page\n  /Lang (fr)\n  /Tabs /S\n>>\n",
+        b"endobj\n\n",
+        b"3 0 obj\n",
+        b"<<\n  /Type /Page\n  /Tabs /W\n>>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_type3_font() {
+    let mut glyph = Content::new();
+    glyph.start_shape_glyph(500.0, 0.0, 0.0, 500.0, 500.0);
+    glyph.rect(0.0, 0.0, 500.0, 500.0).fill_nonzero();
+    let glyph = glyph.finish();
+
+    test!(
+        slice(|w| {
+            let mut font = w.type3_font(Ref::new(1));
+            font.bbox(Rect::new(0.0, 0.0, 500.0, 500.0));
+            font.matrix([0.001, 0.0, 0.0, 0.001, 0.0, 0.0]);
+            font.char_procs().pair(Name(b"square"), Ref::new(2));
+            font.encoding_custom()
+                .differences()
+                .consecutive(65, [Name(b"square")]);
+            font.first_char(65).last_char(65).widths([500.0]);
+            font.finish();
+            w.stream(Ref::new(2), &glyph);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Font\n",
+        b"  /Subtype /Type3\n",
+        b"  /FontBBox [0 0 500 500]\n",
+        b"  /FontMatrix [0.001 0 0 0.001 0 0]\n",
+        b"  /CharProcs <<\n",
+        b"    /square 2 0 R\n",
+        b"  >>\n",
+        b"  /Encoding <<\n",
+        b"    /Type /Encoding\n",
+        b"    /Differences [65 /square]\n",
+        b"  >>\n",
+        b"  /FirstChar 65\n",
+        b"  /LastChar 65\n",
+        b"  /Widths [500]\n",
+        b">>\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"<<\n  /Length 37\n>>\n",
+        b"stream\n",
+        b"500 0 0 0 500 500 d1\n0 0 500 500 re\nf\n",
+        b"endstream\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_encoding_differences() {
+    test_obj!(
+        |obj| {
+            let mut encoding = obj.start::<Encoding>();
+            encoding.base_encoding(Name(b"WinAnsiEncoding"));
+            encoding
+                .differences()
+                .consecutive(32, [Name(b"space"), Name(b"exclam")])
+                .consecutive(128, [Name(b"logo")]);
+        },
+        b"<<\n",
+        b"  /Type /Encoding\n",
+        b"  /BaseEncoding /WinAnsiEncoding\n",
+        b"  /Differences [32 /space /exclam 128 /logo]\n",
+        b">>",
+    );
+}
+
+#[test]
+fn test_cid_to_gid_map() {
+    assert_eq!(CidToGidMap::new(&[0, 1, 2, 3]), CidToGidMap::Identity);
+    assert_eq!(
+        CidToGidMap::new(&[0, 3, 258]),
+        CidToGidMap::Stream(vec![0x00, 0x00, 0x00, 0x03, 0x01, 0x02]),
+    );
+    test_obj!(
+        |obj| {
+            obj.start::<CidFont>().cid_to_gid_map_identity();
+        },
+        b"<<\n  /Type /Font\n  /CIDToGIDMap /Identity\n>>",
+    );
+}
+
+#[test]
+fn test_requirements() {
+    test_obj!(
+        |obj| {
+            let mut catalog = obj.start::<Catalog>();
+            let mut requirements = catalog.requirements();
+            requirements.push().kind(RequirementType::EnableJavaScripts);
+            let mut requirement = requirements.push();
+            requirement.kind(RequirementType::DigSig).version(2, 0);
+            requirement
+                .handlers()
+                .push()
+                .kind(RequirementHandlerType::JavaScript)
+                .script(TextStr("check"));
+        },
+        b"<<\n",
+        b"  /Type /Catalog\n",
+        b"  /Requirements [<<\n",
+        b"    /Type /Requirement\n",
+        b"    /S /EnableJavaScripts\n",
+        b"  >> <<\n",
+        b"    /Type /Requirement\n",
+        b"    /S /DigSig\n",
+        b"    /V /2#2E0\n",
+        b"    /RH [<<\n",
+        b"      /Type /ReqHandler\n",
+        b"      /S /JS\n",
+        b"      /Script (check)\n",
+        b"    >>]\n",
+        b"  >>]\n",
+        b">>",
+    );
+}
+
+#[test]
+fn test_named_destinations() {
+    test!(
+        slice(|w| {
+            w.catalog(Ref::new(1))
+                .names()
+                .destinations()
+                .names()
+                .insert(Str(b"chapter"), Ref::new(2))
+                .insert(Str(b"chapter1"), Ref::new(3))
+                .insert(Str("übersicht".as_bytes()), Ref::new(4));
+            w.destination(Ref::new(2)).page(Ref::new(10)).fit();
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Catalog\n",
+        b"  /Names <<\n",
+        b"    /Dests <<\n",
+        b"      /Names [(chapter) 2 0 R (chapter1) 3 0 R (\xC3\xBCbersicht) 4 0 R]\n",
+        b"    >>\n",
+        b"  >>\n",
+        b">>\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"[10 0 R /Fit]\n",
+        b"endobj\n\n",
+    );
+
+    test!(
+        slice(|w| {
+            w.catalog(Ref::new(1)).names().destinations().kids().item(Ref::new(2));
+            let mut tree = w.indirect(Ref::new(2)).start::<NameTree<Ref>>();
+            tree.limits(Str(b"intro"), Str(b"outro"));
+            tree.names()
+                .insert_destination(Str(b"intro"))
+                .page(Ref::new(10))
+                .xyz(0.0, 500.0, None);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Catalog\n",
+        b"  /Names <<\n",
+        b"    /Dests <<\n",
+        b"      /Kids [2 0 R]\n",
+        b"    >>\n",
+        b"  >>\n",
+        b">>\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"<<\n",
+        b"  /Limits [(intro) (outro)]\n",
+        b"  /Names [(intro) [10 0 R /XYZ 0 500 0]]\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "name tree keys must be inserted in ascending order")]
+fn test_name_tree_unsorted() {
+    test_obj!(
+        |obj| {
+            obj.start::<NameTree<Ref>>()
+                .names()
+                .insert(Str(b"b"), Ref::new(1))
+                .insert(Str(b"a"), Ref::new(2));
+        },
+        b"",
+    );
+}
+
+#[test]
+fn test_lab_color_space() {
+    test_obj!(
+        |obj| obj.start::<ColorSpace>().lab(
+            [0.9642, 1.0, 0.8251],
+            None,
+            Some([-128.0, 127.0, -100.0, 100.0])
+        ),
+        b"[/Lab <<\n",
+        b"  /WhitePoint [0.9642 1 0.8251]\n",
+        b"  /Range [-128 127 -100 100]\n",
+        b">>]",
+    );
+
+    // Computed white points are accepted despite rounding errors.
+    test_obj!(
+        |obj| obj
+            .start::<ColorSpace>()
+            .cal_gray([0.9505, 0.99999994, 1.089], None, None),
+        b"[/CalGray <<\n",
+        b"  /WhitePoint [0.9505 0.99999994 1.089]\n",
+        b">>]",
+    );
+}
+
+#[test]
+fn test_overprint() {
+    test!(
+        slice(|w| {
+            w.ext_graphics(Ref::new(1))
+                .overprint(true)
+                .overprint_fill(true)
+                .overprint_mode(OverprintMode::IgnoreZeroChannel);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /ExtGState\n",
+        b"  /OP true\n",
+        b"  /op true\n",
+        b"  /OPM 1\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_halftone_graphics_state() {
+    test!(
+        slice(|w| {
+            w.halftone(Ref::new(1))
+                .frequency(120.0)
+                .angle(15.0)
+                .spot_function(SpotFunction::Ellipse)
+                .accurate_screens(true);
+            w.ext_graphics(Ref::new(2))
+                .halftone(Ref::new(1))
+                .transfer_components([Ref::new(3), Ref::new(4), Ref::new(5), Ref::new(6)])
+                .black_generation(Ref::new(7))
+                .undercolor_removal(Ref::new(8));
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Halftone\n",
+        b"  /HalftoneType 1\n",
+        b"  /Frequency 120\n",
+        b"  /Angle 15\n",
+        b"  /SpotFunction /Ellipse\n",
+        b"  /AccurateScreens true\n",
+        b">>\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"<<\n",
+        b"  /Type /ExtGState\n",
+        b"  /HT 1 0 R\n",
+        b"  /TR [3 0 R 4 0 R 5 0 R 6 0 R]\n",
+        b"  /BG 7 0 R\n",
+        b"  /UCR 8 0 R\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_halftones() {
+    test!(
+        slice(|w| {
+            let mut composite = w.composite_halftone(Ref::new(1));
+            composite
+                .colorant(Name(b"Default"))
+                .frequency(60.0)
+                .angle(45.0)
+                .spot_functions([SpotFunction::Round, SpotFunction::SimpleDot]);
+            composite.colorant_ref(Name(b"Black"), Ref::new(2));
+            composite.finish();
+            w.threshold_halftone(Ref::new(2), &[0, 128, 64, 192])
+                .dimensions(2, 2)
+                .transfer_function_identity();
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Halftone\n",
+        b"  /HalftoneType 5\n",
+        b"  /Default <<\n",
+        b"    /Type /Halftone\n",
+        b"    /HalftoneType 1\n",
+        b"    /Frequency 60\n",
+        b"    /Angle 45\n",
+        b"    /SpotFunction [/Round /SimpleDot]\n",
+        b"  >>\n",
+        b"  /Black 2 0 R\n",
+        b">>\n",
+        b"endobj\n\n",
+        b"2 0 obj\n",
+        b"<<\n",
+        b"  /Length 4\n",
+        b"  /Type /Halftone\n",
+        b"  /HalftoneType 6\n",
+        b"  /Width 2\n",
+        b"  /Height 2\n",
+        b"  /TransferFunction /Identity\n",
+        b">>\n",
+        b"stream\n\x00\x80\x40\xC0\nendstream\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_stream_filter_chain() {
+    test!(
+        slice(|w| {
+            let mut stream = w.stream(Ref::new(1), b"data");
+            stream.filters([Filter::Ascii85Decode, Filter::FlateDecode]);
+            stream.decode_parms_array().null().push().predictor(12).columns(4);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Length 4\n",
+        b"  /Filter [/ASCII85Decode /FlateDecode]\n",
+        b"  /DecodeParms [null <<\n",
+        b"    /Predictor 12\n",
+        b"    /Columns 4\n",
+        b"  >>]\n",
+        b">>\n",
+        b"stream\ndata\nendstream\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "decode parameters must be written for every filter")]
+fn test_stream_filter_chain_missing_parms() {
+    let mut w = PdfWriter::new();
+    let mut stream = w.stream(Ref::new(1), b"data");
+    stream.filters([Filter::Ascii85Decode, Filter::FlateDecode]);
+    stream.decode_parms_array().null();
+}
+
+#[test]
+fn test_rectilinear_viewport() {
+    test!(
+        slice(|w| {
+            let mut page = w.page(Ref::new(1));
+            let mut viewports = page.viewports();
+            let mut viewport = viewports.push();
+            viewport.bbox(Rect::new(50.0, 50.0, 550.0, 400.0));
+            viewport.name(TextStr("Floor plan"));
+            let mut measure = viewport.measure();
+            measure.subtype(MeasureType::Rectilinear);
+            measure.scale_ratio(TextStr("1 in = 10 ft"));
+            measure
+                .x_axis()
+                .push()
+                .unit(TextStr("ft"))
+                .conversion_factor(0.1389)
+                .fractions(FractionFormat::Decimal)
+                .denominator(100);
+            measure.distance().push().unit(TextStr("ft")).conversion_factor(1.0);
+            measure.area().push().unit(TextStr("sq ft")).conversion_factor(1.0);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Type /Page\n",
+        b"  /VP [<<\n",
+        b"    /Type /Viewport\n",
+        b"    /BBox [50 50 550 400]\n",
+        b"    /Name (Floor plan)\n",
+        b"    /Measure <<\n",
+        b"      /Type /Measure\n",
+        b"      /Subtype /RL\n",
+        b"      /R (1 in = 10 ft)\n",
+        b"      /X [<<\n",
+        b"        /Type /NumberFormat\n",
+        b"        /U (ft)\n",
+        b"        /C 0.1389\n",
+        b"        /F /D\n",
+        b"        /D 100\n",
+        b"      >>]\n",
+        b"      /D [<<\n",
+        b"        /Type /NumberFormat\n",
+        b"        /U (ft)\n",
+        b"        /C 1\n",
+        b"      >>]\n",
+        b"      /A [<<\n",
+        b"        /Type /NumberFormat\n",
+        b"        /U (sq ft)\n",
+        b"        /C 1\n",
+        b"      >>]\n",
+        b"    >>\n",
+        b"  >>]\n",
+        b">>\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+fn test_geospatial_measure() {
+    test!(
+        slice(|w| {
+            let mut image = w.image_xobject(Ref::new(1), &[]);
+            let mut measure = image.measure();
+            measure.subtype(MeasureType::Geospatial);
+            measure.bounds([0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0]);
+            measure.gcs().kind(CoordinateSystemType::Geographic).epsg(4326);
+            measure.geo_points([47.0, 8.0, 47.0, 9.0, 48.0, 9.0]);
+            measure.layout_points([0.0, 0.0, 1.0, 0.0, 1.0, 1.0]);
+        }),
+        b"1 0 obj\n",
+        b"<<\n",
+        b"  /Length 0\n",
+        b"  /Type /XObject\n",
+        b"  /Subtype /Image\n",
+        b"  /Measure <<\n",
+        b"    /Type /Measure\n",
+        b"    /Subtype /GEO\n",
+        b"    /Bounds [0 0 0 1 1 1 1 0]\n",
+        b"    /GCS <<\n",
+        b"      /Type /GEOGCS\n",
+        b"      /EPSG 4326\n",
+        b"    >>\n",
+        b"    /GPTS [47 8 47 9 48 9]\n",
+        b"    /LPTS [0 0 1 0 1 1]\n",
+        b"  >>\n",
+        b">>\n",
+        b"stream\n\nendstream\n",
+        b"endobj\n\n",
+    );
+}
+
+#[test]
+#[should_panic(expected = "geo and layout points must have the same length")]
+fn test_geospatial_measure_mismatch() {
+    test_obj!(
+        |obj| {
+            obj.start::<Measure>()
+                .geo_points([47.0, 8.0, 47.0, 9.0])
+                .layout_points([0.0, 0.0]);
+        },
+        b"",
+    );
+}
+
+#[test]
+fn test_form_default_resources() {
+    test!(
+        slice(|w| {
+            let mut form = w.form(Ref::new(1));
+            form.fields([Ref::new(2)]).need_appearances(false);
+            form.default_resources().fonts().pair(Name(b"Helv"), Ref::new(5));
+            form.default_appearance(Str(b"/Helv 0 Tf 0 g"));
+            form.finish();
+
+            let mut appearance = DefaultAppearance::new();
+            appearance.font(Name(b"Helv"), 12.0).gray(0.0);
+            let appearance = appearance.finish();
+            w.form_field(Ref::new(2))
+                .field_type(FieldType::Text)
+                .partial_name(TextStr("name"))
+                .field_flags(FieldFlags::REQUIRED)
+                .text_value(TextStr("Hi"))
+                .default_appearance(Str(&appearance))
+                .quadding(Quadding::Center);
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Fields [2 0 R]\n",
+        b"  /NeedAppearances false\n",
+        b"  /DR <<\n    /Font <<\n      /Helv 5 0 R\n    >>\n  >>\n",
+        b"  /DA (/Helv 0 Tf 0 g)\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n<<\n",
+        b"  /FT /Tx\n",
+        b"  /T (name)\n",
+        b"  /Ff 2\n",
+        b"  /V (Hi)\n",
+        b"  /DA (/Helv 12 Tf\n0 g)\n",
+        b"  /Q 1\n",
+        b">>\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_form_field_rich_value() {
+    test!(
+        slice(|w| {
+            w.form_field(Ref::new(1))
+                .field_type(FieldType::Text)
+                .field_flags(FieldFlags::RICH_TEXT)
+                .text_value(TextStr("Hi"))
+                .default_style(TextStr("font: 12pt Helvetica"))
+                .rich_value(TextStr("<body><p><b>Hi</b></p></body>"));
+            w.form_field(Ref::new(2))
+                .field_type(FieldType::Text)
+                .field_flags(FieldFlags::RICH_TEXT)
+                .text_value(TextStr("Long"))
+                .rich_value_ref(Ref::new(3));
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /FT /Tx\n",
+        b"  /Ff 33554432\n",
+        b"  /V (Hi)\n",
+        b"  /DS (font: 12pt Helvetica)\n",
+        b"  /RV (<body><p><b>Hi</b></p></body>)\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n<<\n",
+        b"  /FT /Tx\n",
+        b"  /Ff 33554432\n",
+        b"  /V (Long)\n",
+        b"  /RV 3 0 R\n",
+        b">>\nendobj\n\n",
+    );
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(
+    expected = "font `/Helv` used in default appearance is missing from the form's default resources"
+)]
+fn test_form_missing_default_font() {
+    let mut w = PdfWriter::new();
+    w.form(Ref::new(1))
+        .default_resources()
+        .fonts()
+        .pair(Name(b"Cour"), Ref::new(3));
+    w.form_field(Ref::new(2)).default_appearance(Str(b"/Helv 12 Tf 0 g"));
+    w.into_buf();
+}
+
+#[test]
+fn test_form_unregistered_default_font() {
+    // Without any default resources.
+    let mut w = PdfWriter::new();
+    w.form(Ref::new(1)).fields([Ref::new(2)]);
+    w.form_field(Ref::new(2)).default_appearance(Str(b"/Helv 12 Tf 0 g"));
+    assert_eq!(w.finish(), Err(PdfError::MissingFormFont(b"Helv".to_vec())));
+
+    // With fonts written through the dictionary the form derefs to.
+    let mut w = PdfWriter::new();
+    let mut form = w.form(Ref::new(1));
+    form.insert(Name(b"DR"))
+        .dict()
+        .insert(Name(b"Font"))
+        .dict()
+        .pair(Name(b"Helv"), Ref::new(3));
+    form.default_appearance(Str(b"/Helv 0 Tf 0 g"));
+    form.finish();
+    assert_eq!(w.finish(), Err(PdfError::MissingFormFont(b"Helv".to_vec())));
+}
+
+#[test]
+fn test_form_default_font_registration() {
+    let mut w = PdfWriter::new();
+    w.catalog(Ref::new(1)).pages(Ref::new(6)).form(Ref::new(2));
+
+    let mut form = w.form(Ref::new(2));
+    let mut resources = form.default_resources();
+    let mut fonts = resources.fonts();
+    fonts.insert(Name(b"Helv")).dict().pair(Name(b"Type"), Name(b"Font"));
+    fonts.pair(Name(b"F 1"), Ref::new(3));
+    fonts.finish();
+    resources.finish();
+    form.finish();
+
+    let mut appearance = DefaultAppearance::new();
+    appearance.font(Name(b"F 1"), 10.0).gray(0.0);
+    w.form_field(Ref::new(4))
+        .default_appearance(Str(&appearance.finish()));
+    w.form_field(Ref::new(5)).default_appearance(Str(b"/Helv 12 Tf 0 g"));
+    w.indirect(Ref::new(3)).dict().pair(Name(b"Type"), Name(b"Font"));
+    w.pages(Ref::new(6)).kids([Ref::new(7)]).count(1);
+    w.page(Ref::new(7)).parent(Ref::new(6));
+    assert!(w.finish().is_ok());
+}
+
+#[test]
+fn test_page_builder() {
+    test!(
+        slice(|w| {
+            let mut alloc = RefAllocator::starting_at(Ref::new(10));
+            let mut page = PageBuilder::new(Rect::new(0.0, 0.0, 100.0, 200.0));
+            let font = page.font(Ref::new(1));
+            let image = page.image(Ref::new(2));
+            let state = page.ext_g_state(Ref::new(3));
+            assert_eq!(page.image(Ref::new(2)), image);
+            assert_eq!(page.font(Ref::new(4)), b"F2");
+            page.content()
+                .set_font(Name(&font), 12.0)
+                .set_parameters(Name(&state))
+                .x_object(Name(&image));
+            page.annotation(Ref::new(5));
+            assert_eq!(page.finish(w, &mut alloc, Ref::new(6)), Ref::new(10));
+            assert_eq!(alloc.bump(), Ref::new(12));
+        }),
+        b"11 0 obj\n<<\n  /Length 25\n>>\nstream\n",
+        b"/F1 12 Tf\n/GS1 gs\n/Im1 Do\nendstream\nendobj\n\n",
+        b"10 0 obj\n<<\n",
+        b"  /Type /Page\n",
+        b"  /Parent 6 0 R\n",
+        b"  /MediaBox [0 0 100 200]\n",
+        b"  /Contents 11 0 R\n",
+        b"  /Resources <<\n",
+        b"    /Font <<\n      /F1 1 0 R\n      /F2 4 0 R\n    >>\n",
+        b"    /XObject <<\n      /Im1 2 0 R\n    >>\n",
+        b"    /ExtGState <<\n      /GS1 3 0 R\n    >>\n",
+        b"  >>\n",
+        b"  /Annots [5 0 R]\n",
+        b">>\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_page_group() {
+    test!(
+        slice(|w| {
+            let mut alloc = RefAllocator::starting_at(Ref::new(10));
+            let page = PageBuilder::new(Rect::new(0.0, 0.0, 10.0, 10.0));
+            page.finish_with(w, &mut alloc, Ref::new(1), |page| {
+                page.group().transparency().isolated(true).color_space().device_rgb();
+            });
+            w.page(Ref::new(2))
+                .group()
+                .transparency()
+                .knockout(true)
+                .color_space_ref(Ref::new(3));
+        }),
+        b"11 0 obj\n<<\n  /Length 0\n>>\nstream\n\nendstream\nendobj\n\n",
+        b"10 0 obj\n<<\n",
+        b"  /Type /Page\n",
+        b"  /Parent 1 0 R\n",
+        b"  /MediaBox [0 0 10 10]\n",
+        b"  /Contents 11 0 R\n",
+        b"  /Resources <<>>\n",
+        b"  /Group <<\n",
+        b"    /Type /Group\n",
+        b"    /S /Transparency\n",
+        b"    /I true\n",
+        b"    /CS /DeviceRGB\n",
+        b"  >>\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n<<\n",
+        b"  /Type /Page\n",
+        b"  /Group <<\n",
+        b"    /Type /Group\n",
+        b"    /S /Transparency\n",
+        b"    /K true\n",
+        b"    /CS 3 0 R\n",
+        b"  >>\n",
+        b">>\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_profile() {
+    let write = |w: &mut PdfWriter| {
+        w.page(Ref::new(1)).resources().fonts().pair(Name(b"F1"), Ref::new(2));
+        w.type1_font(Ref::new(2))
+            .base_font(Name(b"Helvetica"))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+        let mut annot = w.annotation(Ref::new(3));
+        annot.subtype(AnnotationType::Screen);
+        annot.border_style().width(2.0);
+    };
+
+    test!(
+        slice(write),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Page\n",
+        b"  /Resources <<\n",
+        b"    /Font <<\n",
+        b"      /F1 2 0 R\n",
+        b"    >>\n",
+        b"  >>\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n<<\n",
+        b"  /Type /Font\n",
+        b"  /Subtype /Type1\n",
+        b"  /BaseFont /Helvetica\n",
+        b"  /Encoding /WinAnsiEncoding\n",
+        b">>\nendobj\n\n",
+        b"3 0 obj\n<<\n",
+        b"  /Type /Annot\n",
+        b"  /Subtype /Screen\n",
+        b"  /BS <<\n",
+        b"    /Type /Border\n",
+        b"    /W 2\n",
+        b"  >>\n",
+        b">>\nendobj\n\n",
+    );
+
+    let mut w = PdfWriter::new();
+    assert_eq!(w.profile(), Profile::Modern17);
+    write(&mut w);
+    assert!(!w
+        .validate()
+        .iter()
+        .any(|w| matches!(w, Warning::RequiresVersion { .. })));
+
+    let mut w = PdfWriter::new();
+    w.set_profile(Profile::Legacy14);
+    write(&mut w);
+    assert!(w.validate().contains(&Warning::RequiresVersion {
+        object: Ref::new(3),
+        feature: "screen annotations",
+        version: (1, 5),
+    }));
+
+    let buf = w.into_buf();
+    let text = String::from_utf8_lossy(&buf);
+    assert!(text.starts_with("%PDF-1.4\n"));
+    assert!(text.contains("    /ProcSet [/PDF /Text /ImageB /ImageC /ImageI]\n  >>\n"));
+    assert!(text.contains("    /W 2\n  >>\n  /Border [0 0 2]\n"));
+    if cfg!(feature = "standard14") {
+        assert!(
+            text.contains("  /FirstChar 32\n  /LastChar 255\n  /Widths [278 278 355 ")
+        );
+    }
+
+    let mut w = PdfWriter::new();
+    w.set_profile(Profile::Legacy14);
+    w.page(Ref::new(1)).resources().proc_sets([ProcSet::Pdf]);
+    let mut annot = w.annotation(Ref::new(2));
+    annot.border(0.0, 0.0, 1.0, None);
+    annot.border_style().width(2.0);
+    annot.finish();
+    w.type1_font(Ref::new(3)).base_font(Name(b"Courier"));
+    let buf = w.into_buf();
+    let text = String::from_utf8_lossy(&buf);
+    assert_eq!(text.matches("/ProcSet").count(), 1);
+    assert_eq!(text.matches("/Border [").count(), 1);
+    assert!(!text.contains("/Widths"));
+
+    let mut w = PdfWriter::new();
+    w.set_profile(Profile::Pdf20);
+    assert!(w.into_buf().starts_with(b"%PDF-2.0\n"));
+}
+
+#[test]
+fn test_chunk_deduplication() {
+    let write = |data: &'static [u8]| {
+        let mut chunk = Chunk::new();
+        chunk.stream(Ref::new(1), data).pair(Name(b"Parent"), Ref::new(2));
+        chunk
+            .indirect(Ref::new(2))
+            .dict()
+            .pair(Name(b"A1"), Ref::new(1))
+            .pair(Name(b"Title"), Str(b"see 1 0 R"))
+            .insert(Name(b"Kids"))
+            .array()
+            .items([Ref::new(2), Ref::new(10)])
+            .item(1.5)
+            .item(0);
+        chunk
+    };
+
+    let mut first = write(b"2 0 R");
+    assert_eq!(first.as_bytes(), write(b"2 0 R").as_bytes());
+    assert_ne!(first.as_bytes(), write(b"3 0 R").as_bytes());
+
+    first.renumber(|id| Ref::new(id.get() + 2));
+    test!(
+        slice(|w| w.extend(&first)),
+        b"3 0 obj\n<<\n  /Length 5\n  /Parent 4 0 R\n>>\nstream\n2 0 R\nendstream\n",
+        b"endobj\n\n",
+        b"4 0 obj\n<<\n",
+        b"  /A1 3 0 R\n",
+        b"  /Title (see 1 0 R)\n",
+        b"  /Kids [4 0 R 10 0 R 1.5 0]\n",
+        b">>\nendobj\n\n",
+    );
+}
+
+#[test]
+#[cfg(feature = "flate")]
+fn test_embedded_file_compressed() {
+    let data = b"Hello, Hello, Hello, Hello, World!".repeat(8);
+    let buf = slice(|w| {
+        w.embedded_file_compressed(Ref::new(1), &data)
+            .subtype(Name(b"text/plain"))
+            .params()
+            .checksum_from_bytes(&data);
+    });
+
+    let text = String::from_utf8_lossy(&buf);
+    assert!(text.contains("/Filter /FlateDecode\n"));
+    assert!(text.contains(&format!("/Size {}\n", data.len())));
+
+    let start = buf.windows(10).position(|w| w == b">>\nstream\n").unwrap() + 10;
+    let end = buf.len() - b"\nendstream\nendobj\n\n".len();
+    let inflated = miniz_oxide::inflate::decompress_to_vec_zlib(&buf[start..end]);
+    assert_eq!(inflated.unwrap(), data);
+
+    let buf = slice(|w| {
+        w.embedded_file_compressed(Ref::new(1), &data)
+            .insert(Name(b"Params"))
+            .dict()
+            .pair(Name(b"Size"), 1);
+    });
+
+    let text = String::from_utf8_lossy(&buf);
+    assert_eq!(text.matches("/Params").count(), 1);
+    assert_eq!(text.matches("/Size").count(), 1);
+}
+
+#[test]
+#[cfg(feature = "standard14")]
+fn test_standard14_widths() {
+    use pdf_writer::types::Standard14Font;
+    use pdf_writer::{standard14_widths, text_width};
+
+    let widths: Vec<f32> = standard14_widths(Standard14Font::Helvetica).collect();
+    assert_eq!(widths.len(), 224);
+    assert_eq!(widths[usize::from(b'H' - 32)], 722.0);
+    assert_eq!(widths[0xE9 - 32], 556.0);
+    assert!(standard14_widths(Standard14Font::CourierBold).all(|w| w == 600.0));
+    assert_eq!(standard14_widths(Standard14Font::Symbol).nth(0x80 - 32), Some(0.0));
+
+    assert_eq!(text_width(Standard14Font::Helvetica, 10.0, b"Hello"), 22.78);
+    assert_eq!(text_width(Standard14Font::HelveticaOblique, 10.0, b"\nHello"), 22.78);
+    assert_eq!(text_width(Standard14Font::TimesRoman, 12.0, b"1.00"), 21.0);
+    assert_eq!(Standard14Font::TimesBoldItalic.base_font(), Name(b"Times-BoldItalic"));
+    assert!(Standard14Font::ZapfDingbats.is_symbolic());
+}
+
+#[test]
+fn test_collection_schema() {
+    let buf = slice(|w| {
+        let mut catalog = w.catalog(Ref::new(1));
+        let mut collection = catalog.collection();
+        let mut schema = collection.schema();
+        schema
+            .field(Name(b"Description"))
+            .subtype(CollectionFieldType::Text)
+            .name(TextStr("Description"))
+            .order(0);
+        schema
+            .field(Name(b"Modified"))
+            .subtype(CollectionFieldType::Date)
+            .name(TextStr("Modified"))
+            .order(1)
+            .visible(true);
+        schema.finish();
+        collection.sort().key(Name(b"Modified")).ascending(false);
+    });
+
+    test!(
+        buf,
+        b"1 0 obj\n<<\n  /Type /Catalog\n",
+        b"  /Collection <<\n    /Type /Collection\n",
+        b"    /Schema <<\n      /Type /CollectionSchema\n",
+        b"      /Description <<\n",
+        b"        /Type /CollectionField\n",
+        b"        /Subtype /S\n",
+        b"        /N (Description)\n",
+        b"        /O 0\n",
+        b"      >>\n",
+        b"      /Modified <<\n",
+        b"        /Type /CollectionField\n",
+        b"        /Subtype /D\n",
+        b"        /N (Modified)\n",
+        b"        /O 1\n",
+        b"        /V true\n",
+        b"      >>\n    >>\n",
+        b"    /Sort <<\n",
+        b"      /Type /CollectionSort\n",
+        b"      /S /Modified\n",
+        b"      /A false\n",
+        b"    >>\n  >>\n>>\nendobj\n\n",
+    );
+
+    test_obj!(
+        |obj| {
+            obj.start::<CollectionSort>()
+                .keys([Name(b"Modified"), Name(b"Description")])
+                .ascending_each([false, true]);
+        },
+        b"<<\n  /Type /CollectionSort\n",
+        b"  /S [/Modified /Description]\n",
+        b"  /A [false true]\n>>",
+    );
+}
+
+#[test]
+fn test_written_objects() {
+    let mut w = PdfWriter::new();
+    assert_eq!(w.written_ids().len(), 0);
+    assert!(!w.has(Ref::new(1)));
+
+    w.catalog(Ref::new(3)).pages(Ref::new(1));
+    let start = w.len();
+    w.pages(Ref::new(1)).count(0);
+    let size = w.len() - start;
+    w.stream(Ref::new(2), b"data");
+
+    assert_eq!(
+        w.written_ids().collect::<Vec<_>>(),
+        [Ref::new(3), Ref::new(1), Ref::new(2)]
+    );
+    assert!(w.has(Ref::new(2)));
+    assert!(!w.has(Ref::new(4)));
+    assert_eq!(w.offset(Ref::new(1)), Some(start));
+    assert_eq!(w.offset(Ref::new(4)), None);
+
+    let offsets: Vec<_> = w.written_ids().map(|id| w.offset(id).unwrap()).collect();
+    let buf = w.into_buf();
+    for (id, offset) in [3, 1, 2].into_iter().zip(offsets) {
+        assert!(buf[offset..].starts_with(format!("{id} 0 obj\n").as_bytes()));
+    }
+    assert!(buf[start + size - 8..].starts_with(b"endobj\n\n"));
+}
+
+#[test]
+fn test_chunk_renumber() {
+    fn assert_send<T: Send>(_: &T) {}
+
+    let mut chunk = Chunk::new();
+    chunk.indirect(Ref::new(1)).dict().pair(Name(b"Next"), Ref::new(2));
+    chunk.stream(Ref::new(2), b"1 0 R");
+    chunk.renumber(|id| Ref::new(id.get() + 10));
+    assert_send(&chunk);
+    assert_eq!(chunk.refs().collect::<Vec<_>>(), [Ref::new(11), Ref::new(12)]);
+
+    test!(
+        slice(|w| {
+            w.indirect(Ref::new(1)).primitive(Null);
+            w.extend(&chunk);
+        }),
+        b"1 0 obj\nnull\nendobj\n\n",
+        b"11 0 obj\n<<\n  /Next 12 0 R\n>>\nendobj\n\n",
+        b"12 0 obj\n<<\n  /Length 5\n>>\nstream\n1 0 R\nendstream\nendobj\n\n",
+    );
+
+    let mut w = PdfWriter::new();
+    w.extend(&chunk);
+    let buf = w.into_buf();
+    let xref = b"0000000016 00000 n\r\n0000000054 00000 n\r\ntrailer\n";
+    assert!(buf.windows(xref.len()).any(|w| w == xref));
+}
+
+#[test]
+fn test_chunk_renumber_allocator() {
+    // Every id is only mapped once, even though it occurs multiple times.
+    let mut chunk = Chunk::new();
+    chunk.indirect(Ref::new(1)).array().items([Ref::new(2), Ref::new(1)]);
+    chunk.indirect(Ref::new(2)).primitive(Ref::new(1));
+    let mut alloc = RefAllocator::starting_at(Ref::new(5));
+    chunk.renumber(|_| alloc.bump());
+    assert_eq!(alloc.bump(), Ref::new(7));
+    assert_eq!(chunk.refs().collect::<Vec<_>>(), [Ref::new(5), Ref::new(6)]);
+    test!(
+        chunk.as_bytes().to_vec(),
+        b"5 0 obj\n[6 0 R 5 0 R]\nendobj\n\n",
+        b"6 0 obj\n5 0 R\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_chunk_renumber_external() {
+    // The page tree and the font are written outside of the chunk, so only
+    // the references to the page and its content stream are renumbered.
+    let mut chunk = Chunk::new();
+    let mut page = chunk.indirect(Ref::new(10)).start::<Page>();
+    page.parent(Ref::new(2)).contents(Ref::new(11));
+    page.resources().fonts().pair(Name(b"F1"), Ref::new(3));
+    page.finish();
+    chunk.stream(Ref::new(11), b"BT /F1 12 Tf ET");
+
+    let mut alloc = RefAllocator::starting_at(Ref::new(4));
+    chunk.renumber(|_| alloc.bump());
+    test!(
+        chunk.as_bytes().to_vec(),
+        b"4 0 obj\n<<\n",
+        b"  /Type /Page\n",
+        b"  /Parent 2 0 R\n",
+        b"  /Contents 5 0 R\n",
+        b"  /Resources <<\n    /Font <<\n      /F1 3 0 R\n    >>\n  >>\n",
+        b">>\nendobj\n\n",
+        b"5 0 obj\n<<\n  /Length 15\n>>\nstream\nBT /F1 12 Tf ET\nendstream\nendobj\n\n",
+    );
+
+    let mut w = PdfWriter::new();
+    w.catalog(Ref::new(1)).pages(Ref::new(2));
+    w.pages(Ref::new(2)).kids([Ref::new(4)]).count(1);
+    w.type1_font(Ref::new(3)).base_font(Name(b"Helvetica"));
+    w.extend(&chunk);
+    assert!(w.finish().is_ok());
+}
+
+#[test]
+fn test_default_color_spaces() {
+    test!(
+        slice(|w| {
+            let mut page = w.page(Ref::new(1));
+            page.resources()
+                .color_spaces()
+                .default_rgb(Ref::new(2))
+                .default_cmyk(Ref::new(3))
+                .default_gray(Ref::new(4))
+                .pair(Name(b"sRGB"), Ref::new(2));
+            page.finish();
+            w.color_space(Ref::new(2)).icc_based(Ref::new(5));
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Page\n",
+        b"  /Resources <<\n",
+        b"    /ColorSpace <<\n",
+        b"      /DefaultRGB 2 0 R\n",
+        b"      /DefaultCMYK 3 0 R\n",
+        b"      /DefaultGray 4 0 R\n",
+        b"      /sRGB 2 0 R\n",
+        b"    >>\n",
+        b"  >>\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n[/ICCBased 5 0 R]\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_default_rgb_icc_based() {
+    test!(
+        slice(|w| {
+            w.page(Ref::new(1))
+                .resources()
+                .color_spaces()
+                .default_rgb(Ref::new(2));
+            w.color_space(Ref::new(2)).icc_based(Ref::new(3));
+            w.icc_profile(Ref::new(3), b"icc").n(3);
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Page\n",
+        b"  /Resources <<\n",
+        b"    /ColorSpace <<\n",
+        b"      /DefaultRGB 2 0 R\n",
+        b"    >>\n",
+        b"  >>\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n[/ICCBased 3 0 R]\nendobj\n\n",
+        b"3 0 obj\n<<\n  /Length 3\n  /N 3\n>>\nstream\nicc\nendstream\nendobj\n\n",
+    );
+}
+
+#[test]
+fn test_output_intents() {
+    test!(
+        slice(|w| {
+            w.catalog(Ref::new(1))
+                .typed_output_intents()
+                .push()
+                .subtype(OutputIntentSubtype::PDFX)
+                .output_condition_identifier(TextStr("FOGRA39"))
+                .dest_output_profile(Ref::new(3));
+
+            let mut page = w.page(Ref::new(2));
+            let mut intents = page.output_intents();
+            let mut intent = intents.push();
+            intent.subtype(OutputIntentSubtype::PDFX);
+            intent
+                .dest_output_profile_ref()
+                .checksum_from_bytes(b"")
+                .icc_version([4, 2, 0, 0])
+                .profile_color_space(Str(b"CMYK"))
+                .profile_name(TextStr("Coated FOGRA39"))
+                .urls()
+                .push()
+                .file_system(Name(b"URL"))
+                .path(Str(b"https://example.com/fogra39.icc"));
+        }),
+        b"1 0 obj\n<<\n",
+        b"  /Type /Catalog\n",
+        b"  /OutputIntents [<<\n",
+        b"    /Type /OutputIntent\n",
+        b"    /S /GTS#5FPDFX\n",
+        b"    /OutputConditionIdentifier (FOGRA39)\n",
+        b"    /DestOutputProfile 3 0 R\n",
+        b"  >>]\n",
+        b">>\nendobj\n\n",
+        b"2 0 obj\n<<\n",
+        b"  /Type /Page\n",
+        b"  /OutputIntents [<<\n",
+        b"    /Type /OutputIntent\n",
+        b"    /S /GTS#5FPDFX\n",
+        b"    /DestOutputProfileRef <<\n",
+        b"      /CheckSum (\xd4\x1d\x8c\xd9\x8f\x00\xb2\x04\xe9\x80\x09\x98\xec\xf8\x42\x7e)\n",
+        b"      /ICCVersion (\x04\x02\x00\x00)\n",
+        b"      /ProfileCS (CMYK)\n",
+        b"      /ProfileName (Coated FOGRA39)\n",
+        b"      /URLs [<<\n",
+        b"        /Type /Filespec\n",
+        b"        /FS /URL\n",
+        b"        /F (https://example.com/fogra39.icc)\n",
+        b"      >>]\n",
+        b"    >>\n",
+        b"  >>]\n",
+        b">>\nendobj\n\n",
+    );
+}